@@ -0,0 +1,49 @@
+// Extract the documentation of each rule from its source file, so that only
+// the documentation is embedded in the binary, see `RuleId::documentation()`.
+//
+// For each rule in `src/lints/<name>/<name>.rs`, this writes the doc comment
+// that starts with `## What it does`, without the leading `///`, to
+// `$OUT_DIR/docs/<name>.md`. The file is empty if the rule isn't documented.
+
+use std::path::Path;
+
+fn main() {
+    println!("cargo::rerun-if-changed=src/lints");
+
+    let out_dir = std::env::var("OUT_DIR").expect("`OUT_DIR` is set by cargo");
+    let docs_dir = Path::new(&out_dir).join("docs");
+    std::fs::create_dir_all(&docs_dir).expect("Failed to create the directory of the docs");
+
+    for entry in std::fs::read_dir("src/lints").expect("Failed to read `src/lints`") {
+        let path = entry.expect("Failed to read `src/lints`").path();
+        if !path.is_dir() {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+
+        let source = std::fs::read_to_string(path.join(format!("{name}.rs")))
+            .unwrap_or_else(|e| panic!("Failed to read the source of rule `{name}`: {e}"));
+        std::fs::write(
+            docs_dir.join(format!("{name}.md")),
+            extract_documentation(&source),
+        )
+        .unwrap_or_else(|e| panic!("Failed to write the documentation of rule `{name}`: {e}"));
+    }
+}
+
+/// Extract the doc comment that starts with `## What it does` in the source
+/// of a rule, without the leading `///`.
+fn extract_documentation(source: &str) -> String {
+    source
+        .lines()
+        .skip_while(|line| !line.contains("## What it does"))
+        .take_while(|line| line.trim_start().starts_with("///"))
+        .map(|line| {
+            let line = line.trim_start().trim_start_matches("///");
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
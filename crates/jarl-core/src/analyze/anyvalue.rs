@@ -1,16 +1,8 @@
+use crate::analyze::run_rules;
 use crate::check::Checker;
+use crate::lints::{Hook, anyvalue_rules};
 use air_r_syntax::AnyRValue;
-use biome_rowan::AstNode;
 
-use crate::lints::numeric_leading_zero::numeric_leading_zero::numeric_leading_zero;
-
-pub fn anyvalue(r_expr: &AnyRValue, checker: &mut Checker) -> anyhow::Result<()> {
-    let node = r_expr.syntax();
-
-    if checker.is_rule_enabled("numeric_leading_zero")
-        && !checker.should_skip_rule(node, "numeric_leading_zero")
-    {
-        checker.report_diagnostic(numeric_leading_zero(r_expr)?);
-    }
-    Ok(())
+pub fn anyvalue(r_expr: &AnyRValue, hook: Hook, checker: &mut Checker) -> anyhow::Result<()> {
+    run_rules(r_expr, anyvalue_rules(hook), checker)
 }
@@ -1,44 +1,12 @@
+use crate::analyze::run_rules;
 use crate::check::Checker;
+use crate::lints::{Hook, binary_expression_rules};
 use air_r_syntax::RBinaryExpression;
-use biome_rowan::AstNode;
 
-use crate::lints::assignment::assignment::assignment;
-use crate::lints::class_equals::class_equals::class_equals;
-use crate::lints::empty_assignment::empty_assignment::empty_assignment;
-use crate::lints::equals_na::equals_na::equals_na;
-use crate::lints::implicit_assignment::implicit_assignment::implicit_assignment;
-use crate::lints::is_numeric::is_numeric::is_numeric;
-use crate::lints::redundant_equals::redundant_equals::redundant_equals;
-
-pub fn binary_expression(r_expr: &RBinaryExpression, checker: &mut Checker) -> anyhow::Result<()> {
-    let node = r_expr.syntax();
-
-    if checker.is_rule_enabled("assignment") && !checker.should_skip_rule(node, "assignment") {
-        checker.report_diagnostic(assignment(r_expr, checker.assignment_op)?);
-    }
-    if checker.is_rule_enabled("class_equals") && !checker.should_skip_rule(node, "class_equals") {
-        checker.report_diagnostic(class_equals(r_expr)?);
-    }
-    if checker.is_rule_enabled("empty_assignment")
-        && !checker.should_skip_rule(node, "empty_assignment")
-    {
-        checker.report_diagnostic(empty_assignment(r_expr)?);
-    }
-    if checker.is_rule_enabled("equals_na") && !checker.should_skip_rule(node, "equals_na") {
-        checker.report_diagnostic(equals_na(r_expr)?);
-    }
-    if checker.is_rule_enabled("implicit_assignment")
-        && !checker.should_skip_rule(node, "implicit_assignment")
-    {
-        checker.report_diagnostic(implicit_assignment(r_expr)?);
-    }
-    if checker.is_rule_enabled("is_numeric") && !checker.should_skip_rule(node, "is_numeric") {
-        checker.report_diagnostic(is_numeric(r_expr)?);
-    }
-    if checker.is_rule_enabled("redundant_equals")
-        && !checker.should_skip_rule(node, "redundant_equals")
-    {
-        checker.report_diagnostic(redundant_equals(r_expr)?);
-    }
-    Ok(())
+pub fn binary_expression(
+    r_expr: &RBinaryExpression,
+    hook: Hook,
+    checker: &mut Checker,
+) -> anyhow::Result<()> {
+    run_rules(r_expr, binary_expression_rules(hook), checker)
 }
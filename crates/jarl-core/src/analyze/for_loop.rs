@@ -1,16 +1,8 @@
+use crate::analyze::run_rules;
 use crate::check::Checker;
+use crate::lints::{Hook, for_loop_rules};
 use air_r_syntax::RForStatement;
-use biome_rowan::AstNode;
 
-use crate::lints::for_loop_index::for_loop_index::for_loop_index;
-
-pub fn for_loop(r_expr: &RForStatement, checker: &mut Checker) -> anyhow::Result<()> {
-    let node = r_expr.syntax();
-
-    if checker.is_rule_enabled("for_loop_index")
-        && !checker.should_skip_rule(node, "for_loop_index")
-    {
-        checker.report_diagnostic(for_loop_index(r_expr)?);
-    }
-    Ok(())
+pub fn for_loop(r_expr: &RForStatement, hook: Hook, checker: &mut Checker) -> anyhow::Result<()> {
+    run_rules(r_expr, for_loop_rules(hook), checker)
 }
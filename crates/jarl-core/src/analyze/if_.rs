@@ -1,11 +1,8 @@
+use crate::analyze::run_rules;
 use crate::check::Checker;
+use crate::lints::{Hook, if_rules};
 use air_r_syntax::RIfStatement;
 
-use crate::lints::coalesce::coalesce::coalesce;
-
-pub fn if_(r_expr: &RIfStatement, checker: &mut Checker) -> anyhow::Result<()> {
-    if checker.is_rule_enabled("coalesce") {
-        checker.report_diagnostic(coalesce(r_expr)?);
-    }
-    Ok(())
+pub fn if_(r_expr: &RIfStatement, hook: Hook, checker: &mut Checker) -> anyhow::Result<()> {
+    run_rules(r_expr, if_rules(hook), checker)
 }
@@ -1,3 +1,9 @@
+use crate::check::Checker;
+use crate::diagnostic::Fix;
+use crate::lints::{CheckFn, Hook, RuleId};
+use air_r_syntax::{AnyRExpression, RLanguage};
+use biome_rowan::AstNode;
+
 pub(crate) mod anyvalue;
 pub(crate) mod binary_expression;
 pub(crate) mod call;
@@ -5,5 +11,68 @@ pub(crate) mod for_loop;
 pub(crate) mod identifier;
 pub(crate) mod if_;
 pub(crate) mod subset;
+pub(crate) mod subset2;
 pub(crate) mod unary_expression;
 pub(crate) mod while_;
+
+/// Dispatch an expression to the rules that inspect its kind of node, e.g.
+/// binary expressions are sent to `binary_expression::binary_expression`.
+///
+/// Not all kinds of node are covered, only those for which there are rules.
+pub(crate) fn expression(
+    expression: &AnyRExpression,
+    hook: Hook,
+    checker: &mut Checker,
+) -> anyhow::Result<()> {
+    match expression {
+        AnyRExpression::AnyRValue(x) => anyvalue::anyvalue(x, hook, checker),
+        AnyRExpression::RBinaryExpression(x) => {
+            binary_expression::binary_expression(x, hook, checker)
+        }
+        AnyRExpression::RCall(x) => call::call(x, hook, checker),
+        AnyRExpression::RForStatement(x) => for_loop::for_loop(x, hook, checker),
+        AnyRExpression::RIdentifier(x) => identifier::identifier(x, hook, checker),
+        AnyRExpression::RIfStatement(x) => if_::if_(x, hook, checker),
+        AnyRExpression::RSubset(x) => subset::subset(x, hook, checker),
+        AnyRExpression::RSubset2(x) => subset2::subset2(x, hook, checker),
+        AnyRExpression::RUnaryExpression(x) => unary_expression::unary_expression(x, hook, checker),
+        AnyRExpression::RWhileStatement(x) => while_::while_(x, hook, checker),
+        _ => Ok(()),
+    }
+}
+
+/// Apply the rules that inspect this kind of node, skipping those that are
+/// disabled, and report the violations that are not suppressed by a comment.
+///
+/// Rules run even on nodes where they are suppressed, so that we know which
+/// comments suppress a violation (see `--report-unused-nolint`). With
+/// `--show-suppressed`, suppressed violations are reported with their
+/// comment and without fix.
+///
+/// `rules` comes from the registry in `lints`, e.g. `lints::call_rules()`.
+pub(crate) fn run_rules<N: AstNode<Language = RLanguage>>(
+    node: &N,
+    rules: &[(RuleId, CheckFn<N>)],
+    checker: &mut Checker,
+) -> anyhow::Result<()> {
+    let syntax = node.syntax();
+
+    for (rule, check) in rules {
+        if !checker.is_rule_enabled(*rule) {
+            continue;
+        }
+        let Some(mut diagnostic) = check(node, checker)? else {
+            continue;
+        };
+        match checker.find_suppression(syntax, *rule) {
+            None => checker.report_diagnostic(Some(diagnostic)),
+            Some(comment) if checker.show_suppressed => {
+                diagnostic.suppression = checker.suppression.suppression(comment);
+                diagnostic.fix = Fix::empty();
+                checker.report_diagnostic(Some(diagnostic));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
@@ -1,14 +1,8 @@
+use crate::analyze::run_rules;
 use crate::check::Checker;
+use crate::lints::{Hook, subset_rules};
 use air_r_syntax::RSubset;
-use biome_rowan::AstNode;
 
-use crate::lints::sort::sort::sort;
-
-pub fn subset(r_expr: &RSubset, checker: &mut Checker) -> anyhow::Result<()> {
-    let node = r_expr.syntax();
-
-    if checker.is_rule_enabled("sort") && !checker.should_skip_rule(node, "sort") {
-        checker.report_diagnostic(sort(r_expr)?);
-    }
-    Ok(())
+pub fn subset(r_expr: &RSubset, hook: Hook, checker: &mut Checker) -> anyhow::Result<()> {
+    run_rules(r_expr, subset_rules(hook), checker)
 }
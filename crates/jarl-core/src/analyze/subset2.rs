@@ -0,0 +1,8 @@
+use crate::analyze::run_rules;
+use crate::check::Checker;
+use crate::lints::{Hook, subset2_rules};
+use air_r_syntax::RSubset2;
+
+pub fn subset2(r_expr: &RSubset2, hook: Hook, checker: &mut Checker) -> anyhow::Result<()> {
+    run_rules(r_expr, subset2_rules(hook), checker)
+}
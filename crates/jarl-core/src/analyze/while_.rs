@@ -1,11 +1,8 @@
+use crate::analyze::run_rules;
 use crate::check::Checker;
+use crate::lints::{Hook, while_rules};
 use air_r_syntax::RWhileStatement;
 
-use crate::lints::repeat::repeat::repeat;
-
-pub fn while_(r_expr: &RWhileStatement, checker: &mut Checker) -> anyhow::Result<()> {
-    if checker.is_rule_enabled("repeat") {
-        checker.report_diagnostic(repeat(r_expr)?);
-    }
-    Ok(())
+pub fn while_(r_expr: &RWhileStatement, hook: Hook, checker: &mut Checker) -> anyhow::Result<()> {
+    run_rules(r_expr, while_rules(hook), checker)
 }
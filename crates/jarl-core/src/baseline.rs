@@ -0,0 +1,288 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::hash::Hasher;
+use std::path::{Component, Path, PathBuf};
+
+use crate::diagnostic::Diagnostic;
+use crate::utils::StableHasher;
+
+/// Version of the format of baseline files.
+const BASELINE_VERSION: u32 = 1;
+
+/// Violations that already exist in a project and that are not reported.
+///
+/// Each violation is identified by its file, its rule, and a fingerprint of
+/// the code that violates the rule. The fingerprint doesn't depend on the
+/// location of the code, so violations are still recognized when lines are
+/// added or removed above them. Identical violations in the same file are
+/// stored once with a `count`.
+///
+/// Paths are relative to the directory of the baseline file, so that it can
+/// be committed and used on other machines.
+#[derive(Debug)]
+pub struct Baseline {
+    /// Where the baseline is stored.
+    path: PathBuf,
+    /// Directory to which the paths of the files are relative.
+    root: PathBuf,
+    /// Violations of each file. When filtering diagnostics, the counts are
+    /// decremented so that the remaining ones are the fixed violations.
+    files: BTreeMap<String, Vec<BaselineEntry>>,
+    /// Files whose diagnostics were filtered.
+    checked: HashSet<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    files: BTreeMap<String, Vec<BaselineEntry>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub rule: String,
+    pub fingerprint: String,
+    pub count: usize,
+}
+
+impl Baseline {
+    /// Create an empty baseline to be stored at `path`.
+    pub fn new(path: &Path) -> Self {
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let root = std::path::absolute(parent).unwrap_or_else(|_| parent.to_path_buf());
+        Self {
+            path: path.to_path_buf(),
+            root,
+            files: BTreeMap::new(),
+            checked: HashSet::new(),
+        }
+    }
+
+    /// Read the baseline stored at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline file '{}'", path.display()))?;
+        let file: BaselineFile = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse baseline file '{}'", path.display()))?;
+        if file.version != BASELINE_VERSION {
+            return Err(anyhow::anyhow!(
+                "Unsupported version {} of baseline file '{}'. Regenerate it with `--generate-baseline`.",
+                file.version,
+                path.display()
+            ));
+        }
+        Ok(Self { files: file.files, ..Self::new(path) })
+    }
+
+    /// Write the baseline to its path.
+    pub fn write(&self) -> Result<()> {
+        let file = BaselineFile {
+            version: BASELINE_VERSION,
+            files: self.files.clone(),
+        };
+        let json = serde_json::to_string_pretty(&file)?;
+        fs::write(&self.path, json + "\n")
+            .with_context(|| format!("Failed to write baseline file '{}'", self.path.display()))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of violations in the baseline.
+    pub fn len(&self) -> usize {
+        self.files.values().flatten().map(|entry| entry.count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add the `diagnostics` of the file at `path` to the baseline.
+    pub fn add(&mut self, path: &Path, diagnostics: &[Diagnostic]) -> Result<()> {
+        let contents = read_if_needed(path, diagnostics)?;
+        self.add_source(path, &contents, diagnostics);
+        Ok(())
+    }
+
+    /// Same as [Baseline::add()], for code that doesn't come from the file.
+    pub fn add_source(&mut self, path: &Path, contents: &str, diagnostics: &[Diagnostic]) {
+        // Syntax errors must always be fixed.
+        let mut diagnostics = diagnostics
+            .iter()
+            .filter(|d| !d.is_parse_error())
+            .peekable();
+        if diagnostics.peek().is_none() {
+            return;
+        }
+
+        let key = self.key(path);
+        let entries = self.files.entry(key).or_default();
+        for diagnostic in diagnostics {
+            let rule = &diagnostic.message.name;
+            let fingerprint = fingerprint(diagnostic, contents);
+            match entries
+                .iter_mut()
+                .find(|entry| &entry.rule == rule && entry.fingerprint == fingerprint)
+            {
+                Some(entry) => entry.count += 1,
+                None => entries.push(BaselineEntry { rule: rule.clone(), fingerprint, count: 1 }),
+            }
+        }
+        entries.sort();
+    }
+
+    /// Remove the `diagnostics` of the file at `path` that are in the
+    /// baseline, and return the other ones.
+    pub fn filter(&mut self, path: &Path, diagnostics: Vec<Diagnostic>) -> Result<Vec<Diagnostic>> {
+        let contents = read_if_needed(path, &diagnostics)?;
+        Ok(self.filter_source(path, &contents, diagnostics))
+    }
+
+    /// Same as [Baseline::filter()], for code that doesn't come from the
+    /// file.
+    pub fn filter_source(
+        &mut self,
+        path: &Path,
+        contents: &str,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Vec<Diagnostic> {
+        let key = self.key(path);
+        let entries = self.files.get_mut(&key);
+        self.checked.insert(key);
+
+        let Some(entries) = entries else {
+            return diagnostics;
+        };
+
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                if diagnostic.is_parse_error() || diagnostic.is_suppressed() {
+                    return true;
+                }
+                let fingerprint = fingerprint(diagnostic, contents);
+                match entries.iter_mut().find(|entry| {
+                    entry.count > 0
+                        && entry.rule == diagnostic.message.name
+                        && entry.fingerprint == fingerprint
+                }) {
+                    Some(entry) => {
+                        entry.count -= 1;
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect()
+    }
+
+    /// Violations of the baseline that were not found by
+    /// [Baseline::filter()], as pairs of file and rule. Only files that were
+    /// checked or that don't exist anymore are considered.
+    pub fn fixed(&self) -> Vec<(&str, &str)> {
+        self.files
+            .iter()
+            .filter(|(file, _)| self.checked.contains(*file) || !self.root.join(file).exists())
+            .flat_map(|(file, entries)| {
+                entries.iter().flat_map(move |entry| {
+                    std::iter::repeat_n((file.as_str(), entry.rule.as_str()), entry.count)
+                })
+            })
+            .collect()
+    }
+
+    /// The path of `path` relative to the directory of the baseline, with `/`
+    /// as separator.
+    fn key(&self, path: &Path) -> String {
+        let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        match absolute.strip_prefix(&self.root) {
+            Ok(relative) => relative
+                .components()
+                .filter_map(|component| match component {
+                    Component::Normal(part) => Some(part.to_string_lossy()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("/"),
+            Err(_) => path.to_string_lossy().replace('\\', "/"),
+        }
+    }
+}
+
+/// Read the file at `path`, unless there are no diagnostics to fingerprint.
+fn read_if_needed(path: &Path, diagnostics: &[Diagnostic]) -> Result<String> {
+    if diagnostics.is_empty() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(path).with_context(|| format!("Failed to read '{}'", path.display()))
+}
+
+/// Fingerprint of the code of a violation. Whitespace is normalized so that
+/// re-indenting the code doesn't change the fingerprint.
+fn fingerprint(diagnostic: &Diagnostic, contents: &str) -> String {
+    let start = usize::from(diagnostic.range.start());
+    let end = usize::from(diagnostic.range.end());
+    let code = contents.get(start..end).unwrap_or_default();
+    let code = code.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut hasher = StableHasher::default();
+    hasher.write(diagnostic.message.name.as_bytes());
+    hasher.write(b"\0");
+    hasher.write(code.as_bytes());
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::lint_source;
+    use crate::utils_test::config_for;
+    use tempfile::TempDir;
+
+    fn diagnostics(path: &Path, contents: &str) -> Vec<Diagnostic> {
+        lint_source(contents, path, &config_for(path, false)).unwrap()
+    }
+
+    #[test]
+    fn test_baseline_survives_line_shifts() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("R/foo.R");
+
+        let contents = "any(is.na(x))\nany(is.na(x))\nany(duplicated(y))\n";
+        let mut baseline = Baseline::new(&directory.path().join("baseline.json"));
+        baseline.add_source(&path, contents, &diagnostics(&path, contents));
+        assert_eq!(baseline.len(), 3);
+        assert_eq!(baseline.files["R/foo.R"].len(), 2);
+
+        // New lines and a new violation.
+        let contents = "f <- 1\n\nany(is.na(x))\n  any(is.na(x))\nany(is.na(z))\n";
+        let remaining = baseline.filter_source(&path, contents, diagnostics(&path, contents));
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].location.unwrap().row(), 5);
+
+        assert_eq!(baseline.fixed(), vec![("R/foo.R", "any_duplicated")]);
+    }
+
+    #[test]
+    fn test_baseline_roundtrip() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("foo.R");
+        let baseline_path = directory.path().join("baseline.json");
+
+        let contents = "any(is.na(x))\n";
+        let mut baseline = Baseline::new(&baseline_path);
+        baseline.add_source(&path, contents, &diagnostics(&path, contents));
+        baseline.write().unwrap();
+
+        let mut baseline = Baseline::load(&baseline_path).unwrap();
+        assert_eq!(baseline.len(), 1);
+        let remaining = baseline.filter_source(&path, contents, diagnostics(&path, contents));
+        assert!(remaining.is_empty());
+        assert!(baseline.fixed().is_empty());
+    }
+}
@@ -0,0 +1,242 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::diagnostic::Diagnostic;
+use crate::toml::find_jarl_toml_in_directory;
+use crate::utils::StableHasher;
+
+/// Name of the cache directory that is created in the project root by default.
+pub const DEFAULT_CACHE_DIR: &str = ".jarl_cache";
+
+/// Where the cache is stored if the user doesn't pass `--cache-dir`:
+/// `.jarl_cache` in the project root, i.e. the closest directory containing
+/// `current_dir` that has a `jarl.toml`, a `DESCRIPTION` or a `.git`. If there
+/// is none, this is `.jarl_cache` in `current_dir`.
+pub fn default_cache_dir(current_dir: &Path) -> PathBuf {
+    let root = current_dir
+        .ancestors()
+        .find(|dir| {
+            find_jarl_toml_in_directory(dir).is_some()
+                || dir.join("DESCRIPTION").is_file()
+                || dir.join(".git").exists()
+        })
+        .unwrap_or(current_dir);
+    root.join(DEFAULT_CACHE_DIR)
+}
+
+/// On-disk cache of the diagnostics of each file.
+///
+/// The diagnostics of a file only depend on its content and on the config
+/// (rules, minimum R version and assignment operator). The cache stores one
+/// entry per file path, which contains the hash of the content and of the
+/// config that produced the diagnostics. If both hashes match, the file
+/// doesn't need to be parsed again.
+///
+/// This is only used when linting, not when applying fixes.
+pub struct Cache {
+    /// Directory of the entries. This is a subdirectory of the cache directory
+    /// passed by the user, one per version of Jarl, since new versions may
+    /// report different diagnostics.
+    dir: PathBuf,
+    /// Hash of the parts of the config that affect diagnostics.
+    fingerprint: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    content_hash: u64,
+    fingerprint: u64,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Cache {
+    /// Open the cache stored in `dir`, creating the directory if necessary.
+    pub fn open(dir: &Path, config: &Config) -> Result<Self> {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create cache directory: {}", dir.display()))?;
+
+        // The cache should never be committed.
+        let gitignore = dir.join(".gitignore");
+        if !gitignore.exists() {
+            fs::write(&gitignore, "*\n")?;
+        }
+
+        let dir = dir.join(env!("CARGO_PKG_VERSION"));
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create cache directory: {}", dir.display()))?;
+
+        Ok(Self { dir, fingerprint: fingerprint(config) })
+    }
+
+    /// Get the diagnostics of the file at `path` if its content and the config
+    /// didn't change since they were stored.
+    pub fn get(&self, path: &Path, contents: &str) -> Option<Vec<Diagnostic>> {
+        let entry = fs::read(self.entry_path(path)).ok()?;
+        // An entry that can't be read is treated as missing and will be
+        // overwritten.
+        let entry: CacheEntry = serde_json::from_slice(&entry).ok()?;
+
+        if entry.fingerprint != self.fingerprint || entry.content_hash != hash(contents) {
+            return None;
+        }
+
+        let mut diagnostics = entry.diagnostics;
+        for diagnostic in &mut diagnostics {
+            diagnostic.filename = path.to_path_buf();
+        }
+        Some(diagnostics)
+    }
+
+    /// Store the diagnostics of the file at `path`.
+    pub fn set(&self, path: &Path, contents: &str, diagnostics: &[Diagnostic]) -> Result<()> {
+        #[derive(Serialize)]
+        struct CacheEntryRef<'a> {
+            content_hash: u64,
+            fingerprint: u64,
+            diagnostics: &'a [Diagnostic],
+        }
+
+        let entry = CacheEntryRef {
+            content_hash: hash(contents),
+            fingerprint: self.fingerprint,
+            diagnostics,
+        };
+
+        // Write to a temporary file first so that another process never reads
+        // a partially written entry.
+        let mut file = tempfile::NamedTempFile::new_in(&self.dir)?;
+        serde_json::to_writer(&mut file, &entry)?;
+        file.flush()?;
+        file.persist(self.entry_path(path))?;
+        Ok(())
+    }
+
+    fn entry_path(&self, path: &Path) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", hash(path)))
+    }
+}
+
+fn fingerprint(config: &Config) -> u64 {
+    let mut hasher = StableHasher::default();
+    config.rules_to_apply.hash(&mut hasher);
+    config.minimum_r_version.hash(&mut hasher);
+    config.assignment_op.hash(&mut hasher);
+    config.rule_options.hash(&mut hasher);
+    config.roxygen_examples.hash(&mut hasher);
+    config.report_unused_nolint.hash(&mut hasher);
+    config.show_suppressed.hash(&mut hasher);
+    config.require_nolint_reason.hash(&mut hasher);
+    for per_file_ignore in &config.per_file_ignores {
+        per_file_ignore.pattern.hash(&mut hasher);
+        per_file_ignore.root().hash(&mut hasher);
+        per_file_ignore.rules.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = StableHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::lint_source;
+    use crate::config::{ArgsConfig, build_config};
+    use crate::settings::Settings;
+    use air_workspace::resolve::PathResolver;
+    use tempfile::TempDir;
+
+    fn config(assignment_op: &str) -> Config {
+        let check_config = ArgsConfig {
+            assignment_op: Some(assignment_op.to_string()),
+            ..Default::default()
+        };
+        let resolver = PathResolver::new(Settings::default());
+        build_config(&check_config, &resolver, vec![]).unwrap()
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let directory = TempDir::new().unwrap();
+        let config = config("<-");
+        let cache = Cache::open(directory.path(), &config).unwrap();
+
+        let path = Path::new("foo.R");
+        let contents = "any(is.na(x))\n";
+        assert!(cache.get(path, contents).is_none());
+
+        let diagnostics = lint_source(contents, path, &config).unwrap();
+        cache.set(path, contents, &diagnostics).unwrap();
+        assert_eq!(cache.get(path, contents), Some(diagnostics));
+
+        // Another file with the same content is not in the cache.
+        assert!(cache.get(Path::new("bar.R"), contents).is_none());
+    }
+
+    #[test]
+    fn test_cache_invalidated_by_content() {
+        let directory = TempDir::new().unwrap();
+        let config = config("<-");
+        let cache = Cache::open(directory.path(), &config).unwrap();
+
+        let path = Path::new("foo.R");
+        cache.set(path, "any(is.na(x))\n", &[]).unwrap();
+        assert!(cache.get(path, "anyNA(x)\n").is_none());
+    }
+
+    #[test]
+    fn test_cache_invalidated_by_config() {
+        let directory = TempDir::new().unwrap();
+        let path = Path::new("foo.R");
+
+        let cache = Cache::open(directory.path(), &config("<-")).unwrap();
+        cache.set(path, "x = 1\n", &[]).unwrap();
+        assert_eq!(cache.get(path, "x = 1\n"), Some(vec![]));
+
+        let cache = Cache::open(directory.path(), &config("=")).unwrap();
+        assert!(cache.get(path, "x = 1\n").is_none());
+    }
+
+    #[test]
+    fn test_default_cache_dir() {
+        let directory = TempDir::new().unwrap();
+        let root = directory.path();
+        let nested = root.join("R").join("utils");
+        fs::create_dir_all(&nested).unwrap();
+
+        // No project, the cache is in the current directory.
+        assert_eq!(default_cache_dir(&nested), nested.join(DEFAULT_CACHE_DIR));
+
+        fs::write(root.join("DESCRIPTION"), "Package: foo\n").unwrap();
+        assert_eq!(default_cache_dir(&nested), root.join(DEFAULT_CACHE_DIR));
+        assert_eq!(default_cache_dir(root), root.join(DEFAULT_CACHE_DIR));
+
+        // The closest project wins.
+        fs::write(root.join("R").join("jarl.toml"), "").unwrap();
+        assert_eq!(
+            default_cache_dir(&nested),
+            root.join("R").join(DEFAULT_CACHE_DIR)
+        );
+    }
+
+    #[test]
+    fn test_cache_ignores_corrupted_entries() {
+        let directory = TempDir::new().unwrap();
+        let cache = Cache::open(directory.path(), &config("<-")).unwrap();
+
+        let path = Path::new("foo.R");
+        fs::write(cache.entry_path(path), "not json").unwrap();
+        assert!(cache.get(path, "x <- 1\n").is_none());
+
+        cache.set(path, "x <- 1\n", &[]).unwrap();
+        assert_eq!(cache.get(path, "x <- 1\n"), Some(vec![]));
+    }
+}
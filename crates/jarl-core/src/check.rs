@@ -1,14 +1,15 @@
-use crate::error::ParseError;
+use crate::cache::Cache;
+use crate::error::{PARSE_ERROR, find_parse_errors};
+use crate::semantic::SemanticModel;
 use crate::suppression::SuppressionManager;
 use crate::vcs::check_version_control;
 use air_fs::relativize_path;
 use air_r_parser::RParserOptions;
-use air_r_syntax::{
-    AnyRExpression, RBinaryExpressionFields, RForStatementFields, RIfStatementFields, RSyntaxKind,
-    RWhileStatementFields,
-};
+use air_r_syntax::{AnyRExpression, RSyntaxKind};
 use anyhow::{Context, Result};
+use biome_rowan::TextRange;
 use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
@@ -17,9 +18,14 @@ use std::sync::Arc;
 use crate::analyze;
 use crate::config::Config;
 use crate::diagnostic::*;
+use crate::document::{DocumentKind, find_r_chunks, mask_outside};
 use crate::fix::*;
-use crate::rule_table::RuleTable;
+use crate::lints::{Hook, RuleId};
+use crate::roxygen::{escape_rd, find_examples};
+use crate::rule_options::RuleOptions;
+use crate::rule_table::RuleSet;
 use crate::utils::*;
+use crate::visitor::{self, Visitor, walk_expression};
 
 pub fn check(config: Config) -> Vec<(String, Result<Vec<Diagnostic>, anyhow::Error>)> {
     // Ensure that all paths are covered by VCS. This is conservative because
@@ -35,6 +41,15 @@ pub fn check(config: Config) -> Vec<(String, Result<Vec<Diagnostic>, anyhow::Err
         }
     }
 
+    // The cache is only used when linting since fixes modify the files. If it
+    // can't be opened, we lint all files as if there was no cache.
+    let cache = match &config.cache_dir {
+        Some(dir) if !config.apply_fixes && !config.apply_unsafe_fixes => Cache::open(dir, &config)
+            .inspect_err(|e| tracing::warn!("Not using the cache: {e}"))
+            .ok(),
+        _ => None,
+    };
+
     // Wrap config in Arc to avoid expensive clones in parallel execution
     let config = Arc::new(config);
 
@@ -42,55 +57,212 @@ pub fn check(config: Config) -> Vec<(String, Result<Vec<Diagnostic>, anyhow::Err
         .paths
         .par_iter()
         .map(|file| {
-            let res = check_path(file, Arc::clone(&config));
+            let res = check_path(file, Arc::clone(&config), cache.as_ref());
             (relativize_path(file), res)
         })
         .collect()
 }
 
-pub fn check_path(path: &PathBuf, config: Arc<Config>) -> Result<Vec<Diagnostic>, anyhow::Error> {
+pub fn check_path(
+    path: &PathBuf,
+    config: Arc<Config>,
+    cache: Option<&Cache>,
+) -> Result<Vec<Diagnostic>, anyhow::Error> {
     if config.apply_fixes || config.apply_unsafe_fixes {
         lint_fix(path, config)
     } else {
-        lint_only(path, config)
+        lint_only(path, config, cache)
     }
 }
 
-pub fn lint_only(path: &PathBuf, config: Arc<Config>) -> Result<Vec<Diagnostic>, anyhow::Error> {
+pub fn lint_only(
+    path: &PathBuf,
+    config: Arc<Config>,
+    cache: Option<&Cache>,
+) -> Result<Vec<Diagnostic>, anyhow::Error> {
     let path = relativize_path(path);
     let contents = fs::read_to_string(Path::new(&path))
         .with_context(|| format!("Failed to read file: {path}"))?;
 
-    let checks = get_checks(&contents, &PathBuf::from(&path), &config)
-        .with_context(|| format!("Failed to get checks for file: {path}"))?;
+    let Some(cache) = cache else {
+        return lint_source(&contents, Path::new(&path), &config);
+    };
+
+    if let Some(diagnostics) = cache.get(Path::new(&path), &contents) {
+        return Ok(diagnostics);
+    }
 
-    Ok(checks)
+    let diagnostics = lint_source(&contents, Path::new(&path), &config)?;
+    if let Err(e) = cache.set(Path::new(&path), &contents, &diagnostics) {
+        tracing::warn!("Failed to update the cache for {path}: {e}");
+    }
+    Ok(diagnostics)
 }
 
 pub fn lint_fix(path: &PathBuf, config: Arc<Config>) -> Result<Vec<Diagnostic>, anyhow::Error> {
     let path = relativize_path(path);
+    let FixedSource { original, fixed, diagnostics } = preview_fix(Path::new(&path), &config)?;
 
-    let mut has_skipped_fixes = true;
-    let mut checks: Vec<Diagnostic>;
+    if fixed != original {
+        fs::write(&path, fixed).with_context(|| format!("Failed to write file: {path}",))?;
+    }
 
-    loop {
-        let contents = fs::read_to_string(Path::new(&path))
-            .with_context(|| format!("Failed to read file: {path}",))?;
+    Ok(diagnostics)
+}
 
-        checks = get_checks(&contents, &PathBuf::from(&path), &config)
-            .with_context(|| format!("Failed to get checks for file: {path}",))?;
+/// The code of a file before and after applying fixes.
+#[derive(Debug)]
+pub struct FixedSource {
+    pub original: String,
+    pub fixed: String,
+    /// The diagnostics that are left after applying fixes.
+    pub diagnostics: Vec<Diagnostic>,
+}
 
-        if !has_skipped_fixes {
-            break;
-        }
+/// Compute the fixes of all files in `config.paths` without writing them to
+/// disk, e.g. to show them as a diff.
+///
+/// Since files are not modified, this doesn't check that they are covered by
+/// version control.
+pub fn preview_fixes(config: Config) -> Vec<(String, Result<FixedSource, anyhow::Error>)> {
+    config
+        .paths
+        .par_iter()
+        .map(|file| {
+            let path = relativize_path(file);
+            let res = preview_fix(Path::new(&path), &config);
+            (path, res)
+        })
+        .collect()
+}
+
+fn preview_fix(path: &Path, config: &Config) -> Result<FixedSource> {
+    let original = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let (diagnostics, fixed) = fix_source(&original, path, config)?;
+    Ok(FixedSource { original, fixed, diagnostics })
+}
+
+/// Lint R code that is already in memory, e.g. the content of an editor buffer
+/// or code passed through stdin.
+///
+/// `path` doesn't need to exist: it is only used to fill the filename of the
+/// diagnostics. This never applies fixes, use [fix_source()] for this.
+///
+/// If `path` is an R Markdown, Quarto or Sweave document, only the code of its
+/// R chunks is linted.
+pub fn lint_source(contents: &str, path: &Path, config: &Config) -> Result<Vec<Diagnostic>> {
+    let diagnostics = match DocumentKind::from_path(path) {
+        Some(kind) => lint_document(contents, kind, path, config),
+        None if config.roxygen_examples => lint_with_examples(contents, path, config),
+        None => get_checks(contents, path, config),
+    };
+    diagnostics.with_context(|| format!("Failed to get checks for file: {}", path.display()))
+}
+
+// Chunks are linted one at a time so that a syntax error in one of them
+// doesn't prevent linting the others. Since the rest of the document is masked
+// with spaces, diagnostics and fixes are located in the document.
+fn lint_document(
+    contents: &str,
+    kind: DocumentKind,
+    path: &Path,
+    config: &Config,
+) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = vec![];
+    for chunk in find_r_chunks(contents, kind) {
+        let code = mask_outside(contents, chunk);
+        diagnostics.extend(get_checks(&code, path, config)?);
+    }
+    Ok(diagnostics)
+}
 
-        let (new_has_skipped_fixes, fixed_text) = apply_fixes(&checks, &contents);
-        has_skipped_fixes = new_has_skipped_fixes;
+// The examples of roxygen comments are linted like the chunks of documents.
+// Fixes that span several lines need the `#'` prefix on their new lines, and
+// their `%` are escaped like in the rest of the Rd code.
+fn lint_with_examples(contents: &str, path: &Path, config: &Config) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = get_checks(contents, path, config)?;
+    for examples in find_examples(contents) {
+        let mut examples_diagnostics = get_checks(&examples.code, path, config)?;
+        for diagnostic in &mut examples_diagnostics {
+            for edit in &mut diagnostic.fix.edits {
+                // `%` must be escaped again since the examples are unescaped.
+                let replaced = contents.get(edit.start..edit.end).unwrap_or_default();
+                edit.content = examples.add_prefix(&escape_rd(&edit.content, replaced));
+            }
+        }
+        diagnostics.extend(examples_diagnostics);
+    }
+    Ok(diagnostics)
+}
 
-        fs::write(&path, fixed_text).with_context(|| format!("Failed to write file: {path}",))?;
+/// Apply fixes to R code that is already in memory.
+///
+/// Nothing is written to disk. This returns the diagnostics that are left after
+/// applying fixes, and the fixed code.
+///
+/// Some fixes may be deferred when they conflict with others (see
+/// [apply_fixes()]), and fixes may reveal new violations, so we re-lint the
+/// fixed code until no fix can be applied, at most [MAX_FIX_ITERATIONS] times.
+pub fn fix_source(
+    contents: &str,
+    path: &Path,
+    config: &Config,
+) -> Result<(Vec<Diagnostic>, String)> {
+    let mut contents = contents.to_string();
+
+    for _ in 0..MAX_FIX_ITERATIONS {
+        let checks = lint_source(&contents, path, config)?;
+        let result = apply_fixes(&checks, &contents, config.apply_unsafe_fixes);
+
+        tracing::debug!(
+            "{}: applied {} fixes, deferred {}",
+            path.display(),
+            result.applied.len(),
+            result.deferred.len()
+        );
+
+        if result.applied.is_empty() || result.content == contents {
+            return Ok((checks, contents));
+        }
+        contents = result.content;
     }
 
-    Ok(checks)
+    tracing::warn!(
+        "{}: fixes were still being applied after {MAX_FIX_ITERATIONS} iterations",
+        path.display()
+    );
+    let checks = lint_source(&contents, path, config)?;
+    Ok((checks, contents))
+}
+
+// Syntax errors are reported as diagnostics so that they are shown with their
+// location, like the other diagnostics. Rules are not run on a file that
+// doesn't parse.
+fn parse_error_diagnostics(
+    parsed: &air_r_parser::Parse,
+    contents: &str,
+    file: &Path,
+) -> Vec<Diagnostic> {
+    let diagnostics = find_parse_errors(parsed.diagnostics(), contents)
+        .into_iter()
+        .map(|error| {
+            let mut diagnostic = Diagnostic::new(
+                ViolationData::new(PARSE_ERROR.to_string(), error.message, None),
+                error.range,
+                Fix::empty(),
+            );
+            diagnostic.filename = file.to_path_buf();
+            diagnostic.severity = Severity::Error;
+            diagnostic
+        })
+        .collect();
+
+    let loc_new_lines = contents
+        .match_indices('\n')
+        .map(|x| x.0)
+        .collect::<Vec<usize>>();
+    compute_lints_location(diagnostics, &loc_new_lines)
 }
 
 #[derive(Debug)]
@@ -99,27 +271,45 @@ pub fn lint_fix(path: &PathBuf, config: Arc<Config>) -> Result<Vec<Diagnostic>,
 pub struct Checker {
     // The diagnostics to report (possibly empty).
     pub diagnostics: Vec<Diagnostic>,
-    // A vector of `Rule`. A `rule` contains the name of the rule to apply,
-    // whether it is safe to fix, unsafe to fix, or doesn't have a fix, and
-    // the minimum R version from which this rule is available.
-    pub rules: RuleTable,
+    // The set of rules to apply.
+    pub rules: RuleSet,
     // The R version that is manually passed by the user in the CLI. Any rule
     // that has a minimum R version higher than this value will be deactivated.
     pub minimum_r_version: Option<(u32, u32, u32)>,
     // Tracks comment-based suppression directives like `# nolint`
     pub suppression: SuppressionManager,
+    // Comments that suppressed a violation, and the rule of the violation.
+    pub used_suppressions: HashSet<(TextRange, RuleId)>,
+    // Also report the violations that are suppressed by a comment?
+    pub show_suppressed: bool,
+    // Scopes, bindings and references of the file, for rules that need to know
+    // what a symbol refers to.
+    pub semantic: SemanticModel,
     // Which assignment operator is preferred?
     pub assignment_op: RSyntaxKind,
+    // Where the node being checked is located, e.g. in a loop.
+    pub context: visitor::Context,
+    // Options of the rules, from `[lint.rules.<name>]` in `jarl.toml`.
+    pub rule_options: RuleOptions,
 }
 
 impl Checker {
-    fn new(suppression: SuppressionManager, assignment_op: RSyntaxKind) -> Self {
+    fn new(
+        suppression: SuppressionManager,
+        semantic: SemanticModel,
+        assignment_op: RSyntaxKind,
+    ) -> Self {
         Self {
             diagnostics: vec![],
-            rules: RuleTable::empty(),
+            rules: RuleSet::empty(),
             minimum_r_version: None,
             suppression,
+            used_suppressions: HashSet::new(),
+            show_suppressed: false,
+            semantic,
             assignment_op,
+            context: visitor::Context::default(),
+            rule_options: RuleOptions::default(),
         }
     }
 
@@ -131,13 +321,42 @@ impl Checker {
         }
     }
 
-    pub(crate) fn is_rule_enabled(&mut self, rule: &str) -> bool {
-        self.rules.enabled.iter().any(|r| r.name == rule)
+    pub(crate) fn is_rule_enabled(&self, rule: RuleId) -> bool {
+        self.rules.contains(rule)
+    }
+
+    /// Find the comment that suppresses a violation of a rule in the given
+    /// node, if any, and record that it suppressed it
+    pub(crate) fn find_suppression(
+        &mut self,
+        node: &air_r_syntax::RSyntaxNode,
+        rule: RuleId,
+    ) -> Option<TextRange> {
+        let comment = self.suppression.find_suppression(node, rule.name())?;
+        self.used_suppressions.insert((comment, rule));
+        Some(comment)
+    }
+}
+
+// The checker visits all expressions of the file and runs the rules that
+// inspect this kind of expression, see `analyze::expression()`.
+impl Visitor for Checker {
+    fn enter(
+        &mut self,
+        expression: &AnyRExpression,
+        context: visitor::Context,
+    ) -> anyhow::Result<()> {
+        self.context = context;
+        analyze::expression(expression, Hook::Enter, self)
     }
 
-    /// Check if a rule should be skipped for the given node due to suppression comments
-    pub(crate) fn should_skip_rule(&self, node: &air_r_syntax::RSyntaxNode, rule: &str) -> bool {
-        self.suppression.should_skip_rule(node, rule)
+    fn exit(
+        &mut self,
+        expression: &AnyRExpression,
+        context: visitor::Context,
+    ) -> anyhow::Result<()> {
+        self.context = context;
+        analyze::expression(expression, Hook::Exit, self)
     }
 }
 
@@ -151,7 +370,7 @@ pub fn get_checks(contents: &str, file: &Path, config: &Config) -> Result<Vec<Di
     let parsed = air_r_parser::parse(contents, parser_options);
 
     if parsed.has_error() {
-        return Err(ParseError { filename: file.to_path_buf() }.into());
+        return Ok(parse_error_diagnostics(&parsed, contents, file));
     }
 
     let syntax = &parsed.syntax();
@@ -165,32 +384,58 @@ pub fn get_checks(contents: &str, file: &Path, config: &Config) -> Result<Vec<Di
         return Ok(vec![]);
     }
 
-    let mut checker = Checker::new(suppression, config.assignment_op);
-    checker.rules = config.rules_to_apply.clone();
+    let semantic = SemanticModel::from_node(syntax);
+
+    let mut checker = Checker::new(suppression, semantic, config.assignment_op);
+    checker.rules = config.rules_for_path(file);
     checker.minimum_r_version = config.minimum_r_version;
+    checker.rule_options = config.rule_options.clone();
+    checker.show_suppressed = config.show_suppressed;
     for expr in expressions_vec {
-        check_expression(&expr, &mut checker)?;
+        walk_expression(&mut checker, &expr)?;
+    }
+
+    if config.report_unused_nolint {
+        let unused = checker.suppression.unused_directives(
+            &checker.used_suppressions,
+            &checker.rules,
+            contents,
+        );
+        checker.diagnostics.extend(unused);
+    }
+
+    if config.require_nolint_reason {
+        let without_reason = checker.suppression.directives_without_reason();
+        checker.diagnostics.extend(without_reason);
     }
 
     // Some rules have a fix available in their implementation but do not have
     // fix in the config, for instance because they are part of the "unfixable"
     // arg or not part of the "fixable" arg in `jarl.toml`.
-    // When we get all the diagnostics with check_expression() above, we don't
+    // When we get all the diagnostics with walk_expression() above, we don't
     // pay attention to whether the user wants to fix them or not. Adding this
     // step here is a way to filter those fixes out before calling apply_fixes().
-    let rules_without_fix = checker
-        .rules
-        .enabled
+    let rules_without_fix = config
+        .rules_to_apply
         .iter()
         .filter(|x| x.has_no_fix())
         .map(|x| x.name.clone())
         .collect::<Vec<String>>();
 
+    let severities = config
+        .rules_to_apply
+        .iter()
+        .map(|x| (x.name.as_str(), x.severity))
+        .collect::<HashMap<&str, Severity>>();
+
     let diagnostics: Vec<Diagnostic> = checker
         .diagnostics
         .into_iter()
         .map(|mut x| {
             x.filename = file.to_path_buf();
+            if let Some(severity) = severities.get(x.message.name.as_str()) {
+                x.severity = *severity;
+            }
             if rules_without_fix.contains(&x.message.name) {
                 x.fix = Fix::empty();
             }
@@ -209,127 +454,316 @@ pub fn get_checks(contents: &str, file: &Path, config: &Config) -> Result<Vec<Di
     Ok(diagnostics)
 }
 
-// This function does two things:
-// - dispatch an expression to its appropriate set of rules, e.g. binary
-//   expressions are sent to the rules stored in
-//   analyze::binary_expression::binary_expression.
-// - apply the function recursively to the expression's children (if any, which
-//   is not guaranteed, e.g. for RIdentifier).
-//
-// Some expression types do both (e.g. RBinaryExpression), some only do the
-// dispatch to rules (e.g. RIdentifier), some only do the recursive call (e.g.
-// RFunctionDefinition).
-//
-// Not all patterns are covered but they don't necessarily have to be.
-// For instance, there are currently no rule for RNaExpression and
-// it doesn't have any children expression on which we need to call
-// check_expression().
-//
-// If a rule needs to be applied on RNaExpression in the future, then
-// we can add the corresponding match arm at this moment.
-pub fn check_expression(
-    expression: &air_r_syntax::AnyRExpression,
-    checker: &mut Checker,
-) -> anyhow::Result<()> {
-    match expression {
-        AnyRExpression::AnyRValue(children) => {
-            analyze::anyvalue::anyvalue(children, checker)?;
-        }
-        AnyRExpression::RBinaryExpression(children) => {
-            analyze::binary_expression::binary_expression(children, checker)?;
-            let RBinaryExpressionFields { left, right, .. } = children.as_fields();
-            check_expression(&left?, checker)?;
-            check_expression(&right?, checker)?;
-        }
-        AnyRExpression::RBracedExpressions(children) => {
-            let expressions: Vec<_> = children.expressions().into_iter().collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils_test::config_for;
+
+    #[test]
+    fn test_lint_source_does_not_need_file() {
+        let path = Path::new("does/not/exist.R");
+        let config = config_for(path, false);
+
+        let diagnostics = lint_source("any(is.na(x))\n", path, &config).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message.name, "any_is_na");
+        assert_eq!(diagnostics[0].filename, path);
+        assert!(!path.exists());
+    }
 
-            for expr in expressions {
-                check_expression(&expr, checker)?;
-            }
-        }
-        AnyRExpression::RCall(children) => {
-            analyze::call::call(children, checker)?;
-
-            let arguments: Vec<AnyRExpression> = children
-                .arguments()?
-                .items()
-                .into_iter()
-                .filter_map(|x| x.unwrap().as_fields().value)
-                .collect();
-
-            for expr in arguments {
-                check_expression(&expr, checker)?;
-            }
-        }
-        AnyRExpression::RForStatement(children) => {
-            analyze::for_loop::for_loop(children, checker)?;
-            let RForStatementFields { variable, sequence, body, .. } = children.as_fields();
-            analyze::identifier::identifier(&variable?, checker)?;
+    #[test]
+    fn test_diagnostics_have_rule_severity() {
+        let path = Path::new("does/not/exist.R");
+        let config = config_for(path, false);
+
+        let diagnostics = lint_source("any(is.na(x))\nx == NA\n", path, &config).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].message.name, "any_is_na");
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[1].message.name, "equals_na");
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+    }
 
-            check_expression(&sequence?, checker)?;
-            check_expression(&body?, checker)?;
-        }
-        AnyRExpression::RFunctionDefinition(children) => {
-            let body = children.body();
-            check_expression(&body?, checker)?;
-        }
-        AnyRExpression::RIdentifier(x) => {
-            analyze::identifier::identifier(x, checker)?;
-        }
-        AnyRExpression::RIfStatement(children) => {
-            analyze::if_::if_(children, checker)?;
-
-            let RIfStatementFields { condition, consequence, else_clause, .. } =
-                children.as_fields();
-            check_expression(&condition?, checker)?;
-            check_expression(&consequence?, checker)?;
-            if let Some(else_clause) = else_clause {
-                let alternative = else_clause.alternative();
-                check_expression(&alternative?, checker)?;
-            }
-        }
-        AnyRExpression::RParenthesizedExpression(children) => {
-            let body = children.body();
-            check_expression(&body?, checker)?;
-        }
-        AnyRExpression::RRepeatStatement(children) => {
-            let body = children.body();
-            check_expression(&body?, checker)?;
-        }
-        AnyRExpression::RSubset(children) => {
-            analyze::subset::subset(children, checker)?;
-            let arguments: Vec<_> = children.arguments()?.items().into_iter().collect();
-
-            for expr in arguments {
-                if let Some(expr) = expr?.value() {
-                    check_expression(&expr, checker)?;
-                }
-            }
-        }
-        AnyRExpression::RSubset2(children) => {
-            let arguments: Vec<_> = children.arguments()?.items().into_iter().collect();
+    #[test]
+    fn test_lint_source_parse_error() {
+        let path = Path::new("does/not/exist.R");
+        let config = config_for(path, false);
+
+        let diagnostics = lint_source("x <- 1\nf(", path, &config).unwrap();
+        assert!(!diagnostics.is_empty());
+        assert!(diagnostics.iter().all(|d| d.is_parse_error()));
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+        assert_eq!(diagnostics[0].filename, path);
+        assert_eq!(diagnostics[0].location.unwrap().row(), 2);
+    }
 
-            for expr in arguments {
-                if let Some(expr) = expr?.value() {
-                    check_expression(&expr, checker)?;
-                }
-            }
+    #[test]
+    fn test_rules_in_all_visited_positions() {
+        use crate::utils_test::check_code;
+
+        let n_lints = |text: &str, rule: &str| check_code(text, rule, None).len();
+
+        // The function of calls and subsets, the default values of parameters,
+        // and the left-hand side of `$` are visited, once.
+        for text in [
+            "f(any(duplicated(x)))(y)",
+            "any(duplicated(x))[1]",
+            "any(duplicated(x))[[1]]",
+            "function(a = any(duplicated(x))) a",
+            "\\(a = any(duplicated(x))) a",
+            "any(duplicated(x))$foo",
+        ] {
+            assert_eq!(n_lints(text, "any_duplicated"), 1, "{text}");
         }
-        AnyRExpression::RUnaryExpression(children) => {
-            analyze::unary_expression::unary_expression(children, checker)?;
+        assert_eq!(n_lints("f(a = 1, a = 2)(x)", "duplicated_arguments"), 1);
+        assert_eq!(n_lints("(x == NA)[1]", "equals_na"), 1);
+        assert_eq!(n_lints("function(a = x == NA) a", "equals_na"), 1);
+        assert_eq!(n_lints("function(a = .1) a", "numeric_leading_zero"), 1);
+        assert_eq!(n_lints("function(a = T) a", "true_false_symbol"), 1);
+        assert_eq!(n_lints("T[1]", "true_false_symbol"), 1);
+
+        // Names of functions and of extracted elements are still allowed.
+        assert_eq!(n_lints("T(x)", "true_false_symbol"), 0);
+        assert_eq!(n_lints("T$foo", "true_false_symbol"), 0);
+        assert_eq!(n_lints("df$T", "true_false_symbol"), 0);
+
+        // The diagnostics in the positions that were already visited don't
+        // change.
+        assert_eq!(
+            n_lints("f(any(duplicated(x)), y == NA)", "any_duplicated"),
+            1
+        );
+        assert_eq!(n_lints("f(any(duplicated(x)), y == NA)", "equals_na"), 1);
+        assert_eq!(n_lints("x[any(duplicated(y))]", "any_duplicated"), 1);
+        assert_eq!(
+            n_lints("function(a) any(duplicated(a))", "any_duplicated"),
+            1
+        );
+    }
 
-            let argument = children.argument();
-            check_expression(&argument?, checker)?;
+    fn unused_nolint_for(contents: &str, fix: bool) -> (Vec<Diagnostic>, String) {
+        let path = Path::new("does/not/exist.R");
+        let mut config = config_for(path, fix);
+        config.report_unused_nolint = true;
+        if fix {
+            fix_source(contents, path, &config).unwrap()
+        } else {
+            let diagnostics = lint_source(contents, path, &config).unwrap();
+            (diagnostics, contents.to_string())
         }
-        AnyRExpression::RWhileStatement(children) => {
-            analyze::while_::while_(children, checker)?;
-            let RWhileStatementFields { condition, body, .. } = children.as_fields();
-            check_expression(&condition?, checker)?;
-            check_expression(&body?, checker)?;
-        }
-        _ => {}
     }
 
-    Ok(())
+    #[test]
+    fn test_unused_nolint() {
+        // Used comments are not reported
+        let (diagnostics, _) = unused_nolint_for(
+            "any(is.na(x)) # nolint
+any(is.na(x)) # nolint: any_is_na_linter
+# nolint start
+any(is.na(x))
+# nolint end
+",
+            false,
+        );
+        assert!(diagnostics.is_empty());
+
+        let (diagnostics, _) = unused_nolint_for(
+            "x <- 1 # nolint
+any(is.na(x)) # nolint: any_is_na, foo, any_duplicated
+# nolint end
+# nolint start
+",
+            false,
+        );
+        let messages = diagnostics
+            .iter()
+            .map(|d| (d.message.name.as_str(), d.message.body.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                (
+                    "unused_nolint",
+                    "This `# nolint` comment doesn't suppress any violation."
+                ),
+                (
+                    "unused_nolint",
+                    "Some rules of this `# nolint` comment don't suppress any violation: unknown rule `foo`; no violation of rule `any_duplicated` to suppress."
+                ),
+                (
+                    "unused_nolint",
+                    "This `# nolint end` comment has no matching `# nolint start`."
+                ),
+                (
+                    "unused_nolint",
+                    "This `# nolint start` comment has no matching `# nolint end`, so it doesn't suppress any violation."
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_nolint_ignores_disabled_rules() {
+        let path = Path::new("does/not/exist.R");
+        let mut config = config_for(path, false);
+        config.report_unused_nolint = true;
+        config
+            .rules_to_apply
+            .enabled
+            .retain(|rule| rule.name == "any_is_na");
+
+        let diagnostics = lint_source(
+            "x <- 1 # nolint: any_duplicated
+",
+            path,
+            &config,
+        )
+        .unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_unused_nolint_fix() {
+        let (diagnostics, fixed) = unused_nolint_for(
+            "x <- 1 # nolint
+# nolint start: any_duplicated
+y <- 2
+# nolint end
+any(is.na(x)) # nolint: any_duplicated, any_is_na
+",
+            true,
+        );
+        assert!(diagnostics.is_empty());
+        assert_eq!(
+            fixed,
+            "x <- 1
+y <- 2
+any(is.na(x)) # nolint: any_is_na
+"
+        );
+    }
+
+    #[test]
+    fn test_unused_nolint_fix_unknown_rules_is_unsafe() {
+        // Unknown rules may be lintr linters, they are only removed with
+        // unsafe fixes
+        let code = "any(is.na(x)) # nolint: any_is_na, object_name_linter, any_duplicated\n";
+        let (diagnostics, fixed) = unused_nolint_for(code, true);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].has_unsafe_fix());
+        assert_eq!(fixed, code);
+
+        let path = Path::new("does/not/exist.R");
+        let mut config = config_for(path, true);
+        config.report_unused_nolint = true;
+        config.apply_unsafe_fixes = true;
+        let (_, fixed) = fix_source(code, path, &config).unwrap();
+        assert_eq!(fixed, "any(is.na(x)) # nolint: any_is_na\n");
+    }
+
+    #[test]
+    fn test_unused_nolint_keeps_reason() {
+        let (_, fixed) = unused_nolint_for(
+            "any(is.na(x)) # nolint: any_duplicated, any_is_na -- NA are removed upstream\n",
+            true,
+        );
+        assert_eq!(
+            fixed,
+            "any(is.na(x)) # nolint: any_is_na -- NA are removed upstream\n"
+        );
+    }
+
+    #[test]
+    fn test_require_nolint_reason() {
+        let path = Path::new("does/not/exist.R");
+        let mut config = config_for(path, false);
+        config.require_nolint_reason = true;
+
+        let diagnostics = lint_source(
+            "any(is.na(x)) # nolint: any_is_na
+any(is.na(y)) # nolint: any_is_na -- NA are removed upstream
+",
+            path,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message.name, "nolint_without_reason");
+        assert_eq!(diagnostics[0].location.unwrap().row(), 1);
+    }
+
+    #[test]
+    fn test_show_suppressed() {
+        let path = Path::new("does/not/exist.R");
+        let mut config = config_for(path, true);
+        config.show_suppressed = true;
+
+        let diagnostics = lint_source(
+            "any(is.na(x)) # nolint: any_is_na -- NA are removed upstream
+any(is.na(y))
+",
+            path,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(diagnostics.len(), 2);
+
+        let suppression = diagnostics[0].suppression.as_ref().unwrap();
+        assert_eq!(
+            suppression.comment,
+            "# nolint: any_is_na -- NA are removed upstream"
+        );
+        assert_eq!(
+            suppression.reason.as_deref(),
+            Some("NA are removed upstream")
+        );
+        // Suppressed violations are never fixed
+        assert!(diagnostics[0].has_no_fix());
+
+        assert!(!diagnostics[1].is_suppressed());
+        assert!(diagnostics[1].has_safe_fix());
+    }
+
+    #[test]
+    fn test_fix_source() {
+        let path = Path::new("does/not/exist.R");
+        let config = config_for(path, true);
+
+        let (diagnostics, fixed) =
+            fix_source("x <- any(is.na(y))\nfor (i in f(i)) {}\n", path, &config).unwrap();
+        assert_eq!(fixed, "x <- anyNA(y)\nfor (i in f(i)) {}\n");
+        // Only the diagnostic without a fix is left.
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message.name, "for_loop_index");
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_fix_source_applies_deferred_fixes() {
+        let path = Path::new("does/not/exist.R");
+        let config = config_for(path, true);
+
+        // The fix of `equals_na` is inside the range of the fix of `any_is_na`
+        // so it is only applied after re-linting.
+        let (diagnostics, fixed) = fix_source("any(is.na(x == NA))\n", path, &config).unwrap();
+        assert_eq!(fixed, "anyNA(is.na(x))\n");
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_preview_fixes_does_not_write() {
+        let directory = tempfile::TempDir::new().unwrap();
+        let path = directory.path().join("test.R");
+        fs::write(&path, "any(is.na(x))\n").unwrap();
+
+        let config = config_for(&path, true);
+        let results = preview_fixes(config);
+        assert_eq!(results.len(), 1);
+
+        let FixedSource { original, fixed, diagnostics } = results[0].1.as_ref().unwrap();
+        assert_eq!(original, "any(is.na(x))\n");
+        assert_eq!(fixed, "anyNA(x)\n");
+        assert!(diagnostics.is_empty());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "any(is.na(x))\n");
+    }
 }
@@ -1,15 +1,23 @@
 use crate::{
     description::Description,
-    lints::{RULE_GROUPS, all_rules_and_safety},
-    rule_table::{FixStatus, Rule, RuleTable},
+    diagnostic::Severity,
+    discovery::resolve_settings,
+    lints::{RULE_GROUPS, RuleId, all_rules_and_safety},
+    rule_options::RuleOptions,
+    rule_table::{FixStatus, Rule, RuleSet, RuleTable},
     settings::Settings,
 };
 use air_r_syntax::RSyntaxKind;
 use air_workspace::resolve::PathResolver;
 use anyhow::Result;
-use std::{collections::HashSet, fs, path::PathBuf};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 /// Arguments provided in the CLI.
 pub struct ArgsConfig {
     /// Paths to files to lint.
@@ -34,12 +42,70 @@ pub struct ArgsConfig {
     pub allow_no_vcs: bool,
     /// Which assignment operator to use? Can be `"<-"` or `"="`.
     pub assignment_op: Option<String>,
+    /// Where to store the cache of diagnostics? `None` disables the cache.
+    pub cache_dir: Option<PathBuf>,
+    /// Did the user pass the --report-unused-nolint flag?
+    pub report_unused_nolint: bool,
+    /// Did the user pass the --show-suppressed flag?
+    pub show_suppressed: bool,
+    /// Did the user pass the --verbose flag?
+    pub verbose: bool,
+}
+
+/// Rules ignored in the files matching a pattern of `per-file-ignores`.
+#[derive(Clone, Debug)]
+pub struct PerFileIgnore {
+    /// The pattern, as written in `jarl.toml`.
+    pub pattern: String,
+    /// Matcher of the pattern, relative to the directory of `jarl.toml` or of
+    /// the extended file that defines it.
+    matcher: Gitignore,
+    /// Rules to ignore in the files matching the pattern.
+    pub rules: RuleSet,
+}
+
+impl PerFileIgnore {
+    /// Directory that the pattern is relative to.
+    pub fn root(&self) -> &Path {
+        self.matcher.path()
+    }
+}
+
+impl Config {
+    /// Rules to apply to `path`, i.e. `rules_to_apply` without the rules that
+    /// `per-file-ignores` ignores in this file.
+    pub fn rules_for_path(&self, path: &Path) -> RuleSet {
+        let mut rules = self.rules_to_apply.to_rule_set();
+        if self.per_file_ignores.is_empty() {
+            return rules;
+        }
+
+        let path = crate::fs::normalize_path(path);
+        for per_file_ignore in &self.per_file_ignores {
+            // `Gitignore` panics on paths that are outside of its root.
+            if !path.starts_with(per_file_ignore.matcher.path()) {
+                continue;
+            }
+            if per_file_ignore
+                .matcher
+                .matched_path_or_any_parents(&path, false)
+                .is_ignore()
+            {
+                for rule in per_file_ignore.rules.iter() {
+                    rules.remove(rule);
+                }
+            }
+        }
+        rules
+    }
 }
 
 #[derive(Clone)]
 pub struct Config {
     /// Paths to files to lint.
     pub paths: Vec<PathBuf>,
+    /// Directory of the `jarl.toml` that applies to `paths`, if any.
+    pub root: Option<PathBuf>,
     /// List of rules and whether they have an associated safe fix, passed by
     /// the user and/or recovered from the config file. Those will
     /// not necessarily all be used, for instance if we disable unsafe fixes.
@@ -63,66 +129,118 @@ pub struct Config {
     /// Which assignment operator to use? Can be `RSyntaxKind::ASSIGN` or
     /// `RSyntaxKind::EQUAL`.
     pub assignment_op: RSyntaxKind,
+    /// Where to store the cache of diagnostics? `None` disables the cache.
+    pub cache_dir: Option<PathBuf>,
+    /// Report `# nolint` comments that don't suppress anything?
+    pub report_unused_nolint: bool,
+    /// Also return the violations that are suppressed by `# nolint` comments?
+    pub show_suppressed: bool,
+    /// Messages for the user about the configuration, e.g. to explain why a
+    /// selected rule is disabled.
+    pub warnings: Vec<String>,
+    /// Rules to ignore in the files matching some patterns, from the field
+    /// `per-file-ignores` in `jarl.toml`.
+    pub per_file_ignores: Vec<PerFileIgnore>,
+    /// Options of the rules, from `[lint.rules.<name>]` in `jarl.toml`.
+    pub rule_options: RuleOptions,
+    /// Check the code in the `@examples` of roxygen comments?
+    pub roxygen_examples: bool,
+    /// Report `# nolint` comments that don't give a reason after `--`?
+    pub require_nolint_reason: bool,
+    /// Baseline of violations that are not reported, from the field
+    /// `baseline` in `jarl.toml`.
+    pub baseline: Option<PathBuf>,
 }
 
-pub fn build_config(
+/// Build one [Config] per `jarl.toml` used by `paths`.
+///
+/// Each file uses the settings of its nearest `jarl.toml`, so that a repository
+/// can contain several projects (e.g. several R packages) with their own
+/// configuration. Files that aren't covered by any `jarl.toml` share a
+/// [Config] that only uses the CLI arguments.
+pub fn build_configs(
     check_config: &ArgsConfig,
     resolver: &PathResolver<Settings>,
     paths: Vec<PathBuf>,
-) -> Result<Config> {
-    let root_path = resolver
-        .items()
-        .iter()
-        .map(|x| x.path())
-        .collect::<Vec<_>>();
+) -> Result<Vec<Config>> {
+    let mut groups: BTreeMap<Option<PathBuf>, (Option<&Settings>, Vec<PathBuf>)> = BTreeMap::new();
 
-    if root_path.len() > 1 {
-        todo!("Don't know how to handle multiple TOML")
+    for path in paths {
+        let (root, settings) = match resolve_settings(resolver, &path) {
+            Some((root, settings)) => (Some(root), Some(settings)),
+            None => (None, None),
+        };
+        groups
+            .entry(root)
+            .or_insert_with(|| (settings, vec![]))
+            .1
+            .push(path);
     }
 
-    let toml_settings = if root_path.len() == 1 {
-        Some(resolver.items().first().unwrap().value())
-    } else {
-        None
+    groups
+        .into_iter()
+        .map(|(root, (settings, paths))| {
+            build_config_with_settings(check_config, settings, root, paths)
+        })
+        .collect()
+}
+
+/// Build the [Config] used to check `paths`.
+///
+/// All `paths` are expected to use the same `jarl.toml`, which is the nearest
+/// one of the first path. Use [build_configs()] if they may not.
+pub fn build_config(
+    check_config: &ArgsConfig,
+    resolver: &PathResolver<Settings>,
+    paths: Vec<PathBuf>,
+) -> Result<Config> {
+    let (root, settings) = match paths
+        .first()
+        .and_then(|path| resolve_settings(resolver, path))
+    {
+        Some((root, settings)) => (Some(root), Some(settings)),
+        None => (None, None),
     };
+    build_config_with_settings(check_config, settings, root, paths)
+}
 
+fn build_config_with_settings(
+    check_config: &ArgsConfig,
+    toml_settings: Option<&Settings>,
+    root: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+) -> Result<Config> {
     // Determining the minimum R version has to come first since if it is
     // unknown then only rules that don't have a version restriction are
     // selected.
-    let minimum_r_version = determine_minimum_r_version(check_config, &paths)?;
+    let r_version =
+        determine_minimum_r_version(check_config, toml_settings, root.as_deref(), &paths)?;
+    let minimum_r_version = r_version.as_ref().map(|(version, _)| *version);
 
     let rules_cli = parse_rules_cli(&check_config.select_rules, &check_config.ignore_rules)?;
     let rules_toml = parse_rules_toml(toml_settings)?;
     let rules = reconcile_rules(rules_cli, rules_toml)?;
-
-    let rules = filter_rules_by_version(&rules, minimum_r_version);
+    let rules = apply_severity_toml(rules, toml_settings)?;
+
+    let rules_for_version = filter_rules_by_version(&rules, minimum_r_version);
+    let warnings = version_warnings(
+        &rules,
+        &rules_for_version,
+        r_version.as_ref(),
+        check_config,
+        toml_settings,
+    );
+    let rules = rules_for_version;
 
     // Parse fixable/unfixable rules from TOML and apply them to rules
     // This must happen before we check fix/unsafe-fixes flags
     let (fixable_toml, unfixable_toml) = parse_fixable_toml(toml_settings)?;
     let rules = apply_fixable_filters(rules, fixable_toml, unfixable_toml, check_config);
 
-    // Resolve the interaction between --fix and --unsafe-fixes first. Using
-    // --unsafe-fixes implies using --fix, but the opposite is not true.
-    let rules_to_apply = match (check_config.fix, check_config.unsafe_fixes) {
-        (false, false) => rules.clone(),
-
-        (true, false) => rules
-            .iter()
-            .filter(|r| r.has_no_fix() || r.has_safe_fix())
-            .cloned()
-            .collect::<RuleTable>(),
-
-        (_, true) => rules
-            .iter()
-            .filter(|r| r.has_no_fix() || r.has_safe_fix() || r.has_unsafe_fix())
-            .cloned()
-            .collect::<RuleTable>(),
-    };
-
-    // We can now drop rules that don't have any fix if the user passed
-    // --fix-only. This could maybe be done above but dealing with the three
-    // args at the same time makes it much more complex.
+    // Whether a fix is applied with --fix or only with --unsafe-fixes is
+    // decided for each diagnostic (see `Applicability`), so all rules run
+    // regardless of these flags. We can drop rules that don't have any fix if
+    // the user passed --fix-only.
     let rules_to_apply = if check_config.fix_only {
         rules
             .iter()
@@ -130,13 +248,21 @@ pub fn build_config(
             .cloned()
             .collect::<RuleTable>()
     } else {
-        rules_to_apply
+        rules.clone()
     };
 
     let assignment_op = parse_assignment(check_config, toml_settings)?;
+    let per_file_ignores = parse_per_file_ignores(toml_settings, root.as_deref())?;
+    let baseline = toml_settings
+        .and_then(|settings| settings.linter.baseline.as_ref())
+        .map(|baseline| match &root {
+            Some(root) => root.join(baseline),
+            None => PathBuf::from(baseline),
+        });
 
     Ok(Config {
         paths,
+        root,
         rules,
         rules_to_apply,
         apply_fixes: check_config.fix,
@@ -145,6 +271,21 @@ pub fn build_config(
         allow_dirty: check_config.allow_dirty,
         allow_no_vcs: check_config.allow_no_vcs,
         assignment_op,
+        cache_dir: check_config.cache_dir.clone(),
+        report_unused_nolint: check_config.report_unused_nolint,
+        show_suppressed: check_config.show_suppressed,
+        warnings,
+        per_file_ignores,
+        rule_options: toml_settings
+            .and_then(|settings| settings.linter.rules.clone())
+            .unwrap_or_default(),
+        roxygen_examples: toml_settings
+            .and_then(|settings| settings.linter.roxygen_examples)
+            .unwrap_or(false),
+        require_nolint_reason: toml_settings
+            .and_then(|settings| settings.linter.require_nolint_reason)
+            .unwrap_or(false),
+        baseline,
     })
 }
 
@@ -227,7 +368,8 @@ pub fn parse_rules_toml(
             let invalid_rules = get_invalid_rules(&all_rules, &expanded_rules);
             if let Some(invalid_rules) = invalid_rules {
                 return Err(anyhow::anyhow!(
-                    "Unknown rules in field `select` in 'jarl.toml': {}",
+                    "Unknown rules in {}: {}",
+                    settings.source.field("select"),
                     invalid_rules.join(", ")
                 ));
             }
@@ -248,7 +390,8 @@ pub fn parse_rules_toml(
         let invalid_rules = get_invalid_rules(&all_rules, &expanded_rules);
         if let Some(invalid_rules) = invalid_rules {
             return Err(anyhow::anyhow!(
-                "Unknown rules in field `ignore` in 'jarl.toml': {}",
+                "Unknown rules in {}: {}",
+                settings.source.field("ignore"),
                 invalid_rules.join(", ")
             ));
         }
@@ -290,7 +433,8 @@ pub fn parse_fixable_toml(
             let invalid_rules = get_invalid_rules(&all_rules, &expanded_rules);
             if let Some(invalid_rules) = invalid_rules {
                 return Err(anyhow::anyhow!(
-                    "Unknown rules in field `fixable` in 'jarl.toml': {}",
+                    "Unknown rules in {}: {}",
+                    settings.source.field("fixable"),
                     invalid_rules.join(", ")
                 ));
             }
@@ -312,7 +456,8 @@ pub fn parse_fixable_toml(
         let invalid_rules = get_invalid_rules(&all_rules, &expanded_rules);
         if let Some(invalid_rules) = invalid_rules {
             return Err(anyhow::anyhow!(
-                "Unknown rules in field `unfixable` in 'jarl.toml': {}",
+                "Unknown rules in {}: {}",
+                settings.source.field("unfixable"),
                 invalid_rules.join(", ")
             ));
         }
@@ -329,6 +474,114 @@ pub fn parse_fixable_toml(
     Ok((fixable_rules, unfixable_rules))
 }
 
+/// Override the default severity of rules with the table `severity` in
+/// `jarl.toml`.
+///
+/// Keys can be rule names or groups of rules (e.g. "PERF"). The severity of a
+/// rule name takes precedence over the severity of its groups, and if a rule
+/// is part of several groups with a severity, the highest one is used.
+fn apply_severity_toml(rules: RuleTable, toml_settings: Option<&Settings>) -> Result<RuleTable> {
+    let Some(settings) = toml_settings else {
+        return Ok(rules);
+    };
+    let Some(severity_toml) = &settings.linter.severity else {
+        return Ok(rules);
+    };
+
+    let all_rules = all_rules_and_safety();
+    let names = severity_toml.keys().map(|s| s.as_str()).collect();
+    let expanded_rules = replace_group_rules(&names, &all_rules);
+    if let Some(invalid_rules) = get_invalid_rules(&all_rules, &expanded_rules) {
+        return Err(anyhow::anyhow!(
+            "Unknown rules in {}: {}",
+            settings.source.field("severity"),
+            invalid_rules.join(", ")
+        ));
+    }
+
+    let mut severities = HashMap::new();
+    for (name, value) in severity_toml {
+        let severity = value.parse::<Severity>().map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid value for `{name}` in {}: {e}",
+                settings.source.field("severity")
+            )
+        })?;
+        severities.insert(name.trim(), severity);
+    }
+
+    Ok(rules
+        .iter()
+        .map(|rule| {
+            let severity = severities.get(rule.name.as_str()).copied().or_else(|| {
+                rule.categories
+                    .iter()
+                    .filter_map(|category| severities.get(category.as_str()).copied())
+                    .max()
+            });
+            Rule {
+                severity: severity.unwrap_or(rule.severity),
+                ..rule.clone()
+            }
+        })
+        .collect())
+}
+
+/// Parse the field `per-file-ignores` of `jarl.toml`, whose patterns are
+/// relative to `root`, the directory of `jarl.toml`, or to the directory of
+/// the extended file that defines them.
+fn parse_per_file_ignores(
+    toml_settings: Option<&Settings>,
+    root: Option<&Path>,
+) -> Result<Vec<PerFileIgnore>> {
+    let (Some(settings), Some(root)) = (toml_settings, root) else {
+        return Ok(vec![]);
+    };
+    let Some(per_file_ignores) = &settings.linter.per_file_ignores else {
+        return Ok(vec![]);
+    };
+
+    let all_rules = all_rules_and_safety();
+    let mut out = Vec::with_capacity(per_file_ignores.len());
+
+    for (pattern, rules) in per_file_ignores {
+        let passed_by_user = rules.iter().map(|s| s.as_str()).collect();
+        let expanded_rules = replace_group_rules(&passed_by_user, &all_rules);
+        if let Some(invalid_rules) = get_invalid_rules(&all_rules, &expanded_rules) {
+            return Err(anyhow::anyhow!(
+                "Unknown rules in {}: {}",
+                settings.source.field("per-file-ignores"),
+                invalid_rules.join(", ")
+            ));
+        }
+
+        let pattern_root = settings
+            .linter
+            .per_file_ignores_roots
+            .get(pattern)
+            .map_or(root, |directory| directory.as_path());
+        let mut builder = GitignoreBuilder::new(pattern_root);
+        builder.add_line(None, pattern).map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid pattern `{pattern}` in {}: {e}",
+                settings.source.field("per-file-ignores")
+            )
+        })?;
+        let matcher = builder.build()?;
+
+        out.push(PerFileIgnore {
+            pattern: pattern.clone(),
+            matcher,
+            rules: expanded_rules
+                .iter()
+                .filter_map(|name| RuleId::from_name(name))
+                .collect(),
+        });
+    }
+
+    Ok(out)
+}
+
 // This takes rules that refer to groups (e.g. "PERF", "READ") and replaces them
 // with the rule names.
 // Returns a vector with the original rule names left unmodified and the expanded
@@ -432,40 +685,171 @@ fn reconcile_rules(
     Ok(final_rules)
 }
 
-/// Determine the minimum R version from CLI args or DESCRIPTION file
+/// Where the minimum R version of the project comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RVersionSource {
+    /// The argument `--min-r-version`.
+    Cli,
+    /// The field `min-r-version` of `jarl.toml`.
+    Toml,
+    /// The `Depends` field of this DESCRIPTION file.
+    Description(PathBuf),
+}
+
+impl std::fmt::Display for RVersionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RVersionSource::Cli => write!(f, "`--min-r-version`"),
+            RVersionSource::Toml => write!(f, "`min-r-version` in 'jarl.toml'"),
+            RVersionSource::Description(path) => write!(f, "'{}'", path.display()),
+        }
+    }
+}
+
+/// Determine the minimum R version and where it comes from, by order of
+/// precedence:
+/// 1. the CLI argument `--min-r-version`;
+/// 2. the field `min-r-version` in `jarl.toml`;
+/// 3. the `Depends` field of the DESCRIPTION at the root of the package, found
+///    by walking up from `root` and from each of `paths`.
 fn determine_minimum_r_version(
     check_config: &ArgsConfig,
+    toml_settings: Option<&Settings>,
+    root: Option<&Path>,
     paths: &[PathBuf],
-) -> Result<Option<(u32, u32, u32)>> {
+) -> Result<Option<((u32, u32, u32), RVersionSource)>> {
     if let Some(version_string) = &check_config.min_r_version {
-        return Ok(Some(parse_r_version(version_string.clone())?));
+        return Ok(Some((
+            parse_r_version(version_string.clone())?,
+            RVersionSource::Cli,
+        )));
     }
 
-    // Look for DESCRIPTION file in any of the project paths
-    // TODO: this seems wasteful but I don't have a good infrastructure for now
-    // for getting the common root of the paths.
-    for path in paths {
-        let desc_path = if path.is_dir() {
-            path.join("DESCRIPTION")
+    if let Some(version_string) = toml_settings.and_then(|s| s.linter.min_r_version.as_ref()) {
+        return Ok(Some((
+            parse_r_version(version_string.clone())?,
+            RVersionSource::Toml,
+        )));
+    }
+
+    // Look for DESCRIPTION file next to `jarl.toml` first, since this is
+    // usually the root of the package. Then look in any of the project paths.
+    // Directories that were already visited are skipped, as are their parents.
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    for path in root.map(Path::to_path_buf).iter().chain(paths) {
+        let start = if path.is_dir() {
+            path.as_path()
         } else if let Some(parent) = path.parent() {
-            parent.join("DESCRIPTION")
+            parent
         } else {
             continue;
         };
+        let start = std::path::absolute(start).unwrap_or_else(|_| start.to_path_buf());
+
+        for directory in start.ancestors() {
+            if !visited.insert(directory.to_path_buf()) {
+                break;
+            }
+            let desc_path = directory.join("DESCRIPTION");
+            if !desc_path.is_file() {
+                continue;
+            }
 
-        if desc_path.exists() {
             let desc = fs::read_to_string(&desc_path)?;
             if let Ok(versions) = Description::get_depend_r_version(&desc)
                 && let Some(version_str) = versions.first()
             {
-                return Ok(Some(parse_r_version(version_str.to_string())?));
+                return Ok(Some((
+                    parse_r_version(version_str.to_string())?,
+                    RVersionSource::Description(desc_path),
+                )));
             }
+            // This is the root of the package, its parents are not part of it.
+            break;
         }
     }
 
     Ok(None)
 }
 
+/// Explain why rules were disabled because of the minimum R version. Rules
+/// that the user selected by name get one warning each. The rules that were
+/// selected through a group (e.g. `PERF`) or by default are summarized in a
+/// single warning, only with `--verbose` since this concerns most projects.
+fn version_warnings(
+    rules: &RuleTable,
+    kept_rules: &RuleTable,
+    r_version: Option<&((u32, u32, u32), RVersionSource)>,
+    check_config: &ArgsConfig,
+    toml_settings: Option<&Settings>,
+) -> Vec<String> {
+    let mut selected_by_name: HashSet<&str> = check_config.select_rules.split(',').collect();
+    if let Some(select) = toml_settings.and_then(|s| s.linter.select.as_ref()) {
+        selected_by_name.extend(select.iter().map(String::as_str));
+    }
+
+    let (named, unnamed): (Vec<_>, Vec<_>) = rules
+        .iter()
+        .filter(|rule| !kept_rules.iter().any(|kept| kept.name == rule.name))
+        .filter_map(|rule| {
+            Some((
+                rule.name.as_str(),
+                format_r_version(rule.minimum_r_version?),
+            ))
+        })
+        .partition(|(name, _)| selected_by_name.contains(name));
+
+    let mut warnings = named
+        .into_iter()
+        .map(|(name, required)| match r_version {
+            None => format!(
+                "Rule `{name}` is disabled because it requires R >= {required} and the minimum R version of the project is unknown. Set it with `--min-r-version`, with `min-r-version` in 'jarl.toml', or with `Depends: R (>= {required})` in DESCRIPTION."
+            ),
+            Some((version, source)) => format!(
+                "Rule `{name}` is disabled because it requires R >= {required} but the minimum R version of the project is {} (from {source}).",
+                format_r_version(*version)
+            ),
+        })
+        .collect::<Vec<_>>();
+
+    if check_config.verbose && !unnamed.is_empty() {
+        let summary = match unnamed.len() {
+            1 => "1 rule is".to_string(),
+            n => format!("{n} rules are"),
+        };
+        warnings.push(match r_version {
+            None => {
+                let names = unnamed
+                    .iter()
+                    .map(|(name, _)| format!("`{name}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "{summary} disabled because the minimum R version of the project is unknown: {names}. Set it with `--min-r-version`, with `min-r-version` in 'jarl.toml', or with `Depends` in DESCRIPTION."
+                )
+            }
+            Some((version, source)) => {
+                let names = unnamed
+                    .iter()
+                    .map(|(name, required)| format!("`{name}` (R >= {required})"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "{summary} disabled because the minimum R version of the project is {} (from {source}): {names}.",
+                    format_r_version(*version)
+                )
+            }
+        });
+    }
+
+    warnings
+}
+
+/// Format an R version as `"x.y.z"`.
+pub fn format_r_version((major, minor, patch): (u32, u32, u32)) -> String {
+    format!("{major}.{minor}.{patch}")
+}
+
 /// Parse R version string in format "x.y" or "x.y.z" and return (major, minor, patch)
 pub fn parse_r_version(min_r_version: String) -> Result<(u32, u32, u32)> {
     let parts: Vec<&str> = min_r_version.split('.').collect();
@@ -560,6 +944,7 @@ fn apply_fixable_filters(
                     categories: rule.categories.clone(),
                     fix_status: FixStatus::None,
                     minimum_r_version: rule.minimum_r_version,
+                    severity: rule.severity,
                 };
             }
 
@@ -572,6 +957,7 @@ fn apply_fixable_filters(
                     categories: rule.categories.clone(),
                     fix_status: FixStatus::None,
                     minimum_r_version: rule.minimum_r_version,
+                    severity: rule.severity,
                 };
             }
 
@@ -614,8 +1000,8 @@ fn parse_assignment(
                 }
                 _ => {
                     return Err(anyhow::anyhow!(
-                        "Invalid value in `--assignment-op`: {}",
-                        assignment_op
+                        "Invalid value `{assignment_op}` in {}. Expected one of: `<-`, `=`.",
+                        settings.source.field("assignment")
                     ));
                 }
             }
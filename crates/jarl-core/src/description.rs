@@ -6,9 +6,16 @@
 // MIT License - Posit PBC
 
 use anyhow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-/// Simple parser for R version requirements from DESCRIPTION files
+use crate::toml::LinterTomlOptions;
+
+/// Prefix of the fields of DESCRIPTION that configure jarl, e.g.
+/// `Config/jarl/select`.
+pub(crate) const CONFIG_PREFIX: &str = "Config/jarl/";
+
+/// Simple parser for R version requirements and jarl options from DESCRIPTION
+/// files
 pub struct Description;
 
 impl Description {
@@ -35,6 +42,121 @@ impl Description {
 
         Ok(r_versions)
     }
+
+    /// Extract the options of jarl from the `Config/jarl/*` fields of a
+    /// DESCRIPTION file
+    ///
+    /// Each field corresponds to an option of the `[lint]` section of
+    /// `jarl.toml`. Lists are separated by commas and tables are written like
+    /// `name = value`, e.g.:
+    /// - "Config/jarl/select: PERF, any_is_na"
+    /// - "Config/jarl/default-exclude: false"
+    /// - "Config/jarl/severity: PERF = hint, any_is_na = error"
+    ///
+    /// Returns `None` if there is no `Config/jarl/*` field.
+    pub fn get_jarl_options(contents: &str) -> anyhow::Result<Option<LinterTomlOptions>> {
+        let fields = parse_dcf(contents);
+
+        let mut config_fields = fields
+            .iter()
+            .filter_map(|(key, value)| Some((key.strip_prefix(CONFIG_PREFIX)?, value.as_str())))
+            .collect::<Vec<_>>();
+        if config_fields.is_empty() {
+            return Ok(None);
+        }
+        config_fields.sort();
+
+        let mut options = LinterTomlOptions::default();
+        for (name, value) in config_fields {
+            match name {
+                "select" => options.select = Some(parse_list(value)),
+                "ignore" => options.ignore = Some(parse_list(value)),
+                "fixable" => options.fixable = Some(parse_list(value)),
+                "unfixable" => options.unfixable = Some(parse_list(value)),
+                "exclude" => options.exclude = Some(parse_list(value)),
+                "default-exclude" => options.default_exclude = Some(parse_bool(name, value)?),
+                "assignment" => options.assignment = Some(unquote(value).to_string()),
+                "severity" => options.severity = Some(parse_table(name, value)?),
+                "document-extensions" => options.document_extensions = Some(parse_list(value)),
+                "roxygen-examples" => options.roxygen_examples = Some(parse_bool(name, value)?),
+                "require-nolint-reason" => {
+                    options.require_nolint_reason = Some(parse_bool(name, value)?)
+                }
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "Unknown field `{CONFIG_PREFIX}{name}` in DESCRIPTION. Expected one of: {}.",
+                        CONFIG_FIELDS
+                            .iter()
+                            .map(|field| format!("`{CONFIG_PREFIX}{field}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+            }
+        }
+
+        Ok(Some(options))
+    }
+}
+
+/// Options of jarl that can be set in DESCRIPTION
+const CONFIG_FIELDS: &[&str] = &[
+    "select",
+    "ignore",
+    "fixable",
+    "unfixable",
+    "exclude",
+    "default-exclude",
+    "assignment",
+    "severity",
+    "document-extensions",
+    "roxygen-examples",
+    "require-nolint-reason",
+];
+
+/// Remove the quotes around a value, e.g. `"<-"`
+pub(crate) fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// Parse a comma-separated list like "PERF, any_is_na"
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(unquote)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Parse a boolean, written like in R ("TRUE") or in TOML ("true")
+fn parse_bool(name: &str, value: &str) -> anyhow::Result<bool> {
+    match unquote(value) {
+        "TRUE" | "true" => Ok(true),
+        "FALSE" | "false" => Ok(false),
+        _ => Err(anyhow::anyhow!(
+            "Invalid value for `{CONFIG_PREFIX}{name}` in DESCRIPTION: expected `true` or `false`, got `{value}`."
+        )),
+    }
+}
+
+/// Parse a comma-separated table like "PERF = hint, any_is_na = error"
+fn parse_table(name: &str, value: &str) -> anyhow::Result<BTreeMap<String, String>> {
+    parse_list(value)
+        .iter()
+        .map(|item| {
+            let (key, value) = item.split_once('=').ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid value for `{CONFIG_PREFIX}{name}` in DESCRIPTION: expected `name = value`, got `{item}`."
+                )
+            })?;
+            Ok((unquote(key).to_string(), unquote(value).to_string()))
+        })
+        .collect()
 }
 
 /// Extract version number from an R dependency string like "R (>= 4.3.0)"
@@ -57,8 +179,9 @@ fn extract_version_from_dependency(dep: &str) -> Option<String> {
 }
 
 /// Parse a DCF (Debian Control File) format string into a key-value map
-/// Minimal implementation focused on extracting the Depends field
-fn parse_dcf(input: &str) -> HashMap<String, String> {
+/// Minimal implementation focused on extracting the Depends and Config/jarl
+/// fields, and the fields of `.lintr` files
+pub(crate) fn parse_dcf(input: &str) -> HashMap<String, String> {
     let mut fields = HashMap::new();
     let mut current_key: Option<String> = None;
     let mut current_value = String::new();
@@ -179,4 +302,60 @@ Depends: R ( >= 4.3.0 ), dplyr
         let result = Description::get_depend_r_version(description).unwrap();
         assert_eq!(result, vec!["4.3.0"]);
     }
+
+    #[test]
+    fn test_no_jarl_options() {
+        let description = r#"
+Package: mypackage
+Version: 1.0.0
+Config/testthat/edition: 3
+"#;
+        assert_eq!(Description::get_jarl_options(description).unwrap(), None);
+    }
+
+    #[test]
+    fn test_jarl_options() {
+        let description = r#"
+Package: mypackage
+Version: 1.0.0
+Config/jarl/select: PERF,
+    any_is_na
+Config/jarl/ignore: "lengths"
+Config/jarl/default-exclude: FALSE
+Config/jarl/assignment: =
+Config/jarl/severity: PERF = hint, any_is_na = error
+"#;
+        let options = Description::get_jarl_options(description).unwrap().unwrap();
+        assert_eq!(
+            options.select,
+            Some(vec!["PERF".to_string(), "any_is_na".to_string()])
+        );
+        assert_eq!(options.ignore, Some(vec!["lengths".to_string()]));
+        assert_eq!(options.default_exclude, Some(false));
+        assert_eq!(options.assignment, Some("=".to_string()));
+        assert_eq!(
+            options.severity,
+            Some(BTreeMap::from([
+                ("PERF".to_string(), "hint".to_string()),
+                ("any_is_na".to_string(), "error".to_string()),
+            ]))
+        );
+        assert_eq!(options.exclude, None);
+    }
+
+    #[test]
+    fn test_invalid_jarl_options() {
+        let err = Description::get_jarl_options("Config/jarl/foo: bar\n").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Unknown field `Config/jarl/foo` in DESCRIPTION.")
+        );
+
+        let err =
+            Description::get_jarl_options("Config/jarl/default-exclude: maybe\n").unwrap_err();
+        assert!(err.to_string().contains("expected `true` or `false`"));
+
+        let err = Description::get_jarl_options("Config/jarl/severity: PERF\n").unwrap_err();
+        assert!(err.to_string().contains("expected `name = value`"));
+    }
 }
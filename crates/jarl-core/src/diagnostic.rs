@@ -1,17 +1,127 @@
 use biome_rowan::TextRange;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
+use std::str::FromStr;
 
-use crate::lints::{nofix_rules_set, safe_rules_set, unsafe_rules_set};
+use crate::error::PARSE_ERROR;
 use crate::location::Location;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
-// The fix to apply to the violation.
-pub struct Fix {
+/// Replacement of the text between `start` and `end` (byte offsets in the
+/// original code) by `content`. An insertion has `start == end` and a deletion
+/// has an empty `content`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Edit {
     pub content: String,
     pub start: usize,
     pub end: usize,
+}
+
+impl Edit {
+    /// Do the two edits modify the same part of the code? Edits that touch
+    /// each other also conflict since the order in which they are applied
+    /// would matter, e.g. two insertions at the same position.
+    pub fn conflicts_with(&self, other: &Edit) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+/// Whether a fix can be applied automatically.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum Applicability {
+    /// The fix preserves the behavior of the code, it is applied with `--fix`.
+    Safe,
+    /// The fix may change the behavior of the code, it is only applied with
+    /// `--unsafe-fixes`.
+    Unsafe,
+    /// The fix is only shown as a suggestion and is never applied.
+    DisplayOnly,
+}
+
+/// How important a diagnostic is. Severities are ordered from the least
+/// (`Hint`) to the most (`Error`) important.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum Severity {
+    Hint,
+    Info,
+    #[default]
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub const ALL: &'static [Severity] = &[
+        Severity::Error,
+        Severity::Warning,
+        Severity::Info,
+        Severity::Hint,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Hint => "hint",
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+
+    /// Default severity of a rule: correctness issues (`CORR`) are errors,
+    /// everything else is a warning.
+    pub fn from_categories<S: AsRef<str>>(categories: &[S]) -> Self {
+        if categories
+            .iter()
+            .any(|category| category.as_ref() == "CORR")
+        {
+            Severity::Error
+        } else {
+            Severity::Warning
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Severity::ALL
+            .iter()
+            .find(|severity| severity.as_str() == s)
+            .copied()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid severity `{s}`. Expected one of: {}.",
+                    Severity::ALL
+                        .iter()
+                        .map(|severity| severity.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+// The fix to apply to the violation. It can contain several edits, for
+// instance to rewrite a call and add a helper elsewhere in the file. The edits
+// of a fix are always applied together.
+pub struct Fix {
+    pub edits: Vec<Edit>,
+    // A rule can report fixes with different applicability, e.g. a fix that is
+    // safe in most cases but not in some edge cases.
+    pub applicability: Applicability,
     // TODO: This is used only to not add a Fix when the node contains a comment
     // because I don't know how to handle them for now, #95.
     pub to_skip: bool,
@@ -20,12 +130,15 @@ pub struct Fix {
 impl Fix {
     pub fn empty() -> Self {
         Self {
-            content: "".to_string(),
-            start: 0usize,
-            end: 0usize,
+            edits: vec![],
+            applicability: Applicability::DisplayOnly,
             to_skip: true,
         }
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
 }
 
 /// Details on the violated rule.
@@ -58,6 +171,22 @@ pub struct Diagnostic {
     pub location: Option<Location>,
     // Fix to apply if the user passed `--fix`.
     pub fix: Fix,
+    // How important the violation is, set from the configuration of the rule.
+    #[serde(default)]
+    pub severity: Severity,
+    // The `# nolint` comment that suppresses the violation. Suppressed
+    // violations are only reported with `--show-suppressed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppression: Option<Suppression>,
+}
+
+/// A `# nolint` comment that suppresses a violation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    /// The text of the comment, e.g. `# nolint: any_is_na -- NA are removed`.
+    pub comment: String,
+    /// The reason given after `--` in the comment, if any.
+    pub reason: Option<String>,
 }
 
 impl<T: Violation> From<T> for ViolationData {
@@ -92,6 +221,8 @@ impl Diagnostic {
             location: None,
             fix,
             filename: "".into(),
+            severity: Severity::default(),
+            suppression: None,
         }
     }
 
@@ -102,19 +233,41 @@ impl Diagnostic {
             location: None,
             fix: Fix::empty(),
             filename: "".into(),
+            severity: Severity::default(),
+            suppression: None,
         }
     }
 
-    // TODO: in these three functions, the first condition should be removed
-    // once comments in nodes are better handled, #95.
+    // TODO: in these three functions, the `to_skip` condition should be
+    // removed once comments in nodes are better handled, #95.
     pub fn has_safe_fix(&self) -> bool {
-        !self.fix.to_skip && safe_rules_set().contains(&self.message.name)
+        self.has_fix_with(Applicability::Safe)
     }
     pub fn has_unsafe_fix(&self) -> bool {
-        !self.fix.to_skip && unsafe_rules_set().contains(&self.message.name)
+        self.has_fix_with(Applicability::Unsafe)
     }
     pub fn has_no_fix(&self) -> bool {
-        self.fix.to_skip || nofix_rules_set().contains(&self.message.name)
+        !self.has_safe_fix() && !self.has_unsafe_fix()
+    }
+
+    fn has_fix_with(&self, applicability: Applicability) -> bool {
+        !self.fix.to_skip && !self.fix.is_empty() && self.fix.applicability == applicability
+    }
+
+    /// Does this diagnostic report a syntax error instead of a rule violation?
+    pub fn is_parse_error(&self) -> bool {
+        self.message.name == PARSE_ERROR
+    }
+
+    /// Is this violation suppressed by a `# nolint` comment? Those are only
+    /// reported with `--show-suppressed`.
+    pub fn is_suppressed(&self) -> bool {
+        self.suppression.is_some()
+    }
+
+    /// Should the fix be applied, given whether the user allowed unsafe fixes?
+    pub fn is_fix_applicable(&self, unsafe_fixes: bool) -> bool {
+        self.has_safe_fix() || (unsafe_fixes && self.has_unsafe_fix())
     }
 }
 
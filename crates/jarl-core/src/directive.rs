@@ -12,6 +12,10 @@ pub enum LintDirective {
     SkipStartRules(Vec<String>),
     /// End a skip block
     SkipEnd,
+    /// Skip all lints on the next line
+    SkipNext,
+    /// Skip specific lints on the next line, e.g. "# nolint next: any_is_na"
+    SkipNextRules(Vec<String>),
 }
 
 /// Parse a comment directive
@@ -24,10 +28,20 @@ pub enum LintDirective {
 /// # nolint start
 /// # nolint start: rule1, rule2
 /// # nolint end
+/// # nolint next
+/// # nolint next: rule1, rule2
 /// ```
 ///
 /// Note that directives are applied to the node they are attached to,
-/// except for start/end directives which define regions.
+/// except for start/end directives which define regions and next directives
+/// which apply to the next line.
+///
+/// For compatibility with `lintr`, the list of rules can end with a period,
+/// e.g. `# nolint: any_is_na_linter.`
+///
+/// All directives can end with a reason after `--`, e.g.
+/// `# nolint: any_is_na -- NA are handled upstream`. It is ignored here, see
+/// [split_nolint_reason()].
 ///
 /// `text` should be single line but we don't check for this. A potential usage
 /// of this function is to iterate over a document line by line to scan for a
@@ -44,6 +58,7 @@ pub fn parse_comment_directive(text: &str) -> Option<LintDirective> {
     }
 
     let text = &text[2..]; // Skip "# "
+    let (text, _) = split_nolint_reason(text);
 
     // Handle "nolint" specially to allow various forms
     if let Some(stripped) = text.strip_prefix("nolint") {
@@ -59,8 +74,7 @@ pub fn parse_comment_directive(text: &str) -> Option<LintDirective> {
                 return Some(LintDirective::SkipStart);
             } else if let Some(after_colon) = after_start.strip_prefix(':') {
                 // "# nolint start: rules"
-                let after_colon = after_colon.trim();
-                return parse_lint_directive_for_start(after_colon);
+                return parse_rule_names(after_colon).map(LintDirective::SkipStartRules);
             } else {
                 // "# nolint start" followed by something that's not a colon -> invalid
                 return None;
@@ -68,10 +82,19 @@ pub fn parse_comment_directive(text: &str) -> Option<LintDirective> {
         } else if rest == "end" {
             // "# nolint end"
             return Some(LintDirective::SkipEnd);
+        } else if let Some(after_next) = rest.strip_prefix("next") {
+            // "# nolint next" or "# nolint next: rules"
+            let after_next = after_next.trim_start();
+            if after_next.is_empty() {
+                return Some(LintDirective::SkipNext);
+            } else if let Some(after_colon) = after_next.strip_prefix(':') {
+                return parse_rule_names(after_colon).map(LintDirective::SkipNextRules);
+            } else {
+                return None;
+            }
         } else if let Some(after_colon) = rest.strip_prefix(':') {
             // "# nolint: rules"
-            let after_colon = after_colon.trim();
-            return parse_lint_directive(after_colon);
+            return parse_rule_names(after_colon).map(LintDirective::SkipRules);
         } else {
             // "# nolint" followed by something that's not recognized -> invalid
             return None;
@@ -100,42 +123,160 @@ pub fn parse_special_skip_file(text: &str) -> Option<LintDirective> {
     None
 }
 
-#[inline]
-fn parse_lint_directive(text: &str) -> Option<LintDirective> {
-    // Parse comma-separated rule names, e.g. "any_is_na, coalesce"
-    let rules: Vec<String> = text
-        .split(',')
-        .map(|s| s.trim().trim_end_matches("_linter").to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
+/// Add a rule to an existing `# nolint: rules` comment
+///
+/// `line` is the comment, possibly indented, e.g. `# nolint: any_is_na`
+/// becomes `# nolint: any_is_na, coalesce`. This is used by the code actions
+/// of the LSP and by `jarl check --add-nolint`.
+///
+/// Returns `None` if `line` is not a `# nolint: rules` comment, or if it
+/// already skips `rule_name`. A generic `# nolint` already skips all rules.
+pub fn update_existing_nolint(line: &str, rule_name: &str) -> Option<String> {
+    let Some(LintDirective::SkipRules(rules)) = parse_comment_directive(line) else {
+        return None;
+    };
 
-    if rules.is_empty() {
-        None
+    if rules.iter().any(|rule| rule == rule_name) {
+        return None;
+    }
+
+    // The rule is added before the reason, if any
+    let (line, reason) = match reason_separator(line) {
+        Some(start) => (&line[..start], format!(" {}", line[start..].trim_end())),
+        None => (line, String::new()),
+    };
+    let line = line.trim_end();
+    match line.strip_suffix('.') {
+        Some(line) => Some(format!("{line}, {rule_name}.{reason}")),
+        None => Some(format!("{line}, {rule_name}{reason}")),
+    }
+}
+
+/// Split a `# nolint` comment into the directive and the reason that follows
+/// `--`, e.g. `# nolint: any_is_na -- NA are handled upstream`
+///
+/// The reason is `None` if there is no `--` after the directive, or if nothing
+/// follows it.
+pub fn split_nolint_reason(text: &str) -> (&str, Option<&str>) {
+    match reason_separator(text) {
+        Some(start) => {
+            let reason = text[start + 2..].trim();
+            (
+                text[..start].trim_end(),
+                if reason.is_empty() {
+                    None
+                } else {
+                    Some(reason)
+                },
+            )
+        }
+        None => (text, None),
+    }
+}
+
+/// Position of the `--` that separates a `# nolint` comment from its reason
+///
+/// The `--` must directly follow the head of the directive (e.g.
+/// `# nolint start`) or its list of rules, so that a `--` in some other text,
+/// e.g. `# nolint: any_is_na. Copied from pkg--utils`, is not a separator.
+fn reason_separator(text: &str) -> Option<usize> {
+    let rest = text.trim_start_matches(|c: char| c == '#' || c.is_whitespace());
+    let rest = rest.strip_prefix("nolint")?.trim_start();
+    let rest = ["start", "next", "end"]
+        .iter()
+        .find_map(|keyword| rest.strip_prefix(keyword))
+        .unwrap_or(rest)
+        .trim_start();
+    let rest = match rest.strip_prefix(':') {
+        Some(rules) => {
+            // lintr compatibility, the list of rules can end with a period
+            let rules = rules.trim_start_matches(|c: char| {
+                c.is_alphanumeric() || c == '_' || c == ',' || c.is_whitespace()
+            });
+            rules.strip_prefix('.').unwrap_or(rules).trim_start()
+        }
+        None => rest,
+    };
+
+    if rest.starts_with("--") {
+        Some(text.len() - rest.len())
     } else {
-        Some(LintDirective::SkipRules(rules))
+        None
     }
 }
 
+/// Parse comma-separated rule names, e.g. "any_is_na, coalesce"
+///
+/// Returns `None` if there are no rule names.
 #[inline]
-fn parse_lint_directive_for_start(text: &str) -> Option<LintDirective> {
-    // Parse comma-separated rule names for start directive
+fn parse_rule_names(text: &str) -> Option<Vec<String>> {
+    // lintr requires the list of rules to end with a period
+    let text = text.trim();
+    let text = text.strip_suffix('.').unwrap_or(text);
+
     let rules: Vec<String> = text
         .split(',')
         .map(|s| s.trim().trim_end_matches("_linter").to_string())
         .filter(|s| !s.is_empty())
         .collect();
 
-    if rules.is_empty() {
-        None
-    } else {
-        Some(LintDirective::SkipStartRules(rules))
-    }
+    if rules.is_empty() { None } else { Some(rules) }
 }
 
 #[cfg(test)]
 mod test {
     use crate::directive::LintDirective;
-    use crate::directive::parse_comment_directive;
+    use crate::directive::{parse_comment_directive, split_nolint_reason, update_existing_nolint};
+
+    #[test]
+    fn test_update_existing_nolint() {
+        assert_eq!(
+            update_existing_nolint("# nolint: any_is_na", "coalesce"),
+            Some("# nolint: any_is_na, coalesce".to_string())
+        );
+        assert_eq!(
+            update_existing_nolint("  # nolint: any_is_na_linter ", "coalesce"),
+            Some("  # nolint: any_is_na_linter, coalesce".to_string())
+        );
+
+        // The rule is already skipped
+        assert_eq!(
+            update_existing_nolint("# nolint: any_is_na", "any_is_na"),
+            None
+        );
+        assert_eq!(
+            update_existing_nolint("# nolint: any_is_na_linter", "any_is_na"),
+            None
+        );
+        assert_eq!(update_existing_nolint("# nolint", "any_is_na"), None);
+
+        // lintr style, the list ends with a period
+        assert_eq!(
+            update_existing_nolint("# nolint: any_is_na_linter.", "coalesce"),
+            Some("# nolint: any_is_na_linter, coalesce.".to_string())
+        );
+
+        // The rule is added before the reason
+        assert_eq!(
+            update_existing_nolint("# nolint: any_is_na -- NA are handled upstream", "coalesce"),
+            Some("# nolint: any_is_na, coalesce -- NA are handled upstream".to_string())
+        );
+        assert_eq!(
+            update_existing_nolint("# nolint: any_is_na_linter. -- legacy code", "coalesce"),
+            Some("# nolint: any_is_na_linter, coalesce. -- legacy code".to_string())
+        );
+        assert_eq!(
+            update_existing_nolint("# nolint: any_is_na -- see pkg--utils", "coalesce"),
+            Some("# nolint: any_is_na, coalesce -- see pkg--utils".to_string())
+        );
+
+        // Not a `# nolint: rules` comment
+        assert_eq!(update_existing_nolint("# a comment", "any_is_na"), None);
+        assert_eq!(
+            update_existing_nolint("# nolint start: coalesce", "any_is_na"),
+            None
+        );
+    }
 
     #[test]
     fn test_lint_directive() {
@@ -195,6 +336,105 @@ mod test {
 
         // Can't have text after nolint without a colon
         assert_eq!(parse_comment_directive("# nolint any_is_na"), None);
+
+        // lintr compatibility: the list of rules can end with a period
+        let result = parse_comment_directive("# nolint: any_is_na_linter, coalesce_linter.");
+        assert!(matches!(
+            result,
+            Some(LintDirective::SkipRules(ref rules))
+            if rules == &vec!["any_is_na", "coalesce"]
+        ));
+        assert_eq!(parse_comment_directive("# nolint: ."), None);
+    }
+
+    #[test]
+    fn test_lint_directive_next() {
+        assert_eq!(
+            parse_comment_directive("# nolint next"),
+            Some(LintDirective::SkipNext)
+        );
+
+        let result = parse_comment_directive("# nolint next: any_is_na_linter, coalesce.");
+        assert!(matches!(
+            result,
+            Some(LintDirective::SkipNextRules(ref rules))
+            if rules == &vec!["any_is_na", "coalesce"]
+        ));
+
+        // Invalid forms
+        assert_eq!(parse_comment_directive("# nolint next:"), None);
+        assert_eq!(parse_comment_directive("# nolint next any_is_na"), None);
+        assert_eq!(parse_comment_directive("# nolint nextline"), None);
+    }
+
+    #[test]
+    fn test_lint_directive_reason() {
+        assert_eq!(
+            parse_comment_directive("# nolint -- generated code"),
+            Some(LintDirective::Skip)
+        );
+
+        let result = parse_comment_directive("# nolint: any_is_na, coalesce -- NA are removed");
+        assert!(matches!(
+            result,
+            Some(LintDirective::SkipRules(ref rules))
+            if rules == &vec!["any_is_na", "coalesce"]
+        ));
+
+        assert_eq!(
+            parse_comment_directive("# nolint start -- vendored code"),
+            Some(LintDirective::SkipStart)
+        );
+        assert_eq!(
+            parse_comment_directive("# nolint end -- vendored code"),
+            Some(LintDirective::SkipEnd)
+        );
+        assert_eq!(
+            parse_comment_directive("# nolint next: any_is_na. -- NA are removed"),
+            Some(LintDirective::SkipNextRules(vec!["any_is_na".to_string()]))
+        );
+
+        // The reason doesn't replace the rules
+        assert_eq!(parse_comment_directive("# nolint: -- NA are removed"), None);
+    }
+
+    #[test]
+    fn test_split_nolint_reason() {
+        assert_eq!(
+            split_nolint_reason("# nolint: any_is_na -- NA are removed "),
+            ("# nolint: any_is_na", Some("NA are removed"))
+        );
+        assert_eq!(
+            split_nolint_reason("# nolint --reason"),
+            ("# nolint", Some("reason"))
+        );
+        assert_eq!(split_nolint_reason("# nolint --  "), ("# nolint", None));
+        assert_eq!(
+            split_nolint_reason("# nolint: any_is_na"),
+            ("# nolint: any_is_na", None)
+        );
+        assert_eq!(
+            split_nolint_reason("# nolint start: any_is_na. -- vendored"),
+            ("# nolint start: any_is_na.", Some("vendored"))
+        );
+
+        // Only a `--` right after the directive starts the reason
+        assert_eq!(
+            split_nolint_reason("# nolint: any_is_na -- see pkg--utils"),
+            ("# nolint: any_is_na", Some("see pkg--utils"))
+        );
+        assert_eq!(
+            split_nolint_reason("# nolint: any_is_na. Copied from pkg--utils"),
+            ("# nolint: any_is_na. Copied from pkg--utils", None)
+        );
+        assert_eq!(
+            split_nolint_reason("# nolint: any_is_na (see `x--y`)"),
+            ("# nolint: any_is_na (see `x--y`)", None)
+        );
+        assert_eq!(
+            split_nolint_reason("# a comment -- nolint"),
+            ("# a comment -- nolint", None)
+        );
     }
 
     #[test]
@@ -5,16 +5,22 @@
 // MIT License - Posit PBC
 
 use ignore::DirEntry;
+use ignore::overrides::{Override, OverrideBuilder};
 use rustc_hash::FxHashSet;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
+use crate::description::Description;
 use crate::fs;
-use crate::fs::has_r_extension;
+use crate::fs::{DEFAULT_DOCUMENT_EXTENSIONS, has_r_extension, is_document_extension};
 use crate::settings::Settings;
+use crate::settings::SettingsSource;
+use crate::toml::TomlOptions;
 use crate::toml::find_jarl_toml_in_directory;
-use crate::toml::parse_jarl_toml;
+use crate::toml::load_jarl_toml;
 use air_workspace::resolve::PathResolver;
+use anyhow::Context;
 
 /// Default patterns to exclude from linting
 /// These match common R project files that should not be linted
@@ -34,14 +40,17 @@ pub struct DiscoveredSettings {
     pub settings: Settings,
 }
 
-/// This is the core function for walking a set of `paths` looking for `air.toml`s.
+/// This is the core function for walking a set of `paths` looking for `jarl.toml`s.
 ///
 /// You typically follow this function up by loading the set of returned path into a
-/// [crate::resolve::PathResolver].
+/// [PathResolver], and use [resolve_settings()] to find the settings of a file.
 ///
 /// For each `path`, we:
-/// - Walk up its ancestors, looking for an `air.toml`
-/// - TODO(hierarchical): Walk down its children, looking for nested `air.toml`s
+/// - Walk up its ancestors, looking for a `jarl.toml`
+/// - Walk down its children, looking for nested `jarl.toml`s
+///
+/// A `DESCRIPTION` with `Config/jarl/*` fields is used like a `jarl.toml` if
+/// there is no `jarl.toml` in the same directory.
 pub fn discover_settings<P: AsRef<Path>>(paths: &[P]) -> anyhow::Result<Vec<DiscoveredSettings>> {
     let paths: Vec<PathBuf> = paths.iter().map(fs::normalize_path).collect();
 
@@ -58,8 +67,7 @@ pub fn discover_settings<P: AsRef<Path>>(paths: &[P]) -> anyhow::Result<Vec<Disc
                 break;
             }
 
-            if let Some(toml) = find_jarl_toml_in_directory(ancestor) {
-                let settings = parse_settings(&toml, ancestor)?;
+            if let Some(settings) = find_settings_in_directory(ancestor)? {
                 discovered_settings
                     .push(DiscoveredSettings { directory: ancestor.to_path_buf(), settings });
                 break;
@@ -67,19 +75,140 @@ pub fn discover_settings<P: AsRef<Path>>(paths: &[P]) -> anyhow::Result<Vec<Disc
         }
     }
 
-    // TODO(hierarchical): Also iterate into the directories and collect `air.toml`
-    // found nested withing the directories for hierarchical support
+    // Also collect the `jarl.toml` nested in the directories, e.g. in a
+    // repository that contains several packages.
+    for path in paths.iter().filter(|path| path.is_dir()) {
+        discover_nested_settings(path, &mut discovered_settings)?;
+    }
 
     Ok(discovered_settings)
 }
 
-/// Parse [Settings] from a given `air.toml`
-// TODO(hierarchical): Allow for an `extends` option in `air.toml`, which will make things
-// more complex, but will be very useful once we support hierarchical configuration as a
-// way of "inheriting" most top level configuration while slightly tweaking it in a nested directory.
+/// Find the settings that apply to `path`, i.e. those of the nearest
+/// `jarl.toml` in its ancestors. This returns the directory of this
+/// `jarl.toml` along with its settings.
+pub fn resolve_settings<'a>(
+    resolver: &'a PathResolver<Settings>,
+    path: &Path,
+) -> Option<(PathBuf, &'a Settings)> {
+    let path = fs::normalize_path(path);
+    resolver
+        .items()
+        .iter()
+        .filter(|item| path.starts_with(item.path()))
+        .max_by_key(|item| Path::new(item.path()).components().count())
+        .map(|item| (Path::new(item.path()).to_path_buf(), item.value()))
+}
+
+/// Collect the settings of the directories below `path` (excluding `path`
+/// itself) into `discovered_settings`.
+///
+/// Like when we look for R files, we skip the hidden and git-ignored
+/// directories, as well as those excluded by the nearest settings (e.g.
+/// `renv/`), so that we never read the `DESCRIPTION` of a vendored package.
+fn discover_nested_settings(
+    path: &Path,
+    discovered_settings: &mut Vec<DiscoveredSettings>,
+) -> anyhow::Result<()> {
+    // Directories are excluded by the settings found above `path`, or by the
+    // default settings if there are none. The settings found while walking
+    // are added as we go: the walk yields a directory before its children.
+    let ancestors = discovered_settings
+        .iter()
+        .filter(|discovered| path.starts_with(&discovered.directory))
+        .map(|discovered| (discovered.directory.as_path(), &discovered.settings))
+        .collect::<Vec<_>>();
+    let default_settings = Settings::default();
+    let ancestors = if ancestors.is_empty() {
+        vec![(path, &default_settings)]
+    } else {
+        ancestors
+    };
+    let excludes = ancestors
+        .into_iter()
+        .filter_map(|(root, settings)| {
+            build_exclude(root, settings).map(|exclude| (root.to_path_buf(), exclude))
+        })
+        .collect::<Vec<_>>();
+    let excludes = Arc::new(Mutex::new(excludes));
+
+    let walker = ignore::WalkBuilder::new(path)
+        .hidden(true)
+        .parents(true)
+        .ignore(false)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .filter_entry({
+            let excludes = Arc::clone(&excludes);
+            move |entry| {
+                let is_directory = entry.file_type().is_none_or(|ft| ft.is_dir());
+                entry.depth() == 0
+                    || !is_excluded(&excludes.lock().unwrap(), entry.path(), is_directory)
+            }
+        })
+        .build();
+
+    for entry in walker.filter_map(Result::ok) {
+        if entry.depth() == 0 || !entry.file_type().is_some_and(|ft| ft.is_dir()) {
+            continue;
+        }
+        let directory = entry.into_path();
+        if discovered_settings
+            .iter()
+            .any(|discovered| discovered.directory == directory)
+        {
+            continue;
+        }
+        if let Some(settings) = find_settings_in_directory(&directory)? {
+            if let Some(exclude) = build_exclude(&directory, &settings) {
+                excludes.lock().unwrap().push((directory.clone(), exclude));
+            }
+            discovered_settings.push(DiscoveredSettings { directory, settings });
+        }
+    }
+
+    Ok(())
+}
+
+/// Find the [Settings] defined in `directory`: those of its `jarl.toml` if
+/// there is one, or else those of the `Config/jarl/*` fields of its
+/// `DESCRIPTION`.
+fn find_settings_in_directory(directory: &Path) -> anyhow::Result<Option<Settings>> {
+    if let Some(toml) = find_jarl_toml_in_directory(directory) {
+        return Ok(Some(parse_settings(&toml, directory)?));
+    }
+
+    let description = directory.join("DESCRIPTION");
+    if !description.is_file() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(&description)
+        .with_context(|| format!("Failed to read {}", description.display()))?;
+    // Like in `jarl.toml`, invalid fields fail the run rather than being
+    // silently ignored.
+    let invalid = || {
+        format!(
+            "Invalid `Config/jarl/*` fields in {}",
+            description.display()
+        )
+    };
+    let Some(lint) = Description::get_jarl_options(&contents).with_context(invalid)? else {
+        return Ok(None);
+    };
+
+    let options = TomlOptions { lint: Some(lint), ..Default::default() };
+    let settings = options
+        .into_settings(directory, SettingsSource::Description)
+        .with_context(invalid)?;
+    Ok(Some(settings))
+}
+
+/// Parse [Settings] from a given `jarl.toml`, including the options of the
+/// files it extends.
 fn parse_settings(toml: &Path, root_directory: &Path) -> anyhow::Result<Settings> {
-    let options = parse_jarl_toml(toml)?;
-    let settings = options.into_settings(root_directory)?;
+    let options = load_jarl_toml(toml)?;
+    let settings = options.into_settings(root_directory, SettingsSource::JarlToml)?;
     Ok(settings)
 }
 
@@ -121,43 +250,34 @@ pub fn discover_r_file_paths<P: AsRef<Path>>(
     builder.git_global(true);
     builder.git_exclude(true);
 
-    // Add exclude patterns from settings if linter settings should be used
-    if use_linter_settings && let Some(settings_item) = resolver.items().first() {
-        let settings = settings_item.value();
-        let root = settings_item.path();
-
-        // Check if default_exclude is disabled (true by default)
-        let use_default_exclude = settings.linter.default_exclude.unwrap_or(true);
-
-        // Build custom ignore patterns
-        let mut patterns = Vec::new();
-
-        if use_default_exclude {
-            // Add default exclude patterns
-            patterns.extend_from_slice(DEFAULT_EXCLUDE_PATTERNS);
-        }
-
-        // Add custom exclude patterns from jarl.toml
-        if let Some(exclude_patterns) = &settings.linter.exclude {
-            for pattern in exclude_patterns {
-                patterns.push(pattern.as_str());
-            }
-        }
+    // Each `jarl.toml` has its own exclude patterns, relative to its directory.
+    let excludes = if use_linter_settings {
+        resolver
+            .items()
+            .iter()
+            .filter_map(|item| {
+                let root = Path::new(item.path());
+                build_exclude(root, item.value()).map(|exclude| (root.to_path_buf(), exclude))
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
 
-        // If we have patterns, create an override and add it to the builder
-        if !patterns.is_empty() {
-            let mut override_builder = ignore::overrides::OverrideBuilder::new(root);
-            for pattern in patterns {
-                // Add as negation pattern (exclude)
-                if let Err(e) = override_builder.add(&format!("!{pattern}")) {
-                    tracing::warn!("Failed to add exclude pattern '{}': {}", pattern, e);
-                }
-            }
-            if let Ok(overrides) = override_builder.build() {
-                builder.overrides(overrides);
-            }
-        }
-    }
+    // Each `jarl.toml` also decides which documents (e.g. R Markdown) are
+    // checked.
+    let document_extensions = if use_linter_settings {
+        resolver
+            .items()
+            .iter()
+            .filter_map(|item| {
+                let extensions = item.value().linter.document_extensions.clone()?;
+                Some((Path::new(item.path()).to_path_buf(), extensions))
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
 
     // Prefer `available_parallelism()`, with a max of 12 threads
     builder.threads(
@@ -169,21 +289,104 @@ pub fn discover_r_file_paths<P: AsRef<Path>>(
     let walker = builder.build_parallel();
 
     // Run the `WalkParallel` to collect all R files.
-    let state = FilesState::new();
+    let state = FilesState::new(excludes, document_extensions);
     let mut visitor_builder = FilesVisitorBuilder::new(&state);
     walker.visit(&mut visitor_builder);
 
     state.finish()
 }
 
+/// Build the matcher of the files excluded by `settings`, whose `jarl.toml` is
+/// in `root`. Returns `None` if nothing is excluded.
+fn build_exclude(root: &Path, settings: &Settings) -> Option<Override> {
+    // Check if default_exclude is disabled (true by default)
+    let use_default_exclude = settings.linter.default_exclude.unwrap_or(true);
+
+    // Build custom ignore patterns
+    let mut patterns = Vec::new();
+
+    if use_default_exclude {
+        // Add default exclude patterns
+        patterns.extend_from_slice(DEFAULT_EXCLUDE_PATTERNS);
+    }
+
+    // Add custom exclude patterns from jarl.toml
+    if let Some(exclude_patterns) = &settings.linter.exclude {
+        for pattern in exclude_patterns {
+            patterns.push(pattern.as_str());
+        }
+    }
+
+    if patterns.is_empty() {
+        return None;
+    }
+
+    let mut override_builder = OverrideBuilder::new(root);
+    for pattern in patterns {
+        // Add as negation pattern (exclude)
+        if let Err(e) = override_builder.add(&format!("!{pattern}")) {
+            tracing::warn!("Failed to add exclude pattern '{}': {}", pattern, e);
+        }
+    }
+    override_builder.build().ok()
+}
+
+/// Is `path` excluded by the patterns of the nearest `jarl.toml`, given the
+/// exclude patterns of each `jarl.toml` with its directory?
+fn is_excluded(excludes: &[(PathBuf, Override)], path: &Path, is_directory: bool) -> bool {
+    excludes
+        .iter()
+        .filter(|(root, _)| path.starts_with(root))
+        .max_by_key(|(root, _)| root.components().count())
+        .is_some_and(|(_, exclude)| exclude.matched(path, is_directory).is_ignore())
+}
+
 /// Shared state across the threads of the walker
 struct FilesState {
     files: std::sync::Mutex<DiscoveredFiles>,
+    /// Exclude patterns of each `jarl.toml`, with the directory of the
+    /// `jarl.toml`.
+    excludes: Vec<(PathBuf, Override)>,
+    /// Extensions of the documents to check of each `jarl.toml` that sets
+    /// `document-extensions`, with the directory of the `jarl.toml`.
+    document_extensions: Vec<(PathBuf, Vec<String>)>,
 }
 
 impl FilesState {
-    fn new() -> Self {
-        Self { files: std::sync::Mutex::new(Vec::new()) }
+    fn new(
+        excludes: Vec<(PathBuf, Override)>,
+        document_extensions: Vec<(PathBuf, Vec<String>)>,
+    ) -> Self {
+        Self {
+            files: std::sync::Mutex::new(Vec::new()),
+            excludes,
+            document_extensions,
+        }
+    }
+
+    /// Is `path` an R script, or a document whose R chunks are checked
+    /// according to the nearest `jarl.toml`?
+    fn is_included(&self, path: &Path) -> bool {
+        if has_r_extension(path) {
+            return true;
+        }
+        let Some(extension) = path.extension().and_then(|extension| extension.to_str()) else {
+            return false;
+        };
+        match self
+            .document_extensions
+            .iter()
+            .filter(|(root, _)| path.starts_with(root))
+            .max_by_key(|(root, _)| root.components().count())
+        {
+            Some((_, extensions)) => is_document_extension(extension, extensions),
+            None => is_document_extension(extension, DEFAULT_DOCUMENT_EXTENSIONS),
+        }
+    }
+
+    /// Is `path` excluded by the nearest `jarl.toml`?
+    fn is_excluded(&self, path: &Path, is_directory: bool) -> bool {
+        is_excluded(&self.excludes, path, is_directory)
     }
 
     fn finish(self) -> DiscoveredFiles {
@@ -270,8 +473,17 @@ impl ignore::ParallelVisitor for FilesVisitor<'_> {
             return ignore::WalkState::Continue;
         }
 
-        // Check if this is an R file (has .R extension)
-        if !is_directory && has_r_extension(path) {
+        if self.state.is_excluded(path, is_directory) {
+            tracing::trace!(
+                "Excluded file due to exclude patterns {path}",
+                path = path.display()
+            );
+            return ignore::WalkState::Skip;
+        }
+
+        // Check if this is an R file (has .R extension) or a document with R
+        // chunks (e.g. .Rmd)
+        if !is_directory && self.state.is_included(path) {
             tracing::trace!("Included R file {path}", path = path.display());
             self.files.push(Ok(entry.into_path()));
             return ignore::WalkState::Continue;
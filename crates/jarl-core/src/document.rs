@@ -0,0 +1,305 @@
+//! R code chunks of R Markdown, Quarto and Sweave documents.
+//!
+//! Each chunk is linted separately. To keep the location of diagnostics and
+//! fixes in the document, a chunk is linted as a copy of the document where
+//! everything but the code of the chunk is replaced by spaces (see
+//! [mask_outside()]). Lines and byte offsets are then the same as in the
+//! document, so diagnostics don't need to be mapped back.
+
+use biome_rowan::{TextRange, TextSize};
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentKind {
+    /// R Markdown and Quarto, where chunks start with ```` ```{r} ```` and end
+    /// with ```` ``` ````.
+    Markdown,
+    /// Sweave, where chunks start with `<<>>=` and end with `@`.
+    Sweave,
+}
+
+impl DocumentKind {
+    /// The kind of document of `path`, or `None` if it is not a document with
+    /// R chunks (e.g. an R script).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "rmd" | "qmd" => Some(Self::Markdown),
+            "rnw" => Some(Self::Sweave),
+            _ => None,
+        }
+    }
+}
+
+/// Find the ranges of the code of the R chunks of `contents` that are
+/// evaluated. Chunks with `eval = FALSE` (or `#| eval: false`) and chunks of
+/// other engines (e.g. `{python}`) are skipped.
+pub fn find_r_chunks(contents: &str, kind: DocumentKind) -> Vec<TextRange> {
+    match kind {
+        DocumentKind::Markdown => find_markdown_chunks(contents),
+        DocumentKind::Sweave => find_sweave_chunks(contents),
+    }
+}
+
+/// Replace everything that is outside of `range` by spaces, except line
+/// endings. The result has the same length and lines as `contents`.
+pub fn mask_outside(contents: &str, range: TextRange) -> String {
+    let mut masked = String::with_capacity(contents.len());
+    for (offset, character) in contents.char_indices() {
+        let offset = TextSize::from(offset as u32);
+        if range.contains(offset) || matches!(character, '\n' | '\r') {
+            masked.push(character);
+        } else {
+            // Non-ASCII characters take several bytes, so we need as many
+            // spaces to keep the same offsets.
+            masked.extend(std::iter::repeat_n(' ', character.len_utf8()));
+        }
+    }
+    masked
+}
+
+/// A chunk whose start was found but not its end yet.
+struct OpenChunk {
+    /// Number of backticks of the fence (only for Markdown).
+    fence: usize,
+    /// Offset of the first line of code.
+    code_start: usize,
+    /// Is this an R chunk that is evaluated?
+    lint: bool,
+}
+
+fn find_markdown_chunks(contents: &str) -> Vec<TextRange> {
+    let mut chunks = vec![];
+    let mut open: Option<OpenChunk> = None;
+    let mut offset = 0;
+
+    for line in contents.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim();
+        let fence = trimmed.chars().take_while(|c| *c == '`').count();
+
+        match &open {
+            None if fence >= 3 => {
+                open = Some(OpenChunk {
+                    fence,
+                    code_start: offset,
+                    lint: is_r_markdown_header(&trimmed[fence..]),
+                });
+            }
+            Some(chunk) if fence >= chunk.fence && fence == trimmed.len() => {
+                let code = &contents[chunk.code_start..line_start];
+                if chunk.lint && !is_disabled_by_quarto_options(code) {
+                    chunks.push(text_range(chunk.code_start, line_start));
+                }
+                open = None;
+            }
+            _ => {}
+        }
+    }
+
+    chunks
+}
+
+fn find_sweave_chunks(contents: &str) -> Vec<TextRange> {
+    let mut chunks = vec![];
+    let mut open: Option<OpenChunk> = None;
+    let mut offset = 0;
+
+    for line in contents.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_end();
+        let header = trimmed
+            .strip_prefix("<<")
+            .and_then(|header| header.strip_suffix(">>="));
+
+        if let Some(header) = header {
+            // A chunk without `@` ends at the start of the next one.
+            if let Some(chunk) = open.take()
+                && chunk.lint
+            {
+                chunks.push(text_range(chunk.code_start, line_start));
+            }
+            open = Some(OpenChunk {
+                fence: 0,
+                code_start: offset,
+                lint: is_evaluated(header),
+            });
+        } else if trimmed == "@" || trimmed.starts_with("@ ") {
+            if let Some(chunk) = open.take()
+                && chunk.lint
+            {
+                chunks.push(text_range(chunk.code_start, line_start));
+            }
+        }
+    }
+
+    if let Some(chunk) = open
+        && chunk.lint
+    {
+        chunks.push(text_range(chunk.code_start, contents.len()));
+    }
+
+    chunks
+}
+
+/// Is the header of a Markdown chunk, e.g. `{r label, eval = FALSE}`, the one
+/// of an R chunk that is evaluated? Code blocks such as ```` ```r ```` are not
+/// chunks and are never evaluated.
+fn is_r_markdown_header(header: &str) -> bool {
+    let Some(header) = header
+        .trim()
+        .strip_prefix('{')
+        .and_then(|header| header.strip_suffix('}'))
+    else {
+        return false;
+    };
+    let engine_end = header
+        .find(|c: char| c == ',' || c.is_whitespace())
+        .unwrap_or(header.len());
+    let (engine, options) = header.split_at(engine_end);
+    matches!(engine, "r" | "R") && is_evaluated(options)
+}
+
+/// Do the options of a chunk, e.g. `label, eval = FALSE`, allow evaluating it?
+fn is_evaluated(options: &str) -> bool {
+    options.split(',').all(|option| {
+        let Some((key, value)) = option.split_once('=') else {
+            return true;
+        };
+        let value = value.trim().trim_matches(['"', '\'']);
+        match key.trim() {
+            "eval" => !matches!(value, "FALSE" | "F"),
+            "engine" => matches!(value, "r" | "R"),
+            _ => true,
+        }
+    })
+}
+
+/// Quarto chunks can set their options in comments at the top of the chunk,
+/// e.g. `#| eval: false`.
+fn is_disabled_by_quarto_options(code: &str) -> bool {
+    code.lines()
+        .map(str::trim_start)
+        .take_while(|line| line.starts_with("#|"))
+        .filter_map(|line| line.trim_start_matches("#|").split_once(':'))
+        .any(|(key, value)| key.trim() == "eval" && value.trim() == "false")
+}
+
+fn text_range(start: usize, end: usize) -> TextRange {
+    TextRange::new(TextSize::from(start as u32), TextSize::from(end as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunks(contents: &str, kind: DocumentKind) -> Vec<&str> {
+        find_r_chunks(contents, kind)
+            .into_iter()
+            .map(|range| &contents[range])
+            .collect()
+    }
+
+    #[test]
+    fn test_document_kind() {
+        assert_eq!(
+            DocumentKind::from_path(Path::new("a.Rmd")),
+            Some(DocumentKind::Markdown)
+        );
+        assert_eq!(
+            DocumentKind::from_path(Path::new("a.qmd")),
+            Some(DocumentKind::Markdown)
+        );
+        assert_eq!(
+            DocumentKind::from_path(Path::new("a.Rnw")),
+            Some(DocumentKind::Sweave)
+        );
+        assert_eq!(DocumentKind::from_path(Path::new("a.R")), None);
+    }
+
+    #[test]
+    fn test_markdown_chunks() {
+        let contents = "\
+# Title
+
+```{r}
+x <- 1
+```
+
+```{r label, eval = FALSE}
+y <- 2
+```
+
+```{python}
+z = 3
+```
+
+```r
+not_a_chunk()
+```
+
+````{r}
+```
+w <- 4
+````
+";
+        assert_eq!(
+            chunks(contents, DocumentKind::Markdown),
+            vec!["x <- 1\n", "```\nw <- 4\n"]
+        );
+    }
+
+    #[test]
+    fn test_quarto_options() {
+        let contents = "\
+```{r}
+#| eval: false
+x <- 1
+```
+
+```{r}
+#| echo: false
+y <- 2
+```
+";
+        assert_eq!(
+            chunks(contents, DocumentKind::Markdown),
+            vec!["#| echo: false\ny <- 2\n"]
+        );
+    }
+
+    #[test]
+    fn test_sweave_chunks() {
+        let contents = "\
+\\section{Title}
+<<label>>=
+x <- 1
+@
+<<eval=FALSE>>=
+y <- 2
+@
+<<engine='python'>>=
+z = 3
+@
+<<>>=
+w <- 4
+";
+        assert_eq!(
+            chunks(contents, DocumentKind::Sweave),
+            vec!["x <- 1\n", "w <- 4\n"]
+        );
+    }
+
+    #[test]
+    fn test_mask_outside() {
+        let contents = "é\n```{r}\nx <- 1\n```\n";
+        let chunk = find_r_chunks(contents, DocumentKind::Markdown)[0];
+        let masked = mask_outside(contents, chunk);
+        assert_eq!(masked, "  \n      \nx <- 1\n   \n");
+        assert_eq!(masked.len(), contents.len());
+    }
+}
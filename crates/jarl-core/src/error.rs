@@ -1,20 +1,111 @@
-use std::fmt;
-use std::path::PathBuf;
+use biome_diagnostics::{Diagnostic, PrintDescription};
+use biome_rowan::{TextRange, TextSize};
 
-/// Custom error type for R parsing errors.
-#[derive(Debug)]
+/// Name of the diagnostics that report syntax errors. This is not a rule: it
+/// can't be selected, ignored or suppressed.
+pub const PARSE_ERROR: &str = "parse_error";
+
+/// A syntax error in R code.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseError {
-    pub filename: PathBuf,
+    pub range: TextRange,
+    pub message: String,
 }
 
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "Failed to parse {} due to syntax errors.",
-            self.filename.display()
-        )
-    }
+/// Locate the syntax errors reported by the R parser, i.e.
+/// `parsed.diagnostics()`, and describe them with the message of the parser,
+/// e.g. "Expected `)`.".
+///
+/// `contents` is the code that was parsed. Errors without a location are
+/// reported at the end of the file, which is where the parser stopped.
+pub fn find_parse_errors<D: Diagnostic>(diagnostics: &[D], contents: &str) -> Vec<ParseError> {
+    let end_of_file = TextRange::empty(TextSize::of(contents));
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let message = PrintDescription(diagnostic).to_string();
+            let message = if message.trim().is_empty() {
+                "Failed to parse this file due to a syntax error.".to_string()
+            } else {
+                message.trim().to_string()
+            };
+            ParseError {
+                range: diagnostic.location().span.unwrap_or(end_of_file),
+                message,
+            }
+        })
+        .collect()
 }
 
-impl std::error::Error for ParseError {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use air_r_parser::{RParserOptions, parse};
+
+    fn parse_errors(contents: &str) -> Vec<ParseError> {
+        let parsed = parse(contents, RParserOptions::default());
+        find_parse_errors(parsed.diagnostics(), contents)
+    }
+
+    /// A diagnostic without location, like some errors of the parser.
+    #[derive(Debug)]
+    struct Unlocated(&'static str);
+
+    impl Diagnostic for Unlocated {
+        fn description(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(fmt, "{}", self.0)
+        }
+    }
+
+    #[test]
+    fn test_unexpected_token() {
+        let contents = "x <- 1\ny <- )\n";
+        let parsed = parse(contents, RParserOptions::default());
+        let errors = find_parse_errors(parsed.diagnostics(), contents);
+        assert!(!errors.is_empty());
+        // The error is on the second line.
+        assert!(errors[0].range.start() >= TextSize::from(7));
+        // The message is the one of the parser.
+        assert_eq!(
+            errors[0].message,
+            PrintDescription(&parsed.diagnostics()[0])
+                .to_string()
+                .trim()
+        );
+        assert_ne!(
+            errors[0].message,
+            "Failed to parse this file due to a syntax error."
+        );
+    }
+
+    #[test]
+    fn test_missing_token() {
+        let errors = parse_errors("x <- 1\nf(x\n");
+        assert!(!errors.is_empty());
+        assert!(errors[0].range.start() >= TextSize::from(7));
+    }
+
+    #[test]
+    fn test_message_and_location() {
+        let errors = find_parse_errors(&[Unlocated("Expected `)`.")], "f(x\n");
+        assert_eq!(
+            errors,
+            vec![ParseError {
+                range: TextRange::empty(TextSize::from(4)),
+                message: "Expected `)`.".to_string(),
+            }]
+        );
+
+        // Without description, we still say what's wrong.
+        let errors = find_parse_errors(&[Unlocated("")], "f(x\n");
+        assert_eq!(
+            errors[0].message,
+            "Failed to parse this file due to a syntax error."
+        );
+    }
+
+    #[test]
+    fn test_valid_code() {
+        assert!(parse_errors("x <- 1\n").is_empty());
+    }
+}
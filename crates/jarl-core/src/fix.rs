@@ -1,65 +1,230 @@
 use crate::diagnostic::*;
+use crate::lints::RuleId;
+
+/// Maximum number of times that the code is re-linted and fixed, see
+/// [crate::check::fix_source()].
+pub const MAX_FIX_ITERATIONS: usize = 10;
+
+/// The outcome of [apply_fixes()].
+#[derive(Debug, PartialEq, Eq)]
+pub struct FixResult {
+    /// The code after applying the fixes.
+    pub content: String,
+    /// Indices (in the diagnostics passed to [apply_fixes()]) of the fixes that
+    /// were applied.
+    pub applied: Vec<usize>,
+    /// Indices of the fixes that were not applied because they conflict with
+    /// a fix of higher priority. They may be applied after re-linting the
+    /// fixed code.
+    pub deferred: Vec<usize>,
+}
 
 /// Takes all diagnostics found in a given file and the content of this file,
-/// and applies automatic fixes.
+/// and applies their fixes in a single pass.
 ///
-/// This returns a boolean indicating whether some fixes were skipped (more on
-/// this below), and a String with the modified content.
+/// ## Conflicting fixes
 ///
-/// ## Overlapping fixes
+/// Two fixes conflict when one of their edits conflict, e.g. when a fix
+/// rewrites a call that contains the range of another fix. Since the edits of
+/// a fix must be applied together, we can only keep one of the two.
 ///
-/// When several fixes have overlapping ranges, it becomes hard to apply all of
-/// them in a single pass.
-/// TODO: it should still be possible since we know the length of the fix so we
-/// should be able to update the range of the subsequent fixes. After all this
-/// is what I do to update the range of following non-overlapping fixes. The
-/// approach below looks quite expensive. This is also how Ruff does it though:
-/// https://github.com/astral-sh/ruff/blob/main/crates/ruff_linter/src/linter.rs#L559
+/// Fixes are considered in the order of their rule in the registry (see
+/// `declare_rules!`), and then by position in the code. A fix is kept if none
+/// of its edits conflict with the edits that were kept before, and is deferred
+/// otherwise. The caller can then re-lint the fixed code to get the new ranges
+/// of the deferred fixes, see [crate::check::fix_source()].
 ///
-/// Therefore, the current approach is to signal to the caller function that
-/// some fixes were skipped. This caller function then takes care of removing
-/// from the list of diagnostics those that have already been addressed, and
-/// then re-runs the diagnostic detection to get the new ranges. This loop
-/// continues until there are no more skipped fixes.
-pub fn apply_fixes(fixes: &[Diagnostic], contents: &str) -> (bool, String) {
-    let fixes = fixes
+/// Diagnostics without a fix are ignored, and so are those whose fix is unsafe
+/// unless `unsafe_fixes` is true.
+pub fn apply_fixes(diagnostics: &[Diagnostic], contents: &str, unsafe_fixes: bool) -> FixResult {
+    let mut candidates = diagnostics
         .iter()
-        .map(|diagnostic| &diagnostic.fix)
+        .enumerate()
+        .filter(|(_, diagnostic)| diagnostic.is_fix_applicable(unsafe_fixes))
+        .map(|(index, diagnostic)| {
+            let mut edits = diagnostic.fix.edits.iter().collect::<Vec<_>>();
+            edits.sort_by_key(|edit| (edit.start, edit.end));
+            let priority = RuleId::from_name(&diagnostic.message.name);
+            (index, priority, edits)
+        })
         .collect::<Vec<_>>();
 
-    let old_content = contents;
-    let mut new_content = old_content.to_string();
-    let mut last_modified_pos = 0;
-    let mut has_skipped_fixes = false;
-
-    let old_length = old_content.chars().count() as i32;
-    let mut new_length = old_length;
-
-    for fix in fixes {
-        let mut start: i32 = fix.start.try_into().unwrap();
-        let mut end: i32 = fix.end.try_into().unwrap();
-
-        // Adjust the range of the fix based on the changes in the contents due
-        // to previous fixes.
-        let diff_length = new_length - old_length;
-        start += diff_length;
-        end += diff_length;
-
-        // We don't know how to handle these cases in a single pass yet.
-        if start < last_modified_pos {
-            if !has_skipped_fixes {
-                has_skipped_fixes = true;
-            }
+    // Unknown rules come last (`None` is lower than `Some`, hence the check).
+    candidates.sort_by_key(|(index, priority, edits)| {
+        (priority.is_none(), *priority, edits[0].start, *index)
+    });
+
+    let mut applied = vec![];
+    let mut deferred = vec![];
+    let mut accepted_edits: Vec<&Edit> = vec![];
+
+    for (index, _, edits) in candidates {
+        // Edits of the same fix must not overlap each other, otherwise we
+        // wouldn't know how to apply them. This would be a bug in the rule.
+        let is_valid = edits.windows(2).all(|pair| pair[0].end <= pair[1].start)
+            && edits.iter().all(|edit| {
+                edit.start <= edit.end
+                    && edit.end <= contents.len()
+                    && contents.is_char_boundary(edit.start)
+                    && contents.is_char_boundary(edit.end)
+            });
+        if !is_valid {
+            tracing::warn!(
+                "Skipping invalid fix of rule `{}`",
+                diagnostics[index].message.name
+            );
             continue;
         }
 
-        let start_usize = start as usize;
-        let end_usize = end as usize;
+        let has_conflict = edits.iter().any(|edit| {
+            accepted_edits
+                .iter()
+                .any(|other| edit.conflicts_with(other))
+        });
+
+        if has_conflict {
+            deferred.push(index);
+        } else {
+            accepted_edits.extend(edits);
+            applied.push(index);
+        }
+    }
+
+    // The accepted edits don't overlap so we can apply them from start to end,
+    // copying the unchanged code between them.
+    accepted_edits.sort_by_key(|edit| (edit.start, edit.end));
+
+    let mut content = String::with_capacity(contents.len());
+    let mut last_pos = 0;
+    for edit in accepted_edits {
+        content.push_str(&contents[last_pos..edit.start]);
+        content.push_str(&edit.content);
+        last_pos = edit.end;
+    }
+    content.push_str(&contents[last_pos..]);
+
+    applied.sort_unstable();
+    deferred.sort_unstable();
+
+    FixResult { content, applied, deferred }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use biome_rowan::{TextRange, TextSize};
 
-        new_content.replace_range(start_usize..end_usize, &fix.content);
-        new_length = new_content.chars().count() as i32;
-        last_modified_pos = end + diff_length;
+    fn diagnostic(rule: &str, edits: Vec<(usize, usize, &str)>) -> Diagnostic {
+        let edits = edits
+            .into_iter()
+            .map(|(start, end, content)| Edit { content: content.to_string(), start, end })
+            .collect::<Vec<_>>();
+        let start = edits.first().map(|edit| edit.start).unwrap_or_default();
+        Diagnostic::new(
+            ViolationData::new(rule.to_string(), String::new(), None),
+            TextRange::new(TextSize::from(start as u32), TextSize::from(start as u32)),
+            Fix {
+                edits,
+                applicability: Applicability::Safe,
+                to_skip: false,
+            },
+        )
     }
 
-    (has_skipped_fixes, new_content.to_string())
+    #[test]
+    fn test_apply_non_overlapping_fixes() {
+        let contents = "any(is.na(x))\nany(is.na(y))\n";
+        let diagnostics = vec![
+            diagnostic("any_is_na", vec![(0, 13, "anyNA(x)")]),
+            diagnostic("any_is_na", vec![(14, 27, "anyNA(y)")]),
+        ];
+        let result = apply_fixes(&diagnostics, contents, false);
+        assert_eq!(result.content, "anyNA(x)\nanyNA(y)\n");
+        assert_eq!(result.applied, vec![0, 1]);
+        assert!(result.deferred.is_empty());
+    }
+
+    #[test]
+    fn test_apply_multi_edit_fix() {
+        let contents = "x <- foo(y)\n";
+        let diagnostics = vec![diagnostic(
+            "any_is_na",
+            vec![(5, 8, "bar"), (0, 0, "library(pkg)\n")],
+        )];
+        let result = apply_fixes(&diagnostics, contents, false);
+        assert_eq!(result.content, "library(pkg)\nx <- bar(y)\n");
+        assert_eq!(result.applied, vec![0]);
+    }
+
+    #[test]
+    fn test_conflicting_fixes_are_deferred_by_priority() {
+        // `any_is_na` is declared before `equals_na` in the registry, so it
+        // wins regardless of the order of the diagnostics.
+        let contents = "any(is.na(x == NA))\n";
+        let diagnostics = vec![
+            diagnostic("equals_na", vec![(10, 17, "is.na(x)")]),
+            diagnostic("any_is_na", vec![(0, 19, "anyNA(x == NA)")]),
+        ];
+        let result = apply_fixes(&diagnostics, contents, false);
+        assert_eq!(result.content, "anyNA(x == NA)\n");
+        assert_eq!(result.applied, vec![1]);
+        assert_eq!(result.deferred, vec![0]);
+    }
+
+    #[test]
+    fn test_multi_edit_fix_is_applied_entirely_or_not_at_all() {
+        let contents = "a; b; c\n";
+        let diagnostics = vec![
+            diagnostic("any_is_na", vec![(3, 4, "B")]),
+            diagnostic("equals_na", vec![(0, 1, "A"), (3, 4, "BB")]),
+        ];
+        let result = apply_fixes(&diagnostics, contents, false);
+        // The second edit of `equals_na` conflicts, so its first edit is not
+        // applied either.
+        assert_eq!(result.content, "a; B; c\n");
+        assert_eq!(result.applied, vec![0]);
+        assert_eq!(result.deferred, vec![1]);
+    }
+
+    #[test]
+    fn test_empty_and_skipped_fixes_are_ignored() {
+        let contents = "x\n";
+        let mut skipped = diagnostic("any_is_na", vec![(0, 1, "y")]);
+        skipped.fix.to_skip = true;
+        let diagnostics = vec![diagnostic("for_loop_index", vec![]), skipped];
+        let result = apply_fixes(&diagnostics, contents, false);
+        assert_eq!(result.content, "x\n");
+        assert!(result.applied.is_empty());
+        assert!(result.deferred.is_empty());
+    }
+
+    #[test]
+    fn test_offsets_are_bytes() {
+        let contents = "\"é\"; any(is.na(x))\n";
+        let diagnostics = vec![diagnostic("any_is_na", vec![(6, 19, "anyNA(x)")])];
+        let result = apply_fixes(&diagnostics, contents, false);
+        assert_eq!(result.content, "\"é\"; anyNA(x)\n");
+    }
+
+    #[test]
+    fn test_unsafe_fixes_need_opt_in() {
+        let contents = "x; y; z\n";
+        let mut unsafe_fix = diagnostic("all_equal", vec![(0, 1, "a")]);
+        unsafe_fix.fix.applicability = Applicability::Unsafe;
+        let mut display_only = diagnostic("any_is_na", vec![(3, 4, "b")]);
+        display_only.fix.applicability = Applicability::DisplayOnly;
+        let diagnostics = vec![
+            unsafe_fix,
+            display_only,
+            diagnostic("any_is_na", vec![(6, 7, "c")]),
+        ];
+
+        let result = apply_fixes(&diagnostics, contents, false);
+        assert_eq!(result.content, "x; y; c\n");
+        assert_eq!(result.applied, vec![2]);
+
+        let result = apply_fixes(&diagnostics, contents, true);
+        assert_eq!(result.content, "a; y; c\n");
+        assert_eq!(result.applied, vec![0, 2]);
+    }
 }
@@ -19,6 +19,18 @@ pub fn is_r_extension(extension: &str) -> bool {
     matches!(extension, "r" | "R")
 }
 
+/// Extensions of the documents whose R chunks are checked by default: R
+/// Markdown, Quarto and Sweave.
+pub const DEFAULT_DOCUMENT_EXTENSIONS: &[&str] = &["Rmd", "qmd", "Rnw"];
+
+/// Is `extension` one of `extensions`? The case is ignored, e.g. `.rmd` files
+/// are R Markdown documents too.
+pub fn is_document_extension<S: AsRef<str>>(extension: &str, extensions: &[S]) -> bool {
+    extensions
+        .iter()
+        .any(|document_extension| document_extension.as_ref().eq_ignore_ascii_case(extension))
+}
+
 /// Convert any path to an absolute path (based on the current working
 /// directory).
 pub fn normalize_path<P: AsRef<Path>>(path: P) -> PathBuf {
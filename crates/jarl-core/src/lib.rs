@@ -7,23 +7,32 @@
 //! - File discovery and processing
 
 pub mod analyze;
+pub mod baseline;
+pub mod cache;
 pub mod check;
 pub mod config;
 pub mod description;
 pub mod diagnostic;
 pub mod directive;
 pub mod discovery;
+pub mod document;
 pub mod error;
 pub mod fix;
 pub mod fs;
+pub mod lintr;
 pub mod lints;
 pub mod location;
+pub mod nolint;
+pub mod roxygen;
+pub mod rule_options;
 pub mod rule_table;
+pub mod semantic;
 pub mod settings;
 pub mod suppression;
 pub mod toml;
 pub mod utils;
 pub mod vcs;
+pub mod visitor;
 
 #[cfg(test)]
 pub mod utils_test;
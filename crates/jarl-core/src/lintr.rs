@@ -0,0 +1,578 @@
+//! Conversion of a `.lintr` file to a `jarl.toml`
+//!
+//! This is used by `jarl migrate lintr`. `.lintr` is a DCF file whose fields
+//! contain R code, e.g.:
+//!
+//! ```text
+//! linters: linters_with_defaults(
+//!     any_is_na_linter(),
+//!     object_name_linter = NULL
+//!   )
+//! exclusions: list("R/generated.R", "tests" = list(T_and_F_symbol_linter = Inf))
+//! encoding: "UTF-8"
+//! ```
+//!
+//! Most linters of lintr are about style, which Jarl leaves to a formatter
+//! like Air, so many of them don't have an equivalent rule.
+
+use air_r_parser::RParserOptions;
+use air_r_syntax::{AnyRExpression, RCall};
+use biome_rowan::AstNode;
+use std::collections::BTreeMap;
+
+use crate::description::{parse_dcf, unquote};
+use crate::lints::RuleId;
+
+/// Linters that are enabled by `linters_with_defaults()` in lintr.
+const LINTR_DEFAULT_LINTERS: &[&str] = &[
+    "assignment",
+    "brace",
+    "commas",
+    "commented_code",
+    "equals_na",
+    "function_left_parentheses",
+    "indentation",
+    "infix_spaces",
+    "line_length",
+    "object_length",
+    "object_name",
+    "object_usage",
+    "paren_body",
+    "pipe_continuation",
+    "quotes",
+    "return",
+    "semicolon",
+    "seq",
+    "spaces_inside",
+    "spaces_left_parentheses",
+    "T_and_F_symbol",
+    "trailing_blank_lines",
+    "trailing_whitespace",
+    "vector_logic",
+    "whitespace",
+];
+
+/// Linters of lintr whose equivalent rule has another name in Jarl. Other
+/// linters have the same name as their rule, without `_linter`.
+const LINTR_RENAMED_LINTERS: &[(&str, &str)] = &[
+    ("T_and_F_symbol", "true_false_symbol"),
+    ("duplicate_argument", "duplicated_arguments"),
+];
+
+/// Fields of `.lintr` that configure `# nolint` comments, and their default
+/// value in lintr. Jarl only recognizes the default markers: custom ones are
+/// reported but not migrated.
+const LINTR_EXCLUDE_FIELDS: &[(&str, &str)] = &[
+    ("exclude", "# nolint"),
+    ("exclude_start", "# nolint start"),
+    ("exclude_end", "# nolint end"),
+    ("exclude_next", "# nolint next"),
+];
+
+/// The result of the conversion of a `.lintr` file.
+#[derive(Debug, Default, PartialEq)]
+pub struct LintrMigration {
+    /// The content of the `jarl.toml`.
+    pub toml: String,
+    /// Linters that are enabled in `.lintr` but that don't have an equivalent
+    /// rule in Jarl.
+    pub unsupported_linters: Vec<String>,
+    /// Settings of `.lintr` that couldn't be converted.
+    pub warnings: Vec<String>,
+}
+
+/// The linters that are used by lintr, from the `linters` field.
+enum LintrLinters {
+    /// `linters_with_defaults()`, `list()`: the listed linters are added to
+    /// `base`, the linters set to `NULL` are removed.
+    Listed {
+        base: &'static [&'static str],
+        enabled: Vec<String>,
+        disabled: Vec<String>,
+    },
+    /// `all_linters()`: all linters except the ones set to `NULL`.
+    All { disabled: Vec<String> },
+}
+
+/// Convert the content of a `.lintr` file to the content of a `jarl.toml`.
+pub fn migrate_lintr(contents: &str) -> anyhow::Result<LintrMigration> {
+    let fields = parse_dcf(contents);
+    let mut migration = LintrMigration::default();
+
+    let mut select: Option<Vec<String>> = None;
+    let mut ignore: Vec<String> = Vec::new();
+    let mut exclude: Vec<String> = Vec::new();
+    let mut per_file_ignores: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    let mut names = fields.keys().collect::<Vec<_>>();
+    names.sort();
+
+    for name in names {
+        let value = fields[name].as_str();
+        match name.as_str() {
+            "linters" => {
+                let linters = parse_linters(value, &mut migration.warnings)?;
+                match linters {
+                    Some(LintrLinters::Listed { base, enabled, disabled }) => {
+                        let mut used = base
+                            .iter()
+                            .map(|linter| linter.to_string())
+                            .collect::<Vec<_>>();
+                        used.extend(enabled);
+                        used.retain(|linter| !disabled.contains(linter));
+
+                        let mut rules = Vec::new();
+                        for linter in used {
+                            match jarl_rule(&linter) {
+                                Some(rule) => rules.push(rule),
+                                None => {
+                                    let linter = format!("{linter}_linter");
+                                    if !migration.unsupported_linters.contains(&linter) {
+                                        migration.unsupported_linters.push(linter);
+                                    }
+                                }
+                            }
+                        }
+                        rules.sort();
+                        rules.dedup();
+
+                        if rules.is_empty() {
+                            migration.warnings.push(
+                                "None of the linters of `.lintr` have an equivalent rule in Jarl, so all rules are used.".to_string(),
+                            );
+                        } else {
+                            select =
+                                Some(rules.iter().map(|rule| rule.name().to_string()).collect());
+                        }
+                    }
+                    Some(LintrLinters::All { disabled }) => {
+                        // We don't know all the linters of lintr, so we can't
+                        // list the ones that have no equivalent rule.
+                        migration.warnings.push(
+                            "`all_linters()` in `.lintr` also enables the linters of lintr that have no equivalent rule in Jarl, and they are not migrated.".to_string(),
+                        );
+                        let mut rules = disabled
+                            .iter()
+                            .filter_map(|linter| jarl_rule(linter))
+                            .collect::<Vec<_>>();
+                        rules.sort();
+                        rules.dedup();
+                        ignore = rules.iter().map(|rule| rule.name().to_string()).collect();
+                    }
+                    None => {}
+                }
+            }
+            "exclusions" => {
+                parse_exclusions(
+                    value,
+                    &mut exclude,
+                    &mut per_file_ignores,
+                    &mut migration.warnings,
+                )?;
+            }
+            "encoding" => {
+                let encoding = unquote(value);
+                if !matches!(encoding.to_lowercase().as_str(), "utf-8" | "utf8") {
+                    migration.warnings.push(format!(
+                        "Jarl only reads UTF-8 files, so `encoding: {encoding}` is not migrated."
+                    ));
+                }
+            }
+            name => {
+                if let Some((_, default)) = LINTR_EXCLUDE_FIELDS
+                    .iter()
+                    .find(|(field, _)| *field == name)
+                {
+                    if unquote(value) != *default {
+                        migration.warnings.push(format!(
+                            "Jarl only recognizes the default `{name}` of lintr, `{default}`, so `{name}: {value}` is not migrated and the comments that use it must be replaced by `{default}`."
+                        ));
+                    }
+                } else {
+                    migration
+                        .warnings
+                        .push(format!("The field `{name}` of `.lintr` is not migrated."));
+                }
+            }
+        }
+    }
+
+    let mut toml = String::from("[lint]\n");
+    if let Some(select) = select {
+        toml.push_str(&format!("select = {}\n", toml_list(&select)));
+    }
+    if !ignore.is_empty() {
+        toml.push_str(&format!("ignore = {}\n", toml_list(&ignore)));
+    }
+    if !exclude.is_empty() {
+        toml.push_str(&format!("exclude = {}\n", toml_list(&exclude)));
+    }
+    if !per_file_ignores.is_empty() {
+        toml.push_str("\n[lint.per-file-ignores]\n");
+        for (pattern, rules) in &per_file_ignores {
+            toml.push_str(&format!(
+                "{} = {}\n",
+                toml_string(pattern),
+                toml_list(rules)
+            ));
+        }
+    }
+    migration.toml = toml;
+
+    Ok(migration)
+}
+
+/// The rule of Jarl that is equivalent to a linter of lintr, e.g.
+/// `any_is_na_linter` or `lintr::any_is_na_linter`.
+fn jarl_rule(linter: &str) -> Option<RuleId> {
+    let linter = linter_name(linter);
+    let name = LINTR_RENAMED_LINTERS
+        .iter()
+        .find(|(lintr_name, _)| *lintr_name == linter)
+        .map_or(linter, |(_, jarl_name)| jarl_name);
+    RuleId::from_name(name)
+}
+
+/// The name of a linter without the `lintr::` prefix and the `_linter`
+/// suffix.
+fn linter_name(linter: &str) -> &str {
+    let linter = unquote_r(linter);
+    let linter = linter.strip_prefix("lintr::").unwrap_or(linter);
+    linter.strip_suffix("_linter").unwrap_or(linter)
+}
+
+/// Remove the quotes or backticks around an R string or symbol.
+fn unquote_r(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\'', '`'] {
+        if let Some(value) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return value;
+        }
+    }
+    value
+}
+
+/// Parse the R code of a field of `.lintr`, which must be a single call.
+fn parse_call(field: &str, value: &str) -> anyhow::Result<RCall> {
+    let parsed = air_r_parser::parse(value, RParserOptions::default());
+    let invalid = || anyhow::anyhow!("Invalid value for `{field}` in `.lintr`: `{value}`.");
+    if parsed.has_error() {
+        return Err(invalid());
+    }
+    let mut expressions = parsed.tree().expressions().into_iter();
+    match (expressions.next(), expressions.next()) {
+        (Some(AnyRExpression::RCall(call)), None) => Ok(call),
+        _ => Err(invalid()),
+    }
+}
+
+/// The name of the function of a call, without the `lintr::` prefix.
+fn function_name(call: &RCall) -> anyhow::Result<String> {
+    let name = call.function()?.to_trimmed_text().to_string();
+    Ok(name.strip_prefix("lintr::").unwrap_or(&name).to_string())
+}
+
+/// The arguments of a call, as pairs of optional name and value.
+fn arguments(call: &RCall) -> anyhow::Result<Vec<(Option<String>, Option<AnyRExpression>)>> {
+    Ok(call
+        .arguments()?
+        .items()
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(|argument| {
+            let fields = argument.as_fields();
+            let name = fields
+                .name_clause
+                .and_then(|name_clause| name_clause.name().ok())
+                .map(|name| unquote_r(&name.into_syntax().text_trimmed().to_string()).to_string());
+            (name, fields.value)
+        })
+        .collect())
+}
+
+/// Parse the `linters` field, e.g.
+/// `linters_with_defaults(line_length_linter(120), object_name_linter = NULL)`.
+///
+/// Returns `None` if the linters can't be converted.
+fn parse_linters(value: &str, warnings: &mut Vec<String>) -> anyhow::Result<Option<LintrLinters>> {
+    let call = parse_call("linters", value)?;
+    let function = function_name(&call)?;
+
+    let mut base = match function.as_str() {
+        "linters_with_defaults" => Some(LINTR_DEFAULT_LINTERS),
+        "list" => Some(&[][..]),
+        "all_linters" => None,
+        _ => {
+            warnings.push(format!(
+                "`{function}()` in the field `linters` of `.lintr` is not supported, so all rules are used."
+            ));
+            return Ok(None);
+        }
+    };
+
+    let mut enabled = Vec::new();
+    let mut disabled = Vec::new();
+    for (name, value) in arguments(&call)? {
+        let Some(value) = value else {
+            continue;
+        };
+        let text = value.to_trimmed_text().to_string();
+
+        match name.as_deref() {
+            // `linters_with_defaults(defaults = list(), ...)`
+            Some("defaults") => {
+                base = match text.strip_prefix("lintr::").unwrap_or(&text) {
+                    "list()" => Some(&[][..]),
+                    "default_linters" => Some(LINTR_DEFAULT_LINTERS),
+                    "all_linters()" => None,
+                    _ => {
+                        warnings.push(format!(
+                            "`defaults = {text}` in the field `linters` of `.lintr` is not supported, so all rules are used."
+                        ));
+                        return Ok(None);
+                    }
+                };
+                continue;
+            }
+            // `all_linters(packages = ...)`
+            Some("packages") => continue,
+            _ => {}
+        }
+
+        if text == "NULL" {
+            if let Some(name) = name {
+                disabled.push(linter_name(&name).to_string());
+            }
+            continue;
+        }
+
+        let linter = match &value {
+            AnyRExpression::RCall(linter_call) => {
+                let linter = function_name(linter_call)?;
+                if !arguments(linter_call)?.is_empty() {
+                    warnings.push(format!(
+                        "The arguments of `{text}` in `.lintr` are not migrated."
+                    ));
+                }
+                name.unwrap_or(linter)
+            }
+            _ => name.unwrap_or(text),
+        };
+        enabled.push(linter_name(&linter).to_string());
+    }
+
+    Ok(Some(match base {
+        Some(base) => LintrLinters::Listed { base, enabled, disabled },
+        None => LintrLinters::All { disabled },
+    }))
+}
+
+/// Parse the `exclusions` field, e.g.
+/// `list("R/generated.R", "tests" = list(T_and_F_symbol_linter = Inf))`.
+///
+/// Files and directories that are entirely excluded are added to `exclude`,
+/// and linters that are excluded from files to `per_file_ignores`. Exclusions
+/// of some lines can't be converted: `# nolint` comments should be used
+/// instead.
+fn parse_exclusions(
+    value: &str,
+    exclude: &mut Vec<String>,
+    per_file_ignores: &mut BTreeMap<String, Vec<String>>,
+    warnings: &mut Vec<String>,
+) -> anyhow::Result<()> {
+    let call = parse_call("exclusions", value)?;
+    if function_name(&call)? != "list" {
+        return Err(anyhow::anyhow!(
+            "Invalid value for `exclusions` in `.lintr`: expected a `list()`, got `{value}`."
+        ));
+    }
+
+    for (name, value) in arguments(&call)? {
+        let Some(value) = value else {
+            continue;
+        };
+        let text = value.to_trimmed_text().to_string();
+
+        // Unnamed paths are excluded entirely
+        let Some(path) = name else {
+            exclude.push(exclude_pattern(unquote_r(&text)));
+            continue;
+        };
+        let pattern = exclude_pattern(&path);
+
+        match &value {
+            _ if text == "Inf" => exclude.push(pattern),
+            AnyRExpression::RCall(linters_call) if function_name(linters_call)? == "list" => {
+                for (linter, lines) in arguments(linters_call)? {
+                    let Some(lines) = lines else {
+                        continue;
+                    };
+                    let lines = lines.to_trimmed_text().to_string();
+                    // Unnamed linters are excluded from all lines
+                    let (linter, lines) = match linter {
+                        Some(linter) => (linter, lines),
+                        None => (unquote_r(&lines).to_string(), "Inf".to_string()),
+                    };
+
+                    if lines != "Inf" {
+                        warnings.push(format!(
+                            "Exclusions of some lines of `{path}` for `{linter}` are not migrated. Use `# nolint` comments instead."
+                        ));
+                        continue;
+                    }
+                    match jarl_rule(&linter) {
+                        Some(rule) => {
+                            let rules = per_file_ignores.entry(pattern.clone()).or_default();
+                            if !rules.iter().any(|name| name == rule.name()) {
+                                rules.push(rule.name().to_string());
+                            }
+                        }
+                        None => warnings.push(format!(
+                            "`{linter}` doesn't have an equivalent rule in Jarl, so its exclusion from `{path}` is not migrated."
+                        )),
+                    }
+                }
+            }
+            _ => warnings.push(format!(
+                "Exclusions of some lines of `{path}` are not migrated. Use `# nolint` comments instead."
+            )),
+        }
+    }
+
+    Ok(())
+}
+
+/// Convert a path of lintr, relative to the directory of `.lintr`, to a
+/// pattern of `exclude` or `per-file-ignores`.
+///
+/// Patterns without a `/` (except at the end) match files anywhere, so they
+/// are anchored to the directory of `jarl.toml` with a leading `/`.
+fn exclude_pattern(path: &str) -> String {
+    let path = path.strip_prefix("./").unwrap_or(path);
+    if path.trim_end_matches('/').contains('/') {
+        path.to_string()
+    } else {
+        format!("/{path}")
+    }
+}
+
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+fn toml_list(values: &[String]) -> String {
+    let values = values
+        .iter()
+        .map(|value| toml_string(value))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("[{values}]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_linters_with_defaults() {
+        let lintr = r#"linters: linters_with_defaults(
+    line_length_linter(120),
+    any_is_na_linter(),
+    lintr::any_duplicated_linter,
+    object_name_linter = NULL,
+    assignment_linter = NULL
+  )
+encoding: "UTF-8"
+"#;
+        let migration = migrate_lintr(lintr).unwrap();
+        assert_eq!(
+            migration.toml,
+            "[lint]\nselect = [\"any_duplicated\", \"any_is_na\", \"equals_na\", \"true_false_symbol\"]\n"
+        );
+        assert!(
+            migration
+                .unsupported_linters
+                .contains(&"line_length_linter".to_string())
+        );
+        assert!(
+            !migration
+                .unsupported_linters
+                .contains(&"object_name_linter".to_string())
+        );
+        assert_eq!(
+            migration.warnings,
+            vec!["The arguments of `line_length_linter(120)` in `.lintr` are not migrated."]
+        );
+    }
+
+    #[test]
+    fn test_migrate_list_and_all_linters() {
+        let migration = migrate_lintr("linters: list(any_is_na_linter(), seq_linter())\n").unwrap();
+        assert_eq!(migration.toml, "[lint]\nselect = [\"any_is_na\"]\n");
+        assert_eq!(migration.unsupported_linters, vec!["seq_linter"]);
+
+        let migration = migrate_lintr(
+            "linters: all_linters(T_and_F_symbol_linter = NULL, seq_linter = NULL)\n",
+        )
+        .unwrap();
+        assert_eq!(migration.toml, "[lint]\nignore = [\"true_false_symbol\"]\n");
+        assert!(migration.unsupported_linters.is_empty());
+        assert_eq!(
+            migration.warnings,
+            vec![
+                "`all_linters()` in `.lintr` also enables the linters of lintr that have no equivalent rule in Jarl, and they are not migrated."
+            ]
+        );
+
+        let migration = migrate_lintr("linters: list(line_length_linter())\n").unwrap();
+        assert_eq!(migration.toml, "[lint]\n");
+        assert_eq!(migration.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_migrate_exclusions() {
+        let lintr = r#"exclusions: list(
+    "R/generated.R",
+    "data-raw/" = Inf,
+    "tests/testthat" = list(T_and_F_symbol_linter = Inf, "any_is_na_linter"),
+    "R/foo.R" = list(any_is_na_linter = 1:10),
+    "R/bar.R" = 3
+  )
+"#;
+        let migration = migrate_lintr(lintr).unwrap();
+        assert_eq!(
+            migration.toml,
+            r#"[lint]
+exclude = ["R/generated.R", "/data-raw/"]
+
+[lint.per-file-ignores]
+"tests/testthat" = ["true_false_symbol", "any_is_na"]
+"#
+        );
+        assert_eq!(migration.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_migrate_other_fields() {
+        let lintr = "encoding: \"latin1\"\nexclude_start: \"# nolint start\"\nexclude_end: \"# end exclude\"\ncache_directory: \"foo\"\n";
+        let migration = migrate_lintr(lintr).unwrap();
+        assert_eq!(migration.toml, "[lint]\n");
+        assert_eq!(
+            migration.warnings,
+            vec![
+                "The field `cache_directory` of `.lintr` is not migrated.",
+                "Jarl only reads UTF-8 files, so `encoding: latin1` is not migrated.",
+                "Jarl only recognizes the default `exclude_end` of lintr, `# nolint end`, so `exclude_end: \"# end exclude\"` is not migrated and the comments that use it must be replaced by `# nolint end`.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_migrate_invalid_lintr() {
+        let err = migrate_lintr("linters: linters_with_defaults(\n").unwrap_err();
+        assert!(err.to_string().starts_with("Invalid value for `linters`"));
+    }
+}
@@ -62,9 +62,12 @@ pub fn all_equal(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
             ),
             range,
             Fix {
-                content: format!("!isTRUE(all.equal({inner_content}))"),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("!isTRUE(all.equal({inner_content}))"),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Unsafe,
                 to_skip: node_contains_comments(ast.syntax()),
             },
         );
@@ -115,12 +118,7 @@ pub fn all_equal(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
                 Some("Wrap `all.equal()` in `isTRUE()`, or replace it by `identical()` if no tolerance is required.".to_string()),
             ),
             range,
-            Fix {
-                content: fix_content,
-                start: range.start().into(),
-                end: range.end().into(),
-                to_skip: node_contains_comments(ast.syntax()),
-            },
+            Fix { edits: vec![Edit { content: fix_content, start: range.start().into(), end: range.end().into() }], to_skip: node_contains_comments(ast.syntax()) },
         );
 
         return Ok(Some(diagnostic));
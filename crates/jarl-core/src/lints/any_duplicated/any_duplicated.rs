@@ -57,9 +57,12 @@ pub fn any_duplicated(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
             AnyDuplicated,
             range,
             Fix {
-                content: format!("anyDuplicated({inner_content}) > 0"),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("anyDuplicated({inner_content}) > 0"),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Safe,
                 to_skip: node_contains_comments(ast.syntax()),
             },
         );
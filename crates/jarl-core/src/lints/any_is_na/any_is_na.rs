@@ -54,9 +54,12 @@ pub fn any_is_na(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
             AnyIsNa,
             range,
             Fix {
-                content: format!("anyNA({inner_content})"),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("anyNA({inner_content})"),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Safe,
                 to_skip: node_contains_comments(ast.syntax()),
             },
         );
@@ -31,6 +31,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lint_any_na_in_all_expressions() {
+        let expected_message = "`any(is.na(...))` is inefficient";
+        expect_lint(
+            "function(x = any(is.na(y))) x",
+            expected_message,
+            "any_is_na",
+            None,
+        );
+        expect_lint(
+            "\\(x = any(is.na(y))) x",
+            expected_message,
+            "any_is_na",
+            None,
+        );
+        expect_lint("x[[any(is.na(y))]]", expected_message, "any_is_na", None);
+        expect_lint("any(is.na(y))$foo", expected_message, "any_is_na", None);
+        expect_lint("any(is.na(y))[1]", expected_message, "any_is_na", None);
+        expect_lint("f(any(is.na(y)))(x)", expected_message, "any_is_na", None);
+    }
+
     #[test]
     fn test_no_lint_any_na() {
         expect_no_lint("any(x)", "any_is_na", None);
@@ -105,9 +105,12 @@ pub fn assignment(
         ViolationData::new("assignment".to_string(), msg.to_string(), None),
         range_to_report,
         Fix {
-            content: replacement,
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: replacement,
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability: Applicability::Safe,
             to_skip: false,
         },
     );
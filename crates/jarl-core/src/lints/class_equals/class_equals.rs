@@ -179,9 +179,12 @@ pub fn class_equals(ast: &RBinaryExpression) -> anyhow::Result<Option<Diagnostic
         ClassEquals,
         range,
         Fix {
-            content: format!("{}({}, {})", fun_name, fun_content.unwrap(), class_name),
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: format!("{}({}, {})", fun_name, fun_content.unwrap(), class_name),
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability: Applicability::Safe,
             to_skip: node_contains_comments(ast.syntax()),
         },
     );
@@ -36,6 +36,19 @@ use biome_rowan::{AstNode, AstNodeList};
 /// ```
 /// wouldn't.
 ///
+/// The fix is unsafe when the branches span several lines, e.g.
+/// ```r,ignore
+/// if (is.null(x)) {
+///   foo(
+///     a
+///   )
+/// } else {
+///   x
+/// }
+/// ```
+/// since the expression is moved on the same line as `x %||%`, which is worth
+/// reviewing.
+///
 /// ## Example
 ///
 /// ```r
@@ -176,6 +189,12 @@ pub fn coalesce(ast: &RIfStatement) -> anyhow::Result<Option<Diagnostic>> {
         return Ok(None);
     }
 
+    let applicability = if fix_content.contains('\n') {
+        Applicability::Unsafe
+    } else {
+        Applicability::Safe
+    };
+
     let range = ast.syntax().text_trimmed_range();
     let diagnostic = Diagnostic::new(
         ViolationData::new(
@@ -185,9 +204,12 @@ pub fn coalesce(ast: &RIfStatement) -> anyhow::Result<Option<Diagnostic>> {
         ),
         range,
         Fix {
-            content: fix_content.clone(),
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: fix_content.clone(),
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability,
             to_skip: node_contains_comments(ast.syntax()) || skip_fix,
         },
     );
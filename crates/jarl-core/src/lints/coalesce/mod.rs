@@ -154,4 +154,14 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_coalesce_multiline_branch_fix_is_unsafe() {
+        let code = "if (is.null(x)) {\n  foo(\n    a\n  )\n} else x";
+        assert_eq!(apply_fixes(code, "coalesce", false, Some("4.5")), code);
+        assert_eq!(
+            apply_fixes(code, "coalesce", true, Some("4.5")),
+            "x %||% foo(\n    a\n  )"
+        );
+    }
 }
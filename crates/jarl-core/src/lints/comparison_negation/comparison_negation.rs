@@ -82,14 +82,17 @@ pub fn comparison_negation(ast: &RUnaryExpression) -> anyhow::Result<Option<Diag
         ),
         range,
         Fix {
-            content: format!(
-                "{} {} {}",
-                left.to_trimmed_text(),
-                replacement_operator,
-                right.to_trimmed_text()
-            ),
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: format!(
+                    "{} {} {}",
+                    left.to_trimmed_text(),
+                    replacement_operator,
+                    right.to_trimmed_text()
+                ),
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability: Applicability::Safe,
             to_skip: node_contains_comments(ast.syntax()),
         },
     );
@@ -1,10 +1,47 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::diagnostic::*;
+use crate::rule_options::matches_function;
 use air_r_syntax::*;
 use anyhow::anyhow;
 use biome_rowan::AstNode;
 
+/// Functions in which duplicated arguments are allowed by default.
+///
+/// See https://github.com/etiennebacher/jarl/issues/172 for `cli_*`.
+const DEFAULT_ALLOWED_FUNCTIONS: &[&str] = &["c", "mutate", "summarize", "transmute", "cli_*"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DuplicatedArgumentsOptions {
+    /// # Functions in which duplicated arguments are allowed
+    ///
+    /// Names of functions whose calls are never reported, for instance
+    /// because they build the result one argument after the other. A name
+    /// ending with `*` matches all functions starting with this name, e.g.
+    /// `"cli_*"`. This replaces the default list, which is
+    /// `["c", "mutate", "summarize", "transmute", "cli_*"]`.
+    pub allowed_functions: Option<Vec<String>>,
+}
+
+impl DuplicatedArgumentsOptions {
+    pub(crate) const DEFAULT: Self = Self { allowed_functions: None };
+
+    pub(crate) fn extend(self, base: Self) -> Self {
+        Self {
+            allowed_functions: self.allowed_functions.or(base.allowed_functions),
+        }
+    }
+
+    fn is_allowed_function(&self, name: &str) -> bool {
+        match &self.allowed_functions {
+            Some(allowed_functions) => matches_function(allowed_functions, name),
+            None => matches_function(DEFAULT_ALLOWED_FUNCTIONS, name),
+        }
+    }
+}
+
 /// ## What it does
 ///
 /// Checks for duplicated arguments in function calls.
@@ -24,7 +61,20 @@ use biome_rowan::AstNode;
 /// ```r
 /// list(x = 1, x = 2)
 /// ```
-pub fn duplicated_arguments(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
+///
+/// ## Options
+///
+/// - `allowed-functions`: functions in which duplicated arguments are allowed.
+///   Defaults to `["c", "mutate", "summarize", "transmute", "cli_*"]`.
+///
+/// ```toml
+/// [lint.rules.duplicated_arguments]
+/// allowed-functions = ["c", "list2"]
+/// ```
+pub fn duplicated_arguments(
+    ast: &RCall,
+    options: &DuplicatedArgumentsOptions,
+) -> anyhow::Result<Option<Diagnostic>> {
     let RCallFields { function, arguments } = ast.as_fields();
 
     let fun_name = match function? {
@@ -56,10 +106,7 @@ pub fn duplicated_arguments(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
         }
     };
 
-    // https://github.com/etiennebacher/jarl/issues/172
-    let is_whitelisted_prefix = fun_name.starts_with("cli_");
-    let whitelisted_funs = ["c", "mutate", "summarize", "transmute"];
-    if whitelisted_funs.contains(&fun_name.as_str()) || is_whitelisted_prefix {
+    if options.is_allowed_function(&fun_name) {
         return Ok(None);
     }
 
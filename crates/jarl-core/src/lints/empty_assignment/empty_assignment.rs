@@ -4,6 +4,29 @@ use biome_rowan::AstNode;
 
 pub struct EmptyAssignment;
 
+/// ## What it does
+///
+/// Checks for assignments of an empty pair of braces, e.g. `x <- {}`.
+///
+/// ## Why is this bad?
+///
+/// `{}` evaluates to `NULL`, so `x <- {}` is the same as `x <- NULL`, but the
+/// latter is clearer. It is also often better to directly allocate an empty
+/// object of the right type and size, e.g. `x <- character(10)` if `x` is
+/// later filled with 10 strings.
+///
+/// ## Example
+///
+/// ```r
+/// x <- {}
+/// y = {}
+/// ```
+///
+/// Use instead:
+/// ```r
+/// x <- NULL
+/// y <- character(0)
+/// ```
 impl Violation for EmptyAssignment {
     fn name(&self) -> String {
         "empty_assignment".to_string()
@@ -84,9 +84,12 @@ pub fn equals_na(ast: &RBinaryExpression) -> anyhow::Result<Option<Diagnostic>>
             EqualsNa,
             range,
             Fix {
-                content: format!("is.na({replacement})"),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("is.na({replacement})"),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Safe,
                 to_skip: node_contains_comments(ast.syntax()),
             },
         ),
@@ -94,9 +97,12 @@ pub fn equals_na(ast: &RBinaryExpression) -> anyhow::Result<Option<Diagnostic>>
             EqualsNa,
             range,
             Fix {
-                content: format!("!is.na({replacement})"),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("!is.na({replacement})"),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Safe,
                 to_skip: node_contains_comments(ast.syntax()),
             },
         ),
@@ -1,4 +1,5 @@
 use crate::diagnostic::*;
+use crate::semantic::SemanticModel;
 use air_r_syntax::*;
 use biome_rowan::{AstNode, Text};
 
@@ -39,13 +40,18 @@ impl Violation for ForLoopIndex {
     }
 }
 
-pub fn for_loop_index(ast: &RForStatement) -> anyhow::Result<Option<Diagnostic>> {
+pub fn for_loop_index(
+    ast: &RForStatement,
+    semantic: &SemanticModel,
+) -> anyhow::Result<Option<Diagnostic>> {
     let RForStatementFields { variable, sequence, .. } = ast.as_fields();
 
     let variable_text = variable?.to_trimmed_text();
     let sequence = sequence?;
 
-    if contains_identifier(&sequence, &variable_text)? {
+    if contains_identifier(&sequence, &variable_text)?
+        || references_identifier(&sequence, &variable_text, semantic)
+    {
         let range_start = ast.variable()?.range().start();
         let range_end = ast.sequence()?.range().end();
         let range = TextRange::new(range_start, range_end);
@@ -56,6 +62,42 @@ pub fn for_loop_index(ast: &RForStatement) -> anyhow::Result<Option<Diagnostic>>
     }
 }
 
+// Checks whether the sequence uses the index symbol anywhere, e.g. `x + 1` or
+// `x[-1]`. References that are bound inside the sequence don't count, e.g.
+// `x` in `lapply(y, function(x) x)` refers to the parameter of the anonymous
+// function and not to the index.
+fn references_identifier(expr: &AnyRExpression, target: &str, semantic: &SemanticModel) -> bool {
+    let sequence_range = expr.syntax().text_trimmed_range();
+
+    // In `DT[, col]`, `col` usually refers to a column of `DT` (e.g. with
+    // data.table) and not to the index.
+    let subset_ranges: Vec<TextRange> = expr
+        .syntax()
+        .descendants()
+        .filter_map(RSubset::cast)
+        .filter_map(|subset| subset.arguments().ok())
+        .map(|arguments| arguments.syntax().text_trimmed_range())
+        .collect();
+
+    semantic
+        .references_in(sequence_range)
+        .filter(|reference| reference.name == target)
+        .filter(|reference| {
+            !subset_ranges
+                .iter()
+                .any(|range| range.contains_range(reference.range))
+        })
+        .any(|reference| match reference.binding {
+            Some(id) => {
+                let scope = semantic.scope(semantic.binding(id).scope);
+                !sequence_range.contains_range(scope.range)
+            }
+            None => true,
+        })
+}
+
+// Checks whether the index symbol is used as an argument name in the sequence,
+// e.g. `foo(x = 1)`.
 fn contains_identifier(expr: &AnyRExpression, target: &str) -> anyhow::Result<bool> {
     let out = match expr {
         AnyRExpression::RIdentifier(ident) => ident.to_trimmed_text() == target,
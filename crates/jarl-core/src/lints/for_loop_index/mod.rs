@@ -20,6 +20,12 @@ mod tests {
             "for_loop_index",
             None,
         );
+        expect_no_lint(
+            "for (x in lapply(y, function(x) x + 1)) {}",
+            "for_loop_index",
+            None,
+        );
+        expect_no_lint("for (x in sapply(y, \\(x) x)) {}", "for_loop_index", None);
     }
 
     #[test]
@@ -53,6 +59,25 @@ mod tests {
             None,
         );
 
+        expect_lint(
+            "for (x in x + 1) {}",
+            expected_message,
+            "for_loop_index",
+            None,
+        );
+        expect_lint(
+            "for (x in x[-1]) {}",
+            expected_message,
+            "for_loop_index",
+            None,
+        );
+        expect_lint(
+            "for (x in lapply(y, function(z) x + z)) {}",
+            expected_message,
+            "for_loop_index",
+            None,
+        );
+
         // No fixes
         assert_snapshot!(
             "fix_output",
@@ -60,6 +85,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_for_loop_index_uses_scopes() {
+        let expected_message = "Don't re-use any sequence symbols as the index";
+
+        // In each pair, the index is shadowed by a parameter of the anonymous
+        // function in the first case but not in the second one, where the
+        // function uses the index.
+        expect_no_lint(
+            "for (x in Map(function(x, y) x + y, a, b)) {}",
+            "for_loop_index",
+            None,
+        );
+        expect_lint(
+            "for (x in Map(function(a, y) x + y, a, b)) {}",
+            expected_message,
+            "for_loop_index",
+            None,
+        );
+        expect_no_lint(
+            "for (x in vapply(y, \\(x) x > 0, logical(1))) {}",
+            "for_loop_index",
+            None,
+        );
+        expect_lint(
+            "for (x in vapply(y, \\(z) x > z, logical(1))) {}",
+            expected_message,
+            "for_loop_index",
+            None,
+        );
+
+        // The parameter only shadows the index inside the function
+        expect_lint(
+            "for (x in lapply(y, function(x) x) + x) {}",
+            expected_message,
+            "for_loop_index",
+            None,
+        );
+    }
+
     #[test]
     fn test_for_loop_index_diagnostic_ranges() {
         use crate::utils_test::expect_diagnostic_highlight;
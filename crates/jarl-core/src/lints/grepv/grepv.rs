@@ -80,9 +80,12 @@ pub fn grepv(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
         Grepv,
         range,
         Fix {
-            content: format!("grepv({inner_content})"),
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: format!("grepv({inner_content})"),
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability: Applicability::Safe,
             to_skip: node_contains_comments(ast.syntax()),
         },
     );
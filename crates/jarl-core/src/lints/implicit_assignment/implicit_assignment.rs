@@ -1,7 +1,81 @@
 use crate::diagnostic::*;
+use crate::rule_options::matches_function;
+use crate::visitor::{Context, Header};
 use air_r_syntax::*;
 use biome_rowan::AstNode;
 
+/// Functions in which implicit assignments are allowed by default. For
+/// instance, `expect_message(x <- foo)` could test that `<-.foo` returns a
+/// message, which is a valid usage.
+const DEFAULT_ALLOWED_FUNCTIONS: &[&str] = &[
+    "expect_error",
+    "expect_warning",
+    "expect_message",
+    "expect_snapshot",
+    "suppressMessages",
+    "suppressWarnings",
+];
+
+/// Where an implicit assignment can be located.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ImplicitAssignmentContext {
+    /// In the condition of `if()`.
+    If,
+    /// In the condition of `while()`.
+    While,
+    /// In the sequence of `for()`.
+    For,
+    /// In the arguments of a function call.
+    Call,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ImplicitAssignmentOptions {
+    /// # Functions in which implicit assignments are allowed
+    ///
+    /// Names of functions whose arguments can contain assignments. A name
+    /// ending with `*` matches all functions starting with this name, e.g.
+    /// `"expect_*"`. This replaces the default list, which is
+    /// `["expect_error", "expect_warning", "expect_message", "expect_snapshot",
+    /// "suppressMessages", "suppressWarnings"]`.
+    pub allowed_functions: Option<Vec<String>>,
+
+    /// # Contexts in which implicit assignments are allowed
+    ///
+    /// Any of `"if"`, `"while"`, `"for"` (outside of the body of these
+    /// statements) and `"call"` (in the arguments of any function call). By
+    /// default, implicit assignments are reported in all of them.
+    pub allowed_contexts: Option<Vec<ImplicitAssignmentContext>>,
+}
+
+impl ImplicitAssignmentOptions {
+    pub(crate) const DEFAULT: Self = Self { allowed_functions: None, allowed_contexts: None };
+
+    pub(crate) fn extend(self, base: Self) -> Self {
+        Self {
+            allowed_functions: self.allowed_functions.or(base.allowed_functions),
+            allowed_contexts: self.allowed_contexts.or(base.allowed_contexts),
+        }
+    }
+
+    fn is_allowed_function(&self, name: &str) -> bool {
+        match &self.allowed_functions {
+            Some(allowed_functions) => matches_function(allowed_functions, name),
+            None => matches_function(DEFAULT_ALLOWED_FUNCTIONS, name),
+        }
+    }
+
+    fn is_allowed_context(&self, context: ImplicitAssignmentContext) -> bool {
+        self.allowed_contexts
+            .as_ref()
+            .is_some_and(|contexts| contexts.contains(&context))
+    }
+}
+
 /// ## What it does
 ///
 /// Checks for implicit assignment in function calls and other situations.
@@ -39,7 +113,25 @@ use biome_rowan::AstNode;
 /// See:
 ///
 /// - [https://style.tidyverse.org/syntax.html#assignment](https://style.tidyverse.org/syntax.html#assignment)
-pub fn implicit_assignment(ast: &RBinaryExpression) -> anyhow::Result<Option<Diagnostic>> {
+///
+/// ## Options
+///
+/// - `allowed-functions`: functions in which implicit assignments are allowed.
+///   Defaults to `["expect_error", "expect_warning", "expect_message",
+///   "expect_snapshot", "suppressMessages", "suppressWarnings"]`.
+/// - `allowed-contexts`: where implicit assignments are allowed, among
+///   `"if"`, `"while"`, `"for"` and `"call"`. Defaults to `[]`.
+///
+/// ```toml
+/// [lint.rules.implicit_assignment]
+/// allowed-functions = ["expect_*", "suppressWarnings"]
+/// allowed-contexts = ["while"]
+/// ```
+pub fn implicit_assignment(
+    ast: &RBinaryExpression,
+    context: Context,
+    options: &ImplicitAssignmentOptions,
+) -> anyhow::Result<Option<Diagnostic>> {
     let operator = ast.operator()?;
     if operator.kind() != RSyntaxKind::ASSIGN
         && operator.kind() != RSyntaxKind::SUPER_ASSIGN
@@ -80,16 +172,7 @@ pub fn implicit_assignment(ast: &RBinaryExpression) -> anyhow::Result<Option<Dia
         for ancestor in ast.syntax().ancestors() {
             if RCall::can_cast(ancestor.kind()) {
                 let function_name = RCall::cast(ancestor).unwrap().function()?.to_trimmed_text();
-                if [
-                    "expect_error",
-                    "expect_warning",
-                    "expect_message",
-                    "expect_snapshot",
-                    "suppressMessages",
-                    "suppressWarnings",
-                ]
-                .contains(&function_name.to_string().as_str())
-                {
+                if options.is_allowed_function(&function_name.to_string()) {
                     return Ok(None);
                 } else {
                     break;
@@ -104,109 +187,32 @@ pub fn implicit_assignment(ast: &RBinaryExpression) -> anyhow::Result<Option<Dia
     // for (i in 1:2) x <- 1
     // while (TRUE) x <- 1
     // ```
-    // i.e., we want to skip cases that are in the body of RIfStatement /
-    // RForStatement / RWhileStatement.
-
-    let ancestor_is_if = {
-        let mut result = false;
-
-        // The `consequence` part of an `RIfStatement` is always the 5th node
-        // (index 4):
-        // IF_KW - L_PAREN - [condition] - R_PAREN - [consequence]
-        //
-        // `.unwrap()` is fine here because the RBinaryExpression will always
-        // have a parent.
-        let in_if_body = ast.syntax().parent().unwrap().kind() == RSyntaxKind::R_IF_STATEMENT
-            && ast.syntax().index() == 4;
-        let in_else_body = ast.syntax().parent().unwrap().kind() == RSyntaxKind::R_ELSE_CLAUSE
-            && ast.syntax().index() == 1;
-
-        if !in_if_body && !in_else_body {
-            for ancestor in ast.syntax().ancestors() {
-                if RBracedExpressions::can_cast(ancestor.kind()) {
-                    // Found braced expressions first, so skip
-                    result = false;
-                    break;
-                } else if RIfStatement::can_cast(ancestor.kind()) {
-                    // Found argument first, so include
-                    result = true;
-                    break;
-                }
-            }
-        }
-        result
-    };
-    let ancestor_is_while = {
-        let mut result = false;
-
-        // The `consequence` part of an `RWhileStatement` is always the 5th node
-        // (index 4):
-        // WHILE_KW - L_PAREN - [condition] - R_PAREN - [consequence]
-        //
-        // `.unwrap()` is fine here because the RBinaryExpression will always
-        // have a parent.
-        let in_while_body = ast.syntax().parent().unwrap().kind() == RSyntaxKind::R_WHILE_STATEMENT
-            && ast.syntax().index() == 4;
-
-        if !in_while_body {
-            for ancestor in ast.syntax().ancestors() {
-                if RBracedExpressions::can_cast(ancestor.kind()) {
-                    // Found braced expressions first, so skip
-                    result = false;
-                    break;
-                } else if RWhileStatement::can_cast(ancestor.kind()) {
-                    // Found argument first, so include
-                    result = true;
-                    break;
-                }
-            }
-        }
-        result
+    // i.e., we only report assignments in the condition of `if()` and
+    // `while()` and in the sequence of `for()`, not in their body.
+    let (location, msg) = match context.header() {
+        Some(Header::If) => (
+            ImplicitAssignmentContext::If,
+            "Avoid implicit assignments in `if()` statements.",
+        ),
+        Some(Header::While) => (
+            ImplicitAssignmentContext::While,
+            "Avoid implicit assignments in `while()` statements.",
+        ),
+        Some(Header::For) => (
+            ImplicitAssignmentContext::For,
+            "Avoid implicit assignments in `for()` statements.",
+        ),
+        None if ancestor_is_arg => (
+            ImplicitAssignmentContext::Call,
+            "Avoid implicit assignments in function calls.",
+        ),
+        None => return Ok(None),
     };
-    let ancestor_is_for = {
-        let mut result = false;
 
-        // The `consequence` part of an `RWhileStatement` is always the 7th node
-        // (index 6):
-        // FOR_KW - L_PAREN - [value] - IN_KW - [sequence] - R_PAREN - [consequence]
-        //
-        // `.unwrap()` is fine here because the RBinaryExpression will always
-        // have a parent.
-        let in_for_body = ast.syntax().parent().unwrap().kind() == RSyntaxKind::R_FOR_STATEMENT
-            && ast.syntax().index() == 6;
-
-        if !in_for_body {
-            for ancestor in ast.syntax().ancestors() {
-                if RBracedExpressions::can_cast(ancestor.kind()) {
-                    // Found braced expressions first, so skip
-                    result = false;
-                    break;
-                } else if RForStatement::can_cast(ancestor.kind()) {
-                    // Found argument first, so include
-                    result = true;
-                    break;
-                }
-            }
-        }
-        result
-    };
-
-    if !ancestor_is_if && !ancestor_is_while && !ancestor_is_arg && !ancestor_is_for {
+    if options.is_allowed_context(location) {
         return Ok(None);
     }
 
-    let msg = if ancestor_is_if {
-        "Avoid implicit assignments in `if()` statements."
-    } else if ancestor_is_while {
-        "Avoid implicit assignments in `while()` statements."
-    } else if ancestor_is_for {
-        "Avoid implicit assignments in `for()` statements."
-    } else if ancestor_is_arg {
-        "Avoid implicit assignments in function calls."
-    } else {
-        unreachable!()
-    };
-
     let range = ast.syntax().text_trimmed_range();
     let diagnostic = Diagnostic::new(
         ViolationData::new("implicit_assignment".to_string(), msg.to_string(), None),
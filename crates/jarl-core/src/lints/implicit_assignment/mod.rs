@@ -66,6 +66,31 @@ mod tests {
             "implicit_assignment",
             None,
         );
+        expect_lint(
+            "while (if (x <- 1) y else z) TRUE",
+            "in `if()` statements",
+            "implicit_assignment",
+            None,
+        );
+        expect_lint(
+            "while (if (a) x <- 1 else z) TRUE",
+            "in `while()` statements",
+            "implicit_assignment",
+            None,
+        );
+        // The body of a statement is not its condition.
+        expect_lint(
+            "if (TRUE) foo(x <- 1)",
+            "in function calls",
+            "implicit_assignment",
+            None,
+        );
+        expect_lint(
+            "for (i in x) foo(y <- 1)",
+            "in function calls",
+            "implicit_assignment",
+            None,
+        );
     }
 
     #[test]
@@ -83,6 +108,9 @@ mod tests {
         expect_no_lint("if (TRUE) x <- 1", "implicit_assignment", None);
         expect_no_lint("for (i in 1:2) x <- 1", "implicit_assignment", None);
         expect_no_lint("while (TRUE) x <- 1", "implicit_assignment", None);
+        expect_no_lint("for (i in 1:2) (x <- 1)", "implicit_assignment", None);
+        expect_no_lint("if ({x <- 1; x}) TRUE", "implicit_assignment", None);
+        expect_no_lint("for (i in {x <- 1:2; x}) TRUE", "implicit_assignment", None);
         expect_no_lint(
             "f <- function() {
   if (TRUE)
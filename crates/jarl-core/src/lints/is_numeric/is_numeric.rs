@@ -91,9 +91,12 @@ pub fn is_numeric(ast: &RBinaryExpression) -> anyhow::Result<Option<Diagnostic>>
         IsNumeric,
         range,
         Fix {
-            content: format!("is.numeric{left_arg}"),
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: format!("is.numeric{left_arg}"),
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability: Applicability::Safe,
             to_skip: node_contains_comments(ast.syntax()),
         },
     );
@@ -48,9 +48,12 @@ pub fn length_levels(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
             LengthLevels,
             range,
             Fix {
-                content: format!("nlevels({inner_content})"),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("nlevels({inner_content})"),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Safe,
                 to_skip: node_contains_comments(ast.syntax()),
             },
         );
@@ -87,9 +87,12 @@ pub fn length_test(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
             LengthTest,
             range,
             Fix {
-                content: format!("length({lhs}) {operator_text} {rhs}"),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("length({lhs}) {operator_text} {rhs}"),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Safe,
                 to_skip: node_contains_comments(ast.syntax()),
             },
         );
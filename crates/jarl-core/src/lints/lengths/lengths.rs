@@ -71,9 +71,12 @@ pub fn lengths(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
             Lengths,
             range,
             Fix {
-                content: format!("lengths({})", arg_x.unwrap().into_syntax().text_trimmed()),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("lengths({})", arg_x.unwrap().into_syntax().text_trimmed()),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Safe,
                 to_skip: node_contains_comments(ast.syntax()),
             },
         );
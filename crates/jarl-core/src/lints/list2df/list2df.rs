@@ -98,9 +98,12 @@ pub fn list2df(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
         List2Df,
         range,
         Fix {
-            content: format!("list2DF({})", fix_content.to_trimmed_text()),
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: format!("list2DF({})", fix_content.to_trimmed_text()),
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability: Applicability::Safe,
             to_skip: node_contains_comments(ast.syntax()),
         },
     );
@@ -145,9 +145,12 @@ pub fn matrix_apply(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
         ),
         range,
         Fix {
-            content: fix,
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: fix,
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability: Applicability::Safe,
             to_skip: node_contains_comments(ast.syntax()),
         },
     );
@@ -1,5 +1,10 @@
+use crate::check::Checker;
+use crate::diagnostic::Diagnostic;
 use crate::rule_table::{FixStatus, RuleTable};
-use std::collections::HashSet;
+use air_r_syntax::{
+    AnyRValue, RBinaryExpression, RCall, RForStatement, RIdentifier, RIfStatement, RSubset,
+    RSubset2, RUnaryExpression, RWhileStatement,
+};
 use std::sync::OnceLock;
 
 pub(crate) mod all_equal;
@@ -32,100 +37,256 @@ pub(crate) mod sort;
 pub(crate) mod true_false_symbol;
 pub(crate) mod which_grepl;
 
+use all_equal::all_equal::all_equal;
+use any_duplicated::any_duplicated::any_duplicated;
+use any_is_na::any_is_na::any_is_na;
+use assignment::assignment::assignment;
+use browser::browser::browser;
+use class_equals::class_equals::class_equals;
+use coalesce::coalesce::coalesce;
+use comparison_negation::comparison_negation::comparison_negation;
+use download_file::download_file::download_file;
+use duplicated_arguments::duplicated_arguments::duplicated_arguments;
+use empty_assignment::empty_assignment::empty_assignment;
+use equals_na::equals_na::equals_na;
+use for_loop_index::for_loop_index::for_loop_index;
+use grepv::grepv::grepv;
+use implicit_assignment::implicit_assignment::implicit_assignment;
+use is_numeric::is_numeric::is_numeric;
+use length_levels::length_levels::length_levels;
+use length_test::length_test::length_test;
+use lengths::lengths::lengths;
+use list2df::list2df::list2df;
+use matrix_apply::matrix_apply::matrix_apply;
+use numeric_leading_zero::numeric_leading_zero::numeric_leading_zero;
+use outer_negation::outer_negation::outer_negation;
+use redundant_equals::redundant_equals::redundant_equals;
+use repeat::repeat::repeat;
+use sample_int::sample_int::sample_int;
+use sort::sort::sort;
+use true_false_symbol::true_false_symbol::true_false_symbol;
+use which_grepl::which_grepl::which_grepl;
+
 pub static RULE_GROUPS: &[&str] = &["CORR", "PERF", "READ", "SUSP"];
 
-/// List of supported rules and whether they have a safe fix.
-///
-/// Possible categories:
-/// - CORR: correctness, code that is outright wrong or useless
-/// - SUSP: suspicious, code that is most likely wrong or useless
-/// - PERF: performance, code that can be written to run faster
-/// - READ: readibility, code is correct but can be written in a way that is
-///   easier to read.
-pub fn all_rules_and_safety() -> RuleTable {
-    let mut rule_table = RuleTable::empty();
-    rule_table.enable("all_equal", "SUSP", FixStatus::Unsafe, None);
-    rule_table.enable("any_duplicated", "PERF", FixStatus::Safe, None);
-    rule_table.enable("any_is_na", "PERF", FixStatus::Safe, None);
-    rule_table.enable("assignment", "READ", FixStatus::Safe, None);
-    rule_table.enable("browser", "CORR", FixStatus::Safe, None);
-    rule_table.enable("class_equals", "SUSP", FixStatus::Safe, None);
-    rule_table.enable("comparison_negation", "READ", FixStatus::Safe, None);
-    rule_table.enable("coalesce", "READ", FixStatus::Safe, Some((4, 4, 0)));
-    rule_table.enable("download_file", "SUSP", FixStatus::None, None);
-    rule_table.enable("duplicated_arguments", "SUSP", FixStatus::None, None);
-    rule_table.enable("empty_assignment", "READ", FixStatus::Safe, None);
-    rule_table.enable("equals_na", "CORR", FixStatus::Safe, None);
-    rule_table.enable("for_loop_index", "READ", FixStatus::None, None);
-    rule_table.enable("grepv", "READ", FixStatus::Safe, Some((4, 5, 0)));
-    rule_table.enable("implicit_assignment", "READ", FixStatus::None, None);
-    rule_table.enable("is_numeric", "READ", FixStatus::Safe, None);
-    rule_table.enable("length_levels", "READ", FixStatus::Safe, None);
-    rule_table.enable("length_test", "CORR", FixStatus::Safe, None);
-    rule_table.enable("lengths", "PERF,READ", FixStatus::Safe, None);
-    rule_table.enable("list2df", "PERF,READ", FixStatus::Safe, Some((4, 0, 0)));
-    rule_table.enable("matrix_apply", "PERF", FixStatus::Safe, None);
-    rule_table.enable("numeric_leading_zero", "READ", FixStatus::Safe, None);
-    rule_table.enable("outer_negation", "PERF,READ", FixStatus::Safe, None);
-    rule_table.enable("redundant_equals", "READ", FixStatus::Safe, None);
-    rule_table.enable("repeat", "READ", FixStatus::Safe, None);
-    rule_table.enable("sample_int", "READ", FixStatus::Safe, None);
-    rule_table.enable("sort", "PERF,READ", FixStatus::Safe, None);
-    rule_table.enable("true_false_symbol", "READ", FixStatus::None, None);
-    rule_table.enable("which_grepl", "PERF,READ", FixStatus::Safe, None);
-    rule_table
+/// Signature of the function that checks a node of type `N` for a rule.
+pub type CheckFn<N> = fn(&N, &Checker) -> anyhow::Result<Option<Diagnostic>>;
+
+/// The kind of node a rule needs to inspect, and the function that does it.
+#[derive(Clone, Copy)]
+pub enum Check {
+    AnyValue(CheckFn<AnyRValue>),
+    BinaryExpression(CheckFn<RBinaryExpression>),
+    Call(CheckFn<RCall>),
+    ForStatement(CheckFn<RForStatement>),
+    Identifier(CheckFn<RIdentifier>),
+    IfStatement(CheckFn<RIfStatement>),
+    Subset(CheckFn<RSubset>),
+    Subset2(CheckFn<RSubset2>),
+    UnaryExpression(CheckFn<RUnaryExpression>),
+    WhileStatement(CheckFn<RWhileStatement>),
+}
+
+/// When a rule runs: before (`Enter`) or after (`Exit`) the children of the
+/// node are visited. Most rules only need `Enter`, which is the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    Enter,
+    Exit,
 }
 
-/// Cached set of safe rule names for O(1) lookup
-static SAFE_RULES: OnceLock<HashSet<String>> = OnceLock::new();
+/// Everything we know about a rule, see `RULES`.
+pub struct RuleInfo {
+    pub id: RuleId,
+    pub name: &'static str,
+    /// Comma-separated list of categories, e.g. `"PERF,READ"`.
+    pub categories: &'static str,
+    pub fix_status: FixStatus,
+    pub minimum_r_version: Option<(u32, u32, u32)>,
+    pub check: Check,
+    pub hook: Hook,
+    /// Documentation of the rule, extracted from its doc comment at build
+    /// time (see `build.rs`). Empty if the rule isn't documented. See
+    /// [RuleId::documentation()].
+    pub documentation: &'static str,
+}
 
-/// Cached set of unsafe rule names for O(1) lookup
-static UNSAFE_RULES: OnceLock<HashSet<String>> = OnceLock::new();
+/// Generates the `RuleId` enum and the `RULES` registry from a single list,
+/// so that the variants and the registry entries are always in the same order.
+///
+/// Rules run when their node is entered, append `, Exit` after the check
+/// function to run them once all the children of the node have been visited.
+macro_rules! declare_rules {
+    (@hook) => { Hook::Enter };
+    (@hook $hook:ident) => { Hook::$hook };
+    ($(
+        $id:ident => ($name:literal, $categories:literal, $fix:ident, $version:expr, $kind:ident($check:expr) $(, $hook:ident)?)
+    ),+ $(,)?) => {
+        /// Identifier of a rule. Use this rather than the rule name as a
+        /// string to refer to a rule in the code.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum RuleId {
+            $($id),+
+        }
 
-/// Cached set of no-fix rule names for O(1) lookup
-static NOFIX_RULES: OnceLock<HashSet<String>> = OnceLock::new();
+        impl RuleId {
+            /// All rules, in the same order as `RULES`.
+            pub const ALL: &'static [RuleId] = &[$(RuleId::$id),+];
+            pub const COUNT: usize = RuleId::ALL.len();
+        }
 
-/// Get the cached set of safe rule names
-pub fn safe_rules_set() -> &'static HashSet<String> {
-    SAFE_RULES.get_or_init(|| {
-        all_rules_and_safety()
-            .iter()
-            .filter(|x| x.has_safe_fix())
-            .map(|x| x.name.clone())
-            .collect()
-    })
+        /// Registry of all rules.
+        pub static RULES: &[RuleInfo] = &[$(
+            RuleInfo {
+                id: RuleId::$id,
+                name: $name,
+                categories: $categories,
+                fix_status: FixStatus::$fix,
+                minimum_r_version: $version,
+                check: Check::$kind($check),
+                hook: declare_rules!(@hook $($hook)?),
+                documentation: include_str!(concat!(env!("OUT_DIR"), "/docs/", $name, ".md")),
+            }
+        ),+];
+    };
 }
 
-/// Get the cached set of unsafe rule names
-pub fn unsafe_rules_set() -> &'static HashSet<String> {
-    UNSAFE_RULES.get_or_init(|| {
-        all_rules_and_safety()
-            .iter()
-            .filter(|x| x.has_unsafe_fix())
-            .map(|x| x.name.clone())
-            .collect()
-    })
+// Possible categories:
+// - CORR: correctness, code that is outright wrong or useless
+// - SUSP: suspicious, code that is most likely wrong or useless
+// - PERF: performance, code that can be written to run faster
+// - READ: readibility, code is correct but can be written in a way that is
+//   easier to read.
+declare_rules! {
+    AllEqual => ("all_equal", "SUSP", Unsafe, None, Call(|x, _| all_equal(x))),
+    AnyDuplicated => ("any_duplicated", "PERF", Safe, None, Call(|x, _| any_duplicated(x))),
+    AnyIsNa => ("any_is_na", "PERF", Safe, None, Call(|x, _| any_is_na(x))),
+    Assignment => ("assignment", "READ", Safe, None, BinaryExpression(|x, checker| assignment(x, checker.assignment_op))),
+    Browser => ("browser", "CORR", Safe, None, Call(|x, _| browser(x))),
+    ClassEquals => ("class_equals", "SUSP", Safe, None, BinaryExpression(|x, _| class_equals(x))),
+    ComparisonNegation => ("comparison_negation", "READ", Safe, None, UnaryExpression(|x, _| comparison_negation(x))),
+    Coalesce => ("coalesce", "READ", Safe, Some((4, 4, 0)), IfStatement(|x, _| coalesce(x))),
+    DownloadFile => ("download_file", "SUSP", None, None, Call(|x, _| download_file(x))),
+    DuplicatedArguments => ("duplicated_arguments", "SUSP", None, None, Call(|x, checker| duplicated_arguments(x, checker.rule_options.duplicated_arguments()))),
+    EmptyAssignment => ("empty_assignment", "READ", Safe, None, BinaryExpression(|x, _| empty_assignment(x))),
+    EqualsNa => ("equals_na", "CORR", Safe, None, BinaryExpression(|x, _| equals_na(x))),
+    ForLoopIndex => ("for_loop_index", "READ", None, None, ForStatement(|x, checker| for_loop_index(x, &checker.semantic))),
+    Grepv => ("grepv", "READ", Safe, Some((4, 5, 0)), Call(|x, _| grepv(x))),
+    ImplicitAssignment => ("implicit_assignment", "READ", None, None, BinaryExpression(|x, checker| implicit_assignment(x, checker.context, checker.rule_options.implicit_assignment()))),
+    IsNumeric => ("is_numeric", "READ", Safe, None, BinaryExpression(|x, _| is_numeric(x))),
+    LengthLevels => ("length_levels", "READ", Safe, None, Call(|x, _| length_levels(x))),
+    LengthTest => ("length_test", "CORR", Safe, None, Call(|x, _| length_test(x))),
+    Lengths => ("lengths", "PERF,READ", Safe, None, Call(|x, _| lengths(x))),
+    List2df => ("list2df", "PERF,READ", Safe, Some((4, 0, 0)), Call(|x, _| list2df(x))),
+    MatrixApply => ("matrix_apply", "PERF", Safe, None, Call(|x, _| matrix_apply(x))),
+    NumericLeadingZero => ("numeric_leading_zero", "READ", Safe, None, AnyValue(|x, _| numeric_leading_zero(x))),
+    OuterNegation => ("outer_negation", "PERF,READ", Safe, None, Call(|x, _| outer_negation(x))),
+    RedundantEquals => ("redundant_equals", "READ", Safe, None, BinaryExpression(|x, _| redundant_equals(x))),
+    Repeat => ("repeat", "READ", Safe, None, WhileStatement(|x, _| repeat(x))),
+    SampleInt => ("sample_int", "READ", Safe, None, Call(|x, _| sample_int(x))),
+    Sort => ("sort", "PERF,READ", Safe, None, Subset(|x, _| sort(x))),
+    TrueFalseSymbol => ("true_false_symbol", "READ", None, None, Identifier(|x, checker| true_false_symbol(x, &checker.semantic))),
+    WhichGrepl => ("which_grepl", "PERF,READ", Safe, None, Call(|x, _| which_grepl(x))),
 }
 
-/// Get the cached set of no-fix rule names
-pub fn nofix_rules_set() -> &'static HashSet<String> {
-    NOFIX_RULES.get_or_init(|| {
-        all_rules_and_safety()
+impl RuleId {
+    pub fn info(self) -> &'static RuleInfo {
+        &RULES[self as usize]
+    }
+
+    pub fn name(self) -> &'static str {
+        self.info().name
+    }
+
+    pub fn from_name(name: &str) -> Option<RuleId> {
+        RULES
             .iter()
-            .filter(|x| x.has_no_fix())
-            .map(|x| x.name.clone())
-            .collect()
-    })
+            .find(|rule| rule.name == name)
+            .map(|rule| rule.id)
+    }
+
+    /// The documentation of the rule, i.e. its sections "What it does", "Why
+    /// is this bad?", "Example", etc. This is the same content as the page of
+    /// the rule on the website. Returns `None` if the rule isn't documented.
+    pub fn documentation(self) -> Option<&'static str> {
+        Some(self.info().documentation).filter(|documentation| !documentation.is_empty())
+    }
 }
 
-pub fn all_safe_rules() -> Vec<String> {
-    safe_rules_set().iter().cloned().collect()
+/// Generates a function returning the rules that inspect a given kind of node
+/// with the given hook, along with their check function. This is computed once
+/// from `RULES`.
+macro_rules! rules_for_node {
+    ($fn_name:ident, $kind:ident, $node:ty) => {
+        pub(crate) fn $fn_name(hook: Hook) -> &'static [(RuleId, CheckFn<$node>)] {
+            type RulesForNode = Vec<(RuleId, CheckFn<$node>)>;
+            static RULES_FOR_NODE: OnceLock<(RulesForNode, RulesForNode)> = OnceLock::new();
+            let (enter, exit) = RULES_FOR_NODE.get_or_init(|| {
+                let rules_with_hook = |hook: Hook| {
+                    RULES
+                        .iter()
+                        .filter(|rule| rule.hook == hook)
+                        .filter_map(|rule| match rule.check {
+                            Check::$kind(check) => Some((rule.id, check)),
+                            _ => None,
+                        })
+                        .collect()
+                };
+                (rules_with_hook(Hook::Enter), rules_with_hook(Hook::Exit))
+            });
+            match hook {
+                Hook::Enter => enter,
+                Hook::Exit => exit,
+            }
+        }
+    };
 }
 
-pub fn all_unsafe_rules() -> Vec<String> {
-    unsafe_rules_set().iter().cloned().collect()
+rules_for_node!(anyvalue_rules, AnyValue, AnyRValue);
+rules_for_node!(binary_expression_rules, BinaryExpression, RBinaryExpression);
+rules_for_node!(call_rules, Call, RCall);
+rules_for_node!(for_loop_rules, ForStatement, RForStatement);
+rules_for_node!(identifier_rules, Identifier, RIdentifier);
+rules_for_node!(if_rules, IfStatement, RIfStatement);
+rules_for_node!(subset_rules, Subset, RSubset);
+rules_for_node!(subset2_rules, Subset2, RSubset2);
+rules_for_node!(unary_expression_rules, UnaryExpression, RUnaryExpression);
+rules_for_node!(while_rules, WhileStatement, RWhileStatement);
+
+/// List of supported rules and whether they have a safe fix.
+pub fn all_rules_and_safety() -> RuleTable {
+    let mut rule_table = RuleTable::empty();
+    for rule in RULES {
+        rule_table.enable(
+            rule.name,
+            rule.categories,
+            rule.fix_status,
+            rule.minimum_r_version,
+        );
+    }
+    rule_table
 }
 
-pub fn all_nofix_rules() -> Vec<String> {
-    nofix_rules_set().iter().cloned().collect()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_documentation() {
+        let documentation = RuleId::AnyIsNa.documentation().unwrap();
+        assert!(
+            documentation.starts_with("## What it does\n\nChecks for usage of `any(is.na(...))`.")
+        );
+        assert!(documentation.ends_with("See `?anyNA`"));
+        assert!(!documentation.contains("///"));
+
+        for id in RuleId::ALL {
+            let documentation = id.documentation();
+            assert!(
+                documentation.is_some_and(|doc| doc.starts_with("## What it does")),
+                "Rule `{}` is not documented",
+                id.name()
+            );
+        }
+    }
 }
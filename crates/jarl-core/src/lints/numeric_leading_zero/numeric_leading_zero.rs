@@ -52,9 +52,12 @@ pub fn numeric_leading_zero(ast: &AnyRValue) -> anyhow::Result<Option<Diagnostic
             NumericLeadingZero,
             range,
             Fix {
-                content: format!("0{value_text}"),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("0{value_text}"),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Safe,
                 to_skip: false,
             },
         );
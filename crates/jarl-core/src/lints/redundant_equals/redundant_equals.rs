@@ -76,9 +76,12 @@ pub fn redundant_equals(ast: &RBinaryExpression) -> anyhow::Result<Option<Diagno
                 RedundantEquals,
                 range,
                 Fix {
-                    content: fix,
-                    start: range.start().into(),
-                    end: range.end().into(),
+                    edits: vec![Edit {
+                        content: fix,
+                        start: range.start().into(),
+                        end: range.end().into(),
+                    }],
+                    applicability: Applicability::Safe,
                     to_skip: node_contains_comments(ast.syntax()),
                 },
             )
@@ -100,9 +103,12 @@ pub fn redundant_equals(ast: &RBinaryExpression) -> anyhow::Result<Option<Diagno
                 RedundantEquals,
                 range,
                 Fix {
-                    content: fix,
-                    start: range.start().into(),
-                    end: range.end().into(),
+                    edits: vec![Edit {
+                        content: fix,
+                        start: range.start().into(),
+                        end: range.end().into(),
+                    }],
+                    applicability: Applicability::Safe,
                     to_skip: node_contains_comments(ast.syntax()),
                 },
             )
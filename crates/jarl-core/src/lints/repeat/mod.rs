@@ -47,4 +47,10 @@ mod tests {
         expect_no_lint("while (j < 5) TRUE", "repeat", None);
         expect_no_lint("while (TRUE && j < 5) { ... }", "repeat", None);
     }
+
+    #[test]
+    fn test_no_lint_repeat_with_nolint() {
+        expect_no_lint("# nolint\nwhile (TRUE) { }", "repeat", None);
+        expect_no_lint("# nolint: repeat\nwhile (TRUE) { }", "repeat", None);
+    }
 }
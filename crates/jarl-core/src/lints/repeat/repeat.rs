@@ -65,9 +65,12 @@ pub fn repeat(ast: &RWhileStatement) -> anyhow::Result<Option<Diagnostic>> {
             Repeat,
             range_to_report,
             Fix {
-                content: format!("repeat {fix_content}"),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("repeat {fix_content}"),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Safe,
                 to_skip: node_contains_comments(ast.syntax()),
             },
         );
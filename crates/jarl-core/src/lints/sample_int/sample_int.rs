@@ -94,9 +94,12 @@ pub fn sample_int(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
         SampleInt,
         range,
         Fix {
-            content: format!("sample.int({inner_content})"),
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: format!("sample.int({inner_content})"),
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability: Applicability::Safe,
             to_skip: node_contains_comments(ast.syntax()),
         },
     );
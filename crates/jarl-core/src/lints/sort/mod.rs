@@ -71,6 +71,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sort_fix_is_unsafe_without_na_last() {
+        // `sort()` removes missing values but `x[order(x)]` keeps them.
+        assert_eq!(
+            apply_fixes("x[order(x)]", "sort", false, None),
+            "x[order(x)]"
+        );
+        assert_eq!(apply_fixes("x[order(x)]", "sort", true, None), "sort(x)");
+        assert_eq!(
+            apply_fixes("x[order(x, decreasing = TRUE)]", "sort", true, None),
+            "sort(x, decreasing = TRUE)"
+        );
+
+        assert_eq!(
+            apply_fixes("x[order(x, na.last = TRUE)]", "sort", false, None),
+            "sort(x, na.last = TRUE)"
+        );
+        assert_eq!(
+            apply_fixes("x[order(x, na.last = NA)]", "sort", false, None),
+            "sort(x, na.last = NA)"
+        );
+    }
+
     #[test]
     fn test_sort_with_comments_no_fix() {
         use insta::assert_snapshot;
@@ -14,6 +14,10 @@ pub struct Sort;
 /// It is better to use `sort(x, ...)`, which is more readable than
 /// `x[order(x, ...)]` and more efficient.
 ///
+/// The fix is unsafe if `na.last` is not passed to `order()`: by default,
+/// `order()` puts missing values last while `sort()` removes them, so
+/// `x[order(x)]` and `sort(x)` differ if `x` contains missing values.
+///
 /// ## Example
 ///
 /// ```r
@@ -97,6 +101,14 @@ pub fn sort(ast: &RSubset) -> anyhow::Result<Option<Diagnostic>> {
     let decreasing = get_arg_by_name(&args, "decreasing");
     let method = get_arg_by_name(&args, "method");
 
+    // `order()` keeps missing values by default but `sort()` removes them, so
+    // both are only equivalent if `na.last` is passed.
+    let applicability = if na_last.is_some() {
+        Applicability::Safe
+    } else {
+        Applicability::Unsafe
+    };
+
     // Prepare text of other args to include in the fix.
     let mut additional_args = vec![];
     if let Some(na_last) = na_last {
@@ -124,9 +136,12 @@ pub fn sort(ast: &RSubset) -> anyhow::Result<Option<Diagnostic>> {
         Sort,
         range,
         Fix {
-            content: fix,
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: fix,
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability,
             to_skip: node_contains_comments(ast.syntax()),
         },
     );
@@ -56,12 +56,35 @@ mod tests {
         expect_no_lint("lm(T ~ weight, data)", "true_false_symbol", None);
     }
 
-    // TODO
-    // #[test]
-    // fn test_true_false_symbol_in_function_args() {
-    //     expect_no_lint("myfun <- function(T) {}", "true_false_symbol", None));
-    //     expect_no_lint("myfun <- function(F) {}", "true_false_symbol", None));
-    // }
+    #[test]
+    fn test_true_false_symbol_in_function_args() {
+        expect_no_lint("myfun <- function(T) {}", "true_false_symbol", None);
+        expect_no_lint("myfun <- function(F) {}", "true_false_symbol", None);
+        expect_no_lint("myfun <- function(T) T", "true_false_symbol", None);
+        expect_no_lint("myfun <- \\(F = 1) F + 1", "true_false_symbol", None);
+    }
+
+    #[test]
+    fn test_true_false_symbol_redefined() {
+        expect_no_lint("T <- 1", "true_false_symbol", None);
+        expect_no_lint("T <- 1\nx <- T + 1", "true_false_symbol", None);
+        expect_no_lint("1 -> F; F", "true_false_symbol", None);
+        expect_no_lint("assign('T', 1)\nT", "true_false_symbol", None);
+        expect_no_lint("for (T in 1:3) print(T)", "true_false_symbol", None);
+        expect_no_lint("f <- function() T\nT <- 1", "true_false_symbol", None);
+    }
+
+    #[test]
+    fn test_true_false_symbol_shadowing_is_scoped() {
+        // `T` is only redefined inside the function, so the last `T` is still
+        // reported.
+        let diagnostics = check_code("f <- function(T) T\nx <- T", "true_false_symbol", None);
+        assert_eq!(diagnostics.len(), 1);
+
+        // `T` is used before being redefined.
+        let diagnostics = check_code("x <- T\nT <- 1", "true_false_symbol", None);
+        assert_eq!(diagnostics.len(), 1);
+    }
 
     // #[test]
     // fn test_true_false_symbol_in_named_vectors() {
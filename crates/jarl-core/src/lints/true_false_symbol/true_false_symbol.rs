@@ -1,4 +1,5 @@
 use crate::diagnostic::*;
+use crate::semantic::SemanticModel;
 use air_r_syntax::*;
 use biome_rowan::AstNode;
 
@@ -8,8 +9,8 @@ pub struct TrueFalseSymbol;
 ///
 /// Checks for usage of `T` and `F` symbols. If they correspond to the `TRUE`
 /// and `FALSE` values, then replace them by that. If they correspond to
-/// something else, such as an object or a variable name, then no automatic
-/// fixes are applied.
+/// something else, such as an object, a function parameter, or a loop variable
+/// defined in the same file, then they are not reported.
 ///
 /// ## Why is this bad?
 ///
@@ -41,13 +42,23 @@ impl Violation for TrueFalseSymbol {
     }
 }
 
-pub fn true_false_symbol(ast: &RIdentifier) -> anyhow::Result<Option<Diagnostic>> {
+pub fn true_false_symbol(
+    ast: &RIdentifier,
+    semantic: &SemanticModel,
+) -> anyhow::Result<Option<Diagnostic>> {
     let token = ast.name_token()?;
     let name = token.text_trimmed();
     if name != "T" && name != "F" {
         return Ok(None);
     }
 
+    // Allow `T <- 1`, `for (T in x)`, and any usage of `T` after that since it
+    // now refers to a user-defined object.
+    let range = ast.syntax().text_trimmed_range();
+    if semantic.is_binding_site(range) || semantic.resolve(name, range).is_some() {
+        return Ok(None);
+    }
+
     // Allow T(), F()
     if ast.parent::<RCall>().is_some() {
         return Ok(None);
@@ -71,18 +82,20 @@ pub fn true_false_symbol(ast: &RIdentifier) -> anyhow::Result<Option<Diagnostic>
         return Ok(None);
     }
 
-    let range = ast.syntax().text_trimmed_range();
     let diagnostic = Diagnostic::new(
         TrueFalseSymbol,
         range,
         Fix {
-            content: if ast.syntax().text_trimmed() == "T" {
-                "TRUE".to_string()
-            } else {
-                "FALSE".to_string()
-            },
-            start: range.start().into(),
-            end: range.end().into(),
+            edits: vec![Edit {
+                content: if ast.syntax().text_trimmed() == "T" {
+                    "TRUE".to_string()
+                } else {
+                    "FALSE".to_string()
+                },
+                start: range.start().into(),
+                end: range.end().into(),
+            }],
+            applicability: Applicability::Safe,
             to_skip: false,
         },
     );
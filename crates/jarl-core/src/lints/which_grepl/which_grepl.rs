@@ -53,9 +53,12 @@ pub fn which_grepl(ast: &RCall) -> anyhow::Result<Option<Diagnostic>> {
             WhichGrepl,
             range,
             Fix {
-                content: format!("grep({inner_content})"),
-                start: range.start().into(),
-                end: range.end().into(),
+                edits: vec![Edit {
+                    content: format!("grep({inner_content})"),
+                    start: range.start().into(),
+                    end: range.end().into(),
+                }],
+                applicability: Applicability::Safe,
                 to_skip: node_contains_comments(ast.syntax()),
             },
         );
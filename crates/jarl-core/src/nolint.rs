@@ -0,0 +1,270 @@
+//! Insertion of `# nolint` comments for the violations of existing code
+//!
+//! This is used by `jarl check --add-nolint`, so that new rules can be enabled
+//! immediately and the code that violates them fixed gradually.
+
+use air_fs::relativize_path;
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::check::{FixedSource, lint_source};
+use crate::config::Config;
+use crate::diagnostic::Diagnostic;
+use crate::directive::{LintDirective, parse_comment_directive, update_existing_nolint};
+use crate::suppression::NOLINT_WITHOUT_REASON;
+use crate::vcs::check_version_control;
+
+/// Add `# nolint` comments to all files in `config.paths` and write them.
+/// `reason` is added to the new comments, see [add_nolint_source()].
+///
+/// Like fixes, this requires the files to be covered by version control,
+/// unless `--allow-dirty` or `--allow-no-vcs` is passed.
+pub fn add_nolint(config: Config, reason: Option<&str>) -> Vec<(String, Result<FixedSource>)> {
+    for path in &config.paths {
+        let path_str = relativize_path(path);
+        if let Err(e) = check_version_control(&path_str, &config) {
+            return vec![(path_str, Err(e))];
+        }
+    }
+
+    config
+        .paths
+        .par_iter()
+        .map(|file| {
+            let path = relativize_path(file);
+            let res = add_nolint_file(Path::new(&path), &config, reason);
+            (path, res)
+        })
+        .collect()
+}
+
+fn add_nolint_file(path: &Path, config: &Config, reason: Option<&str>) -> Result<FixedSource> {
+    let original = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let (diagnostics, fixed) = add_nolint_source(&original, path, config, reason)?;
+
+    if fixed != original {
+        fs::write(path, &fixed)
+            .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    }
+
+    Ok(FixedSource { original, fixed, diagnostics })
+}
+
+/// Add `# nolint: rules` comments to R code that is already in memory.
+///
+/// For each line where a violation ends, this adds a trailing
+/// `# nolint: rules` comment or extends the one that is already there.
+///
+/// A trailing comment suppresses the violations of the node it is attached
+/// to and of the nodes that start on its line (see
+/// [crate::suppression::SuppressionManager]). It is attached to the outermost
+/// node that ends on its line, so it doesn't suppress violations in nested
+/// nodes that start on a previous line, e.g. the `any()` call of
+/// `x <- any(\n  is.na(y)\n)`. We therefore lint the code with the new
+/// comments and only keep the rules that did suppress a violation.
+///
+/// New comments end with ` -- reason` if `reason` is passed, e.g. so that
+/// they are not reported with `require-nolint-reason = true`. Existing
+/// comments keep their own reason.
+///
+/// This returns the diagnostics that are left, i.e. those that can't be
+/// suppressed with a trailing comment, and the new code. Code that doesn't
+/// parse is left as is.
+pub fn add_nolint_source(
+    contents: &str,
+    path: &Path,
+    config: &Config,
+    reason: Option<&str>,
+) -> Result<(Vec<Diagnostic>, String)> {
+    let diagnostics = lint_source(contents, path, config)?;
+    if diagnostics.is_empty() || diagnostics.iter().any(|d| d.is_parse_error()) {
+        return Ok((diagnostics, contents.to_string()));
+    }
+
+    let mut rules_by_line: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for diagnostic in &diagnostics {
+        // This reports a `# nolint` comment, it can't be suppressed by another
+        if diagnostic.message.name == NOLINT_WITHOUT_REASON {
+            continue;
+        }
+        let rules = rules_by_line
+            .entry(line_end(contents, diagnostic))
+            .or_default();
+        if !rules.contains(&diagnostic.message.name.as_str()) {
+            rules.push(&diagnostic.message.name);
+        }
+    }
+
+    let candidate = insert_nolint(contents, &rules_by_line, reason);
+    let remaining = lint_source(&candidate, path, config)?;
+    if remaining.iter().any(|d| d.is_parse_error()) {
+        return Ok((diagnostics, contents.to_string()));
+    }
+
+    // Comments are added at the end of lines, so the rows and columns of the
+    // violations that are not suppressed don't change.
+    for (line, rules) in rules_by_line.iter_mut() {
+        rules.retain(|rule| {
+            diagnostics.iter().any(|diagnostic| {
+                diagnostic.message.name == *rule
+                    && line_end(contents, diagnostic) == *line
+                    && !remaining.iter().any(|other| {
+                        other.message.name == diagnostic.message.name
+                            && other.location == diagnostic.location
+                    })
+            })
+        });
+    }
+
+    let updated = insert_nolint(contents, &rules_by_line, reason);
+    let diagnostics = lint_source(&updated, path, config)?;
+    Ok((diagnostics, updated))
+}
+
+/// Offset of the end of the line where `diagnostic` ends, before the line
+/// break.
+fn line_end(contents: &str, diagnostic: &Diagnostic) -> usize {
+    let end = usize::from(diagnostic.range.end());
+    let line_end = contents[end..]
+        .find('\n')
+        .map_or(contents.len(), |offset| end + offset);
+    if contents[..line_end].ends_with('\r') {
+        line_end - 1
+    } else {
+        line_end
+    }
+}
+
+/// Add `rules` to the trailing `# nolint: rules` comment of the line ending
+/// at each offset, or add this comment, followed by ` -- reason`, if there is
+/// none.
+///
+/// Lines that have another `# nolint` comment are left as is: appending to it
+/// would make it invalid.
+fn insert_nolint(
+    contents: &str,
+    rules_by_line: &BTreeMap<usize, Vec<&str>>,
+    reason: Option<&str>,
+) -> String {
+    let reason = reason
+        .map(|reason| format!(" -- {reason}"))
+        .unwrap_or_default();
+    let mut output = String::with_capacity(contents.len());
+    let mut last = 0;
+
+    for (&line_end, rules) in rules_by_line {
+        if rules.is_empty() {
+            continue;
+        }
+        let line_start = contents[..line_end].rfind('\n').map_or(0, |i| i + 1);
+        let line = &contents[line_start..line_end];
+
+        let updated = match line.rfind("# nolint") {
+            Some(comment_start) => {
+                let comment = &line[comment_start..];
+                let Some(LintDirective::SkipRules(_)) = parse_comment_directive(comment) else {
+                    continue;
+                };
+                let comment = rules.iter().fold(comment.to_string(), |comment, rule| {
+                    update_existing_nolint(&comment, rule).unwrap_or(comment)
+                });
+                format!("{}{}", &line[..comment_start], comment)
+            }
+            None => format!("{} # nolint: {}{reason}", line.trim_end(), rules.join(", ")),
+        };
+
+        output.push_str(&contents[last..line_start]);
+        output.push_str(&updated);
+        last = line_end;
+    }
+
+    output.push_str(&contents[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils_test::config_for;
+
+    fn nolint_for(contents: &str) -> (Vec<Diagnostic>, String) {
+        let path = Path::new("does/not/exist.R");
+        add_nolint_source(contents, path, &config_for(path, false), None).unwrap()
+    }
+
+    #[test]
+    fn test_add_nolint() {
+        let (diagnostics, updated) = nolint_for("any(is.na(x))\nany(duplicated(y))\n");
+        assert!(diagnostics.is_empty());
+        assert_eq!(
+            updated,
+            "any(is.na(x)) # nolint: any_is_na\nany(duplicated(y)) # nolint: any_duplicated\n"
+        );
+    }
+
+    #[test]
+    fn test_add_nolint_extends_existing_comment() {
+        let (diagnostics, updated) = nolint_for("any(is.na(x)) # nolint: coalesce\n");
+        assert!(diagnostics.is_empty());
+        assert_eq!(updated, "any(is.na(x)) # nolint: coalesce, any_is_na\n");
+    }
+
+    #[test]
+    fn test_add_nolint_with_reason() {
+        let path = Path::new("does/not/exist.R");
+        let mut config = config_for(path, false);
+        config.require_nolint_reason = true;
+        let contents = "any(is.na(x))\nany(duplicated(y)) # nolint: coalesce -- legacy\n";
+
+        // Without a reason, the new comments are reported
+        let (diagnostics, _) = add_nolint_source(contents, path, &config, None).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message.name, NOLINT_WITHOUT_REASON);
+
+        let (diagnostics, updated) =
+            add_nolint_source(contents, path, &config, Some("to fix later")).unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(
+            updated,
+            "any(is.na(x)) # nolint: any_is_na -- to fix later\nany(duplicated(y)) # nolint: coalesce, any_duplicated -- legacy\n"
+        );
+    }
+
+    #[test]
+    fn test_add_nolint_multiline_node() {
+        let (diagnostics, updated) = nolint_for("any(\n  is.na(x)\n)\n");
+        assert!(diagnostics.is_empty());
+        assert_eq!(updated, "any(\n  is.na(x)\n) # nolint: any_is_na\n");
+    }
+
+    #[test]
+    fn test_add_nolint_nested_node() {
+        // The call starts on the line of the comment
+        let (diagnostics, updated) = nolint_for("x <- any(is.na(y))\n");
+        assert!(diagnostics.is_empty());
+        assert_eq!(updated, "x <- any(is.na(y)) # nolint: any_is_na\n");
+    }
+
+    #[test]
+    fn test_add_nolint_keeps_unsuppressed_violations() {
+        // The trailing comment is attached to the assignment, not to the
+        // call, which starts on another line
+        let contents = "x <- any(\n  is.na(y)\n)\n";
+        let (diagnostics, updated) = nolint_for(contents);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message.name, "any_is_na");
+        assert_eq!(updated, contents);
+    }
+
+    #[test]
+    fn test_add_nolint_ignores_parse_errors() {
+        let contents = "any(is.na(x))\nf(\n";
+        let (diagnostics, updated) = nolint_for(contents);
+        assert!(diagnostics.iter().any(|d| d.is_parse_error()));
+        assert_eq!(updated, contents);
+    }
+}
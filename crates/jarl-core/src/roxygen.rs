@@ -0,0 +1,330 @@
+//! Code of the `@examples` of roxygen comments.
+//!
+//! Like the chunks of R Markdown documents (see [crate::document]), each block
+//! of examples is linted as a copy of the file where everything but the code
+//! of the examples is replaced by spaces, including the `#'` of the comments.
+//! Diagnostics are then located on the comment lines.
+
+/// Rd macros whose content is run as examples, e.g. `\dontrun{ ... }`. They are
+/// masked along with their closing brace.
+const RD_SECTIONS: &[&str] = &["\\dontrun{", "\\donttest{", "\\dontshow{", "\\testonly{"];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Examples {
+    /// The file where everything but the code of the examples is masked.
+    pub code: String,
+    /// Start of the roxygen comments, e.g. `#' `.
+    pub prefix: String,
+}
+
+impl Examples {
+    /// Add the roxygen prefix to the lines of `content` (except the first
+    /// one), e.g. for the replacement of a fix that spans several lines.
+    pub fn add_prefix(&self, content: &str) -> String {
+        content.replace('\n', &format!("\n{}", self.prefix))
+    }
+}
+
+/// A line of code of some examples and its offset in the file.
+struct ExampleLine<'a> {
+    offset: usize,
+    code: &'a str,
+}
+
+/// Find the `@examples` and `@examplesIf` of the roxygen comments of
+/// `contents`.
+pub fn find_examples(contents: &str) -> Vec<Examples> {
+    let mut blocks: Vec<(String, Vec<ExampleLine>)> = vec![];
+    let mut current: Option<(String, Vec<ExampleLine>)> = None;
+    let mut offset = 0;
+
+    for line in contents.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let line = line.trim_end_matches(['\n', '\r']);
+        let indent = line.len() - line.trim_start().len();
+        let Some(text) = line[indent..].strip_prefix("#'") else {
+            // The roxygen block ends with the first line that isn't a comment.
+            blocks.extend(current.take());
+            continue;
+        };
+        let prefix_len = indent + 2 + usize::from(text.starts_with(' '));
+        let text = &line[prefix_len..];
+
+        if let Some(tag) = text.trim_start().strip_prefix('@') {
+            // Any tag ends the examples.
+            blocks.extend(current.take());
+
+            let prefix = line[..prefix_len].to_string();
+            if let Some(rest) = tag.strip_prefix("examplesIf") {
+                // The condition on the first line is not part of the examples.
+                if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                    current = Some((prefix, vec![]));
+                }
+            } else if let Some(rest) = tag.strip_prefix("examples") {
+                if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                    let code = rest.trim_start();
+                    let lines = if code.is_empty() {
+                        vec![]
+                    } else {
+                        let offset = line_start + line.len() - code.len();
+                        vec![ExampleLine { offset, code }]
+                    };
+                    current = Some((prefix, lines));
+                }
+            }
+            continue;
+        }
+
+        if let Some((_, lines)) = &mut current {
+            lines.push(ExampleLine { offset: line_start + prefix_len, code: text });
+        }
+    }
+    blocks.extend(current);
+
+    blocks
+        .into_iter()
+        .filter(|(_, lines)| lines.iter().any(|line| !line.code.trim().is_empty()))
+        .map(|(prefix, lines)| Examples { code: mask_examples(contents, &lines), prefix })
+        .collect()
+}
+
+/// Replace everything but `lines` by spaces (except line endings), and turn
+/// the Rd syntax of the examples into R code of the same length.
+fn mask_examples(contents: &str, lines: &[ExampleLine]) -> String {
+    // Replacing each byte by a space keeps the same offsets, including for
+    // non-ASCII characters.
+    let mut masked: Vec<u8> = contents
+        .bytes()
+        .map(|byte| {
+            if matches!(byte, b'\n' | b'\r') {
+                byte
+            } else {
+                b' '
+            }
+        })
+        .collect();
+
+    // Depth of braces at which each `\dontrun{` (or similar) was opened, to
+    // find its closing brace.
+    let mut sections: Vec<usize> = vec![];
+    let mut depth = 0;
+
+    for line in lines {
+        let code = unescape_rd(line.code);
+        let bytes = code.as_bytes();
+        let mut out = bytes.to_vec();
+        let mut quote: Option<u8> = None;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let byte = bytes[i];
+            if let Some(q) = quote {
+                if byte == b'\\' {
+                    i += 1;
+                } else if byte == q {
+                    quote = None;
+                }
+                i += 1;
+                continue;
+            }
+            match byte {
+                b'#' => break,
+                b'"' | b'\'' | b'`' => quote = Some(byte),
+                b'\\' => {
+                    if let Some(section) = RD_SECTIONS.iter().find(|s| code[i..].starts_with(**s)) {
+                        out[i..i + section.len()].fill(b' ');
+                        sections.push(depth);
+                        i += section.len();
+                        continue;
+                    }
+                }
+                b'{' => depth += 1,
+                b'}' => {
+                    if sections.last() == Some(&depth) {
+                        sections.pop();
+                        out[i] = b' ';
+                    } else {
+                        depth = depth.saturating_sub(1);
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+
+        masked[line.offset..line.offset + out.len()].copy_from_slice(&out);
+    }
+
+    // Only ASCII characters were replaced by other ASCII characters.
+    String::from_utf8(masked).expect("masked examples should be valid UTF-8")
+}
+
+/// `%` must be escaped in Rd files, so infix operators are written like
+/// `\%in\%` in the examples. This turns them into ` %in% ` to keep the same
+/// length. Other `\%` (e.g. in strings) become `% `.
+fn unescape_rd(code: &str) -> String {
+    let mut out = String::with_capacity(code.len());
+    let mut rest = code;
+
+    while let Some(start) = rest.find("\\%") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("\\%") {
+            Some(end) if !after[..end].contains(char::is_whitespace) => {
+                out.push_str(" %");
+                out.push_str(&after[..end]);
+                out.push_str("% ");
+                rest = &after[end + 2..];
+            }
+            _ => {
+                out.push_str("% ");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Write the replacement of a fix in Rd, i.e. undo [unescape_rd()]: `replaced`
+/// is the text of the file that the fix replaces, so the infix operators (and
+/// other `%`) copied from it are written back as they were, e.g. ` %in% ` as
+/// `\%in\%`. Any other `%`, e.g. of an operator added by the fix, is escaped.
+pub fn escape_rd(content: &str, replaced: &str) -> String {
+    // Pairs of unescaped and escaped text found in `replaced`.
+    let mut escapes: Vec<(String, String)> = vec![];
+    let mut rest = replaced;
+    while let Some(start) = rest.find("\\%") {
+        let after = &rest[start + 2..];
+        let (unescaped, escaped, next) = match after.find("\\%") {
+            Some(end) if !after[..end].contains(char::is_whitespace) => (
+                format!(" %{}% ", &after[..end]),
+                format!("\\%{}\\%", &after[..end]),
+                &after[end + 2..],
+            ),
+            _ => ("% ".to_string(), "\\%".to_string(), after),
+        };
+        if !escapes.iter().any(|(u, _)| *u == unescaped) {
+            escapes.push((unescaped, escaped));
+        }
+        rest = next;
+    }
+    // Check the operators before the lone `%`.
+    escapes.sort_by_key(|(unescaped, _)| std::cmp::Reverse(unescaped.len()));
+
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    'outer: while let Some(c) = rest.chars().next() {
+        for (unescaped, escaped) in &escapes {
+            if let Some(after) = rest.strip_prefix(unescaped.as_str()) {
+                out.push_str(escaped);
+                rest = after;
+                continue 'outer;
+            }
+        }
+        if c == '%' {
+            out.push_str("\\%");
+        } else {
+            out.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn examples(contents: &str) -> Vec<String> {
+        find_examples(contents)
+            .into_iter()
+            .map(|examples| {
+                examples
+                    .code
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| line.trim_end().to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_examples() {
+        let contents = "\
+#' Title
+#'
+#' @param x A vector.
+#' @examples
+#' f(1)
+#' \\dontrun{
+#' if (TRUE) {
+#'   f(2)
+#' }
+#' }
+#' @export
+f <- function(x) x
+
+#' @examplesIf interactive()
+#' g(1)
+g <- function(x) x
+";
+        assert_eq!(
+            examples(contents),
+            vec!["   f(1)\n   if (TRUE) {\n     f(2)\n   }", "   g(1)"]
+        );
+
+        let masked = &find_examples(contents)[0].code;
+        assert_eq!(masked.len(), contents.len());
+        assert_eq!(masked.lines().count(), contents.lines().count());
+    }
+
+    #[test]
+    fn test_examples_on_tag_line() {
+        assert_eq!(
+            examples("#' @examples f(1)\nf <- 1\n"),
+            vec!["             f(1)"]
+        );
+    }
+
+    #[test]
+    fn test_no_examples() {
+        assert!(find_examples("#' @export\nf <- 1\n# @examples\n").is_empty());
+        assert!(find_examples("#' @examples\n#'\nf <- 1\n").is_empty());
+    }
+
+    #[test]
+    fn test_unescape_rd() {
+        assert_eq!(unescape_rd("x \\%in\\% y"), "x  %in%  y");
+        assert_eq!(unescape_rd("\"50\\%\""), "\"50% \"");
+    }
+
+    #[test]
+    fn test_escape_rd() {
+        // Operators copied from the replaced code are written as they were.
+        assert_eq!(
+            escape_rd("anyNA(x  %in%  y)", "any(is.na(x \\%in\\% y))"),
+            "anyNA(x \\%in\\% y)"
+        );
+        assert_eq!(escape_rd("\"50% \"", "\"50\\%\""), "\"50\\%\"");
+        // Other `%` are escaped.
+        assert_eq!(
+            escape_rd("x %||% y", "if (is.null(x)) y else x"),
+            "x \\%||\\% y"
+        );
+        assert_eq!(escape_rd("anyNA(x)", "any(is.na(x))"), "anyNA(x)");
+    }
+
+    #[test]
+    fn test_add_prefix() {
+        let examples = Examples { code: String::new(), prefix: "#' ".to_string() };
+        assert_eq!(
+            examples.add_prefix("if (x) {\n  y\n}"),
+            "if (x) {\n#'   y\n#' }"
+        );
+    }
+}
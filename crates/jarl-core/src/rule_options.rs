@@ -0,0 +1,117 @@
+//! Options of the rules, set in the tables `[lint.rules.<name>]` of
+//! `jarl.toml`.
+//!
+//! Each configurable rule declares its options next to its implementation.
+//! All their fields are optional so that the options of a `jarl.toml` can be
+//! merged with the ones of the file it extends.
+
+pub use crate::lints::duplicated_arguments::duplicated_arguments::DuplicatedArgumentsOptions;
+pub use crate::lints::implicit_assignment::implicit_assignment::{
+    ImplicitAssignmentContext, ImplicitAssignmentOptions,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RuleOptions {
+    /// # Options of `duplicated_arguments`
+    pub duplicated_arguments: Option<DuplicatedArgumentsOptions>,
+
+    /// # Options of `implicit_assignment`
+    pub implicit_assignment: Option<ImplicitAssignmentOptions>,
+}
+
+impl RuleOptions {
+    /// Combine these options with the options of the file they extend. Options
+    /// are merged field by field, the ones of this file taking precedence.
+    pub fn extend(self, base: RuleOptions) -> RuleOptions {
+        RuleOptions {
+            duplicated_arguments: merge(
+                self.duplicated_arguments,
+                base.duplicated_arguments,
+                DuplicatedArgumentsOptions::extend,
+            ),
+            implicit_assignment: merge(
+                self.implicit_assignment,
+                base.implicit_assignment,
+                ImplicitAssignmentOptions::extend,
+            ),
+        }
+    }
+
+    pub fn duplicated_arguments(&self) -> &DuplicatedArgumentsOptions {
+        const DEFAULT: DuplicatedArgumentsOptions = DuplicatedArgumentsOptions::DEFAULT;
+        self.duplicated_arguments.as_ref().unwrap_or(&DEFAULT)
+    }
+
+    pub fn implicit_assignment(&self) -> &ImplicitAssignmentOptions {
+        const DEFAULT: ImplicitAssignmentOptions = ImplicitAssignmentOptions::DEFAULT;
+        self.implicit_assignment.as_ref().unwrap_or(&DEFAULT)
+    }
+}
+
+fn merge<T>(options: Option<T>, base: Option<T>, extend: fn(T, T) -> T) -> Option<T> {
+    match (options, base) {
+        (Some(options), Some(base)) => Some(extend(options, base)),
+        (options, base) => options.or(base),
+    }
+}
+
+/// Does the function `name` match one of `patterns`? A pattern ending with `*`
+/// matches all functions starting with the rest of the pattern, e.g. `cli_*`
+/// matches `cli_abort`.
+pub(crate) fn matches_function<S: AsRef<str>>(patterns: &[S], name: &str) -> bool {
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.as_ref();
+        match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == pattern,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_function() {
+        assert!(matches_function(&["c", "cli_*"], "c"));
+        assert!(matches_function(&["c", "cli_*"], "cli_abort"));
+        assert!(!matches_function(&["c", "cli_*"], "cat"));
+        assert!(!matches_function::<&str>(&[], "c"));
+    }
+
+    #[test]
+    fn test_extend() {
+        let base = RuleOptions {
+            duplicated_arguments: Some(DuplicatedArgumentsOptions {
+                allowed_functions: Some(vec!["list".into()]),
+            }),
+            implicit_assignment: Some(ImplicitAssignmentOptions {
+                allowed_functions: Some(vec!["local".into()]),
+                allowed_contexts: None,
+            }),
+        };
+        let options = RuleOptions {
+            duplicated_arguments: None,
+            implicit_assignment: Some(ImplicitAssignmentOptions {
+                allowed_functions: None,
+                allowed_contexts: Some(vec![ImplicitAssignmentContext::If]),
+            }),
+        };
+
+        let options = options.extend(base);
+        assert_eq!(
+            options.duplicated_arguments().allowed_functions,
+            Some(vec!["list".into()])
+        );
+        assert_eq!(
+            options.implicit_assignment(),
+            &ImplicitAssignmentOptions {
+                allowed_functions: Some(vec!["local".into()]),
+                allowed_contexts: Some(vec![ImplicitAssignmentContext::If]),
+            }
+        );
+    }
+}
@@ -1,9 +1,15 @@
-#[derive(Debug, Clone, Default)]
+use crate::diagnostic::Severity;
+use crate::lints::RuleId;
+
+#[derive(Debug, Clone, Default, Hash)]
 pub struct Rule {
     pub name: String,
     pub categories: Vec<String>,
     pub fix_status: FixStatus,
     pub minimum_r_version: Option<(u32, u32, u32)>,
+    /// Severity of the diagnostics reported by this rule. It is derived from
+    /// the categories of the rule unless it is set in `jarl.toml`.
+    pub severity: Severity,
 }
 
 impl Rule {
@@ -18,7 +24,7 @@ impl Rule {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
 pub enum FixStatus {
     #[default]
     None,
@@ -26,7 +32,17 @@ pub enum FixStatus {
     Unsafe,
 }
 
-#[derive(Debug, Clone, Default)]
+impl FixStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FixStatus::None => "none",
+            FixStatus::Safe => "safe",
+            FixStatus::Unsafe => "unsafe",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Hash)]
 pub struct RuleTable {
     pub enabled: Vec<Rule>,
 }
@@ -36,7 +52,7 @@ impl RuleTable {
         Self { enabled: Vec::new() }
     }
 
-    /// Enables the given rule.
+    /// Enables the given rule, with the default severity of its categories.
     #[inline]
     pub fn enable(
         &mut self,
@@ -45,11 +61,14 @@ impl RuleTable {
         fix_status: FixStatus,
         minimum_r_version: Option<(u32, u32, u32)>,
     ) {
+        let categories: Vec<String> = categories.split(',').map(|s| s.to_string()).collect();
+        let severity = Severity::from_categories(&categories);
         self.enabled.push(Rule {
             name: rule.to_string(),
-            categories: categories.split(',').map(|s| s.to_string()).collect(),
+            categories,
             fix_status,
             minimum_r_version,
+            severity,
         });
     }
 
@@ -57,6 +76,14 @@ impl RuleTable {
     pub fn iter(&self) -> std::slice::Iter<'_, Rule> {
         self.enabled.iter()
     }
+
+    /// Returns the set of rules in this table. Names that don't correspond
+    /// to a known rule are ignored.
+    pub fn to_rule_set(&self) -> RuleSet {
+        self.iter()
+            .filter_map(|rule| RuleId::from_name(&rule.name))
+            .collect()
+    }
 }
 
 impl FromIterator<Rule> for RuleTable {
@@ -65,3 +92,124 @@ impl FromIterator<Rule> for RuleTable {
         RuleTable { enabled }
     }
 }
+
+const RULE_SET_WORDS: usize = RuleId::COUNT.div_ceil(64);
+
+/// A set of rules stored as a bitset, where the bit at position `i` is set if
+/// the rule `RuleId::ALL[i]` is in the set.
+///
+/// This is what the `Checker` uses to know whether a rule is enabled, since
+/// this check happens for every rule on every node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RuleSet([u64; RULE_SET_WORDS]);
+
+impl RuleSet {
+    /// Creates a new empty rule set.
+    pub const fn empty() -> Self {
+        Self([0; RULE_SET_WORDS])
+    }
+
+    /// Adds the given rule to the set.
+    #[inline]
+    pub fn insert(&mut self, rule: RuleId) {
+        let index = rule as usize;
+        self.0[index / 64] |= 1 << (index % 64);
+    }
+
+    /// Removes the given rule from the set.
+    #[inline]
+    pub fn remove(&mut self, rule: RuleId) {
+        let index = rule as usize;
+        self.0[index / 64] &= !(1 << (index % 64));
+    }
+
+    /// Returns `true` if the given rule is in the set.
+    #[inline]
+    pub const fn contains(&self, rule: RuleId) -> bool {
+        let index = rule as usize;
+        self.0[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|word| *word == 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Returns an iterator over the rules in the set, in the order of
+    /// `RuleId::ALL`.
+    pub fn iter(&self) -> impl Iterator<Item = RuleId> + '_ {
+        RuleId::ALL
+            .iter()
+            .copied()
+            .filter(|rule| self.contains(*rule))
+    }
+}
+
+impl FromIterator<RuleId> for RuleSet {
+    fn from_iter<I: IntoIterator<Item = RuleId>>(iter: I) -> Self {
+        let mut set = RuleSet::empty();
+        for rule in iter {
+            set.insert(rule);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lints::all_rules_and_safety;
+
+    #[test]
+    fn test_rule_set() {
+        let mut set = RuleSet::empty();
+        assert!(set.is_empty());
+
+        set.insert(RuleId::AnyIsNa);
+        set.insert(RuleId::WhichGrepl);
+        assert!(set.contains(RuleId::AnyIsNa));
+        assert!(set.contains(RuleId::WhichGrepl));
+        assert!(!set.contains(RuleId::AllEqual));
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![RuleId::AnyIsNa, RuleId::WhichGrepl]
+        );
+
+        set.remove(RuleId::AnyIsNa);
+        assert!(!set.contains(RuleId::AnyIsNa));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_rule_table_to_rule_set() {
+        let all_rules = all_rules_and_safety();
+        let set = all_rules.to_rule_set();
+        assert_eq!(set.len(), RuleId::COUNT);
+        assert!(RuleId::ALL.iter().all(|rule| set.contains(*rule)));
+
+        let mut table = RuleTable::empty();
+        table.enable("any_is_na", "PERF", FixStatus::Safe, None);
+        table.enable("not_a_rule", "PERF", FixStatus::Safe, None);
+        let set = table.to_rule_set();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![RuleId::AnyIsNa]);
+    }
+
+    #[test]
+    fn test_default_severity() {
+        let all_rules = all_rules_and_safety();
+        let severity = |name: &str| {
+            all_rules
+                .iter()
+                .find(|rule| rule.name == name)
+                .unwrap()
+                .severity
+        };
+        assert_eq!(severity("equals_na"), Severity::Error);
+        assert_eq!(severity("any_is_na"), Severity::Warning);
+        assert_eq!(severity("class_equals"), Severity::Warning);
+    }
+}
@@ -0,0 +1,527 @@
+//! Scope and binding analysis
+//!
+//! This module builds a [SemanticModel] for a file: it records the function
+//! scopes, the bindings (objects created by `<-`, `=`, `->`, `<<-`, `assign()`,
+//! function parameters and for-loop variables) and the references to those
+//! bindings.
+//!
+//! This is a lexical approximation of R's scoping rules. R is much more
+//! dynamic than that (e.g. `eval()`, `local()`, `with()`, `attach()`), so rules
+//! should use this model to *avoid* false positives rather than to prove that
+//! some code is wrong.
+
+use air_r_syntax::{
+    AnyRExpression, RBinaryExpression, RCall, RExtractExpression, RForStatement,
+    RFunctionDefinition, RIdentifier, RSyntaxKind, RSyntaxNode,
+};
+use biome_rowan::{AstNode, TextRange, TextSize};
+use std::collections::HashSet;
+
+use crate::utils::{get_arg_by_name, get_arg_by_name_then_position, get_function_name};
+
+pub type ScopeId = usize;
+pub type BindingId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    /// The top-level of the file.
+    Global,
+    /// The body (and parameters) of a `function()` or `\()` definition.
+    Function,
+}
+
+#[derive(Debug)]
+pub struct Scope {
+    pub kind: ScopeKind,
+    /// The enclosing scope, `None` only for the global scope.
+    pub parent: Option<ScopeId>,
+    /// The range of the node that creates this scope.
+    pub range: TextRange,
+    /// Bindings created directly in this scope.
+    pub bindings: Vec<BindingId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    /// `x <- 1`, `x = 1` or `1 -> x`.
+    Assignment,
+    /// `x <<- 1` or `1 ->> x`. Those are stored in the parent scope.
+    SuperAssignment,
+    /// `assign("x", 1)`.
+    AssignCall,
+    /// `function(x) ...`.
+    Parameter,
+    /// `for (x in y) ...`.
+    ForLoopVariable,
+}
+
+#[derive(Debug)]
+pub struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    /// The range of the symbol (or string for `assign()`) creating the binding.
+    pub range: TextRange,
+    pub scope: ScopeId,
+    /// Position from which the binding can be referenced in its own scope.
+    /// For instance, in `x <- x + 1`, the `x` on the right-hand side doesn't
+    /// refer to the new binding.
+    pub visible_from: TextSize,
+}
+
+#[derive(Debug)]
+pub struct Reference {
+    pub name: String,
+    pub range: TextRange,
+    /// The scope in which the reference appears.
+    pub scope: ScopeId,
+    /// The binding this reference resolves to, `None` if the symbol isn't
+    /// defined in this file (e.g. a function from another package).
+    pub binding: Option<BindingId>,
+}
+
+/// The scopes, bindings and references of a file.
+#[derive(Debug)]
+pub struct SemanticModel {
+    scopes: Vec<Scope>,
+    bindings: Vec<Binding>,
+    references: Vec<Reference>,
+    /// Ranges of the symbols that create a binding. Those are not references.
+    binding_sites: HashSet<TextRange>,
+}
+
+impl SemanticModel {
+    /// Build the semantic model from the root syntax node
+    pub fn from_node(root: &RSyntaxNode) -> Self {
+        let mut model = Self {
+            scopes: vec![Scope {
+                kind: ScopeKind::Global,
+                parent: None,
+                range: root.text_range(),
+                bindings: vec![],
+            }],
+            bindings: vec![],
+            references: vec![],
+            binding_sites: HashSet::new(),
+        };
+
+        model.visit(root, 0);
+
+        // Bindings of enclosing scopes can be created after a function
+        // definition (but before it is called), so references can only be
+        // resolved once all bindings are known.
+        let resolved: Vec<Option<BindingId>> = model
+            .references
+            .iter()
+            .map(|r| model.resolve_in_scope(&r.name, r.range, r.scope))
+            .collect();
+        for (reference, binding) in model.references.iter_mut().zip(resolved) {
+            reference.binding = binding;
+        }
+
+        model
+    }
+
+    fn visit(&mut self, node: &RSyntaxNode, scope: ScopeId) {
+        if let Some(function) = RFunctionDefinition::cast(node.clone()) {
+            let new_scope = self.add_scope(ScopeKind::Function, scope, node.text_trimmed_range());
+            self.visit_function_parameters(&function, new_scope);
+            for child in node.children() {
+                self.visit(&child, new_scope);
+            }
+            return;
+        }
+
+        if let Some(binary) = RBinaryExpression::cast(node.clone()) {
+            self.visit_assignment(&binary, scope);
+        } else if let Some(for_loop) = RForStatement::cast(node.clone()) {
+            self.visit_for_loop(&for_loop, scope);
+        } else if let Some(call) = RCall::cast(node.clone()) {
+            self.visit_assign_call(&call, scope);
+        } else if let Some(identifier) = RIdentifier::cast(node.clone()) {
+            self.visit_identifier(&identifier, scope);
+        }
+
+        for child in node.children() {
+            self.visit(&child, scope);
+        }
+    }
+
+    fn visit_function_parameters(&mut self, function: &RFunctionDefinition, scope: ScopeId) {
+        let Ok(parameters) = function.parameters() else {
+            return;
+        };
+        let visible_from = function.syntax().text_trimmed_range().start();
+        for parameter in parameters.items().into_iter().flatten() {
+            // The name of the parameter is always the first child, the
+            // optional default value comes after. `...` and `..1` are not
+            // identifiers so they are ignored.
+            if let Some(name) = parameter.syntax().first_child().and_then(RIdentifier::cast) {
+                self.add_binding(&name, BindingKind::Parameter, scope, visible_from);
+            }
+        }
+    }
+
+    fn visit_assignment(&mut self, binary: &RBinaryExpression, scope: ScopeId) {
+        let Ok(operator) = binary.operator() else {
+            return;
+        };
+        let target = match operator.kind() {
+            RSyntaxKind::ASSIGN | RSyntaxKind::EQUAL | RSyntaxKind::SUPER_ASSIGN => binary.left(),
+            RSyntaxKind::ASSIGN_RIGHT | RSyntaxKind::SUPER_ASSIGN_RIGHT => binary.right(),
+            _ => return,
+        };
+        let Ok(AnyRExpression::RIdentifier(target)) = target else {
+            return;
+        };
+
+        let (kind, scope) = match operator.kind() {
+            RSyntaxKind::SUPER_ASSIGN | RSyntaxKind::SUPER_ASSIGN_RIGHT => (
+                BindingKind::SuperAssignment,
+                self.scopes[scope].parent.unwrap_or(scope),
+            ),
+            _ => (BindingKind::Assignment, scope),
+        };
+        let visible_from = binary.syntax().text_trimmed_range().end();
+        self.add_binding(&target, kind, scope, visible_from);
+    }
+
+    fn visit_for_loop(&mut self, for_loop: &RForStatement, scope: ScopeId) {
+        let (Ok(variable), Ok(body)) = (for_loop.variable(), for_loop.body()) else {
+            return;
+        };
+        // `for (x in x)`: the `x` in the sequence refers to the previous `x`.
+        let visible_from = body.syntax().text_trimmed_range().start();
+        self.add_binding(&variable, BindingKind::ForLoopVariable, scope, visible_from);
+    }
+
+    fn visit_assign_call(&mut self, call: &RCall, scope: ScopeId) {
+        let Ok(function) = call.function() else {
+            return;
+        };
+        if get_function_name(function) != "assign" {
+            return;
+        }
+        let Ok(arguments) = call.arguments() else {
+            return;
+        };
+        let arguments = arguments.items();
+
+        // `assign("x", 1, envir = e)` doesn't create a binding in this scope.
+        if get_arg_by_name(&arguments, "envir").is_some()
+            || get_arg_by_name(&arguments, "pos").is_some()
+        {
+            return;
+        }
+
+        let Some(name) = get_arg_by_name_then_position(&arguments, "x", 1)
+            .and_then(|arg| arg.value())
+            .and_then(|value| value.as_any_r_value().cloned())
+            .and_then(|value| value.as_r_string_value().cloned())
+        else {
+            return;
+        };
+
+        let text = name.syntax().text_trimmed().to_string();
+        let unquoted = text.trim_matches(|c| c == '"' || c == '\'');
+        let range = name.syntax().text_trimmed_range();
+        let binding = Binding {
+            name: unquoted.to_string(),
+            kind: BindingKind::AssignCall,
+            range,
+            scope,
+            visible_from: call.syntax().text_trimmed_range().end(),
+        };
+        self.push_binding(binding);
+    }
+
+    fn visit_identifier(&mut self, identifier: &RIdentifier, scope: ScopeId) {
+        let range = identifier.syntax().text_trimmed_range();
+        if self.binding_sites.contains(&range) || !is_reference(identifier) {
+            return;
+        }
+        let Ok(token) = identifier.name_token() else {
+            return;
+        };
+        self.references.push(Reference {
+            name: token.text_trimmed().to_string(),
+            range,
+            scope,
+            binding: None,
+        });
+    }
+
+    fn add_scope(&mut self, kind: ScopeKind, parent: ScopeId, range: TextRange) -> ScopeId {
+        self.scopes.push(Scope {
+            kind,
+            parent: Some(parent),
+            range,
+            bindings: vec![],
+        });
+        self.scopes.len() - 1
+    }
+
+    fn add_binding(
+        &mut self,
+        identifier: &RIdentifier,
+        kind: BindingKind,
+        scope: ScopeId,
+        visible_from: TextSize,
+    ) {
+        let Ok(token) = identifier.name_token() else {
+            return;
+        };
+        let range = identifier.syntax().text_trimmed_range();
+        self.binding_sites.insert(range);
+        self.push_binding(Binding {
+            name: token.text_trimmed().to_string(),
+            kind,
+            range,
+            scope,
+            visible_from,
+        });
+    }
+
+    fn push_binding(&mut self, binding: Binding) {
+        let scope = binding.scope;
+        self.bindings.push(binding);
+        let id = self.bindings.len() - 1;
+        self.scopes[scope].bindings.push(id);
+    }
+
+    fn resolve_in_scope(&self, name: &str, range: TextRange, scope: ScopeId) -> Option<BindingId> {
+        let mut current = Some(scope);
+        let mut is_own_scope = true;
+
+        while let Some(scope_id) = current {
+            let scope = &self.scopes[scope_id];
+            // In the scope where the reference appears, the binding must come
+            // before the reference. In enclosing scopes, the function can be
+            // called after the binding is created so any binding is valid.
+            let binding = scope
+                .bindings
+                .iter()
+                .rev()
+                .copied()
+                .filter(|id| self.bindings[*id].name == name)
+                .find(|id| !is_own_scope || self.bindings[*id].visible_from <= range.start());
+
+            if binding.is_some() {
+                return binding;
+            }
+
+            current = scope.parent;
+            is_own_scope = false;
+        }
+
+        None
+    }
+
+    /// All scopes of the file. The first one is always the global scope.
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    /// All bindings of the file, in the order in which they appear.
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    /// All references of the file, in the order in which they appear.
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    pub fn scope(&self, id: ScopeId) -> &Scope {
+        &self.scopes[id]
+    }
+
+    pub fn binding(&self, id: BindingId) -> &Binding {
+        &self.bindings[id]
+    }
+
+    /// Return the innermost scope containing `range`.
+    pub fn scope_at(&self, range: TextRange) -> ScopeId {
+        // Scopes are stored in pre-order, so the last one containing the range
+        // is the innermost.
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, scope)| scope.range.contains_range(range))
+            .map(|(id, _)| id)
+            .unwrap_or(0)
+    }
+
+    /// Does the symbol at `range` create a binding (e.g. `x` in `x <- 1`)?
+    pub fn is_binding_site(&self, range: TextRange) -> bool {
+        self.binding_sites.contains(&range)
+    }
+
+    /// Find the binding that the symbol `name` located at `range` refers to.
+    ///
+    /// Returns `None` if `name` isn't defined in this file before `range`.
+    pub fn resolve(&self, name: &str, range: TextRange) -> Option<&Binding> {
+        self.resolve_in_scope(name, range, self.scope_at(range))
+            .map(|id| &self.bindings[id])
+    }
+
+    /// All references that appear in `range`.
+    pub fn references_in(&self, range: TextRange) -> impl Iterator<Item = &Reference> {
+        self.references
+            .iter()
+            .filter(move |reference| range.contains_range(reference.range))
+    }
+
+    /// Is the binding referenced anywhere in the file?
+    pub fn is_used(&self, id: BindingId) -> bool {
+        self.references.iter().any(|r| r.binding == Some(id))
+    }
+}
+
+/// Some symbols are not evaluated in the current scope so they are not
+/// references to a binding, e.g. `y` in `x$y`, `pkg::fun`, or the argument
+/// name in `f(x = 1)`.
+fn is_reference(identifier: &RIdentifier) -> bool {
+    let Some(parent) = identifier.syntax().parent() else {
+        return true;
+    };
+
+    match parent.kind() {
+        RSyntaxKind::R_NAMESPACE_EXPRESSION | RSyntaxKind::R_ARGUMENT_NAME_CLAUSE => false,
+        _ => {
+            if let Some(extract) = RExtractExpression::cast(parent)
+                && let Ok(operator) = extract.operator()
+            {
+                let start = identifier.syntax().text_trimmed_range().start();
+                start < operator.text_trimmed_range().start()
+            } else {
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use air_r_parser::{RParserOptions, parse};
+
+    fn model(code: &str) -> SemanticModel {
+        let parsed = parse(code, RParserOptions::default());
+        SemanticModel::from_node(&parsed.syntax())
+    }
+
+    fn range_of(code: &str, needle: &str, nth: usize) -> TextRange {
+        let start = code.match_indices(needle).nth(nth).unwrap().0 as u32;
+        TextRange::at(start.into(), (needle.len() as u32).into())
+    }
+
+    #[test]
+    fn test_bindings() {
+        let code = r#"
+x <- 1
+y = 2
+3 -> z
+assign("a", 4)
+f <- function(b, c = 1, ...) {
+  for (i in 1:2) {
+    d <<- i
+  }
+}
+"#;
+        let model = model(code);
+        let names: Vec<(&str, BindingKind)> = model
+            .bindings()
+            .iter()
+            .map(|b| (b.name.as_str(), b.kind))
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                ("x", BindingKind::Assignment),
+                ("y", BindingKind::Assignment),
+                ("z", BindingKind::Assignment),
+                ("a", BindingKind::AssignCall),
+                ("f", BindingKind::Assignment),
+                ("b", BindingKind::Parameter),
+                ("c", BindingKind::Parameter),
+                ("i", BindingKind::ForLoopVariable),
+                ("d", BindingKind::SuperAssignment),
+            ]
+        );
+
+        // `d <<- i` is stored in the global scope.
+        let d = model.bindings().iter().find(|b| b.name == "d").unwrap();
+        assert_eq!(model.scope(d.scope).kind, ScopeKind::Global);
+    }
+
+    #[test]
+    fn test_scopes() {
+        let code = "f <- function(x) {\n  g <- \\(y) x + y\n}";
+        let model = model(code);
+
+        assert_eq!(model.scopes().len(), 3);
+        let y = range_of(code, "y", 1);
+        let inner = model.scope_at(y);
+        assert_eq!(model.scope(inner).kind, ScopeKind::Function);
+        assert_eq!(model.scope(inner).parent, Some(1));
+        assert_eq!(model.scope_at(range_of(code, "f", 0)), 0);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let code = "x <- 1\nf <- function(x) x\ny <- x\nz <- w";
+        let model = model(code);
+
+        // `x` in the body of `f` refers to the parameter.
+        let binding = model.resolve("x", range_of(code, "x", 2)).unwrap();
+        assert_eq!(binding.kind, BindingKind::Parameter);
+
+        // `x` at the top-level refers to the first assignment.
+        let binding = model.resolve("x", range_of(code, "x", 3)).unwrap();
+        assert_eq!(binding.kind, BindingKind::Assignment);
+        assert_eq!(binding.range, range_of(code, "x", 0));
+
+        // `w` is never defined.
+        assert!(model.resolve("w", range_of(code, "w", 0)).is_none());
+    }
+
+    #[test]
+    fn test_binding_visibility() {
+        // The `x` on the right-hand side doesn't refer to the new binding.
+        let code = "x <- x + 1";
+        let model = model(code);
+        assert!(model.resolve("x", range_of(code, "x", 1)).is_none());
+
+        // Same for the sequence of a for loop.
+        let code = "for (x in x) x";
+        let model = model(code);
+        assert!(model.resolve("x", range_of(code, "x", 1)).is_none());
+        assert!(model.resolve("x", range_of(code, "x", 2)).is_some());
+
+        // Functions can use bindings created after their definition.
+        let code = "f <- function() y\ny <- 1";
+        let model = model(code);
+        assert!(model.resolve("y", range_of(code, "y", 0)).is_some());
+    }
+
+    #[test]
+    fn test_references() {
+        let code = "x <- 1\nfoo(x = x, df$x, pkg::x)\nunused <- 2";
+        let model = model(code);
+
+        let references: Vec<&str> = model.references().iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(references, vec!["foo", "x", "df"]);
+
+        let x = model.bindings().iter().position(|b| b.name == "x").unwrap();
+        let unused = model
+            .bindings()
+            .iter()
+            .position(|b| b.name == "unused")
+            .unwrap();
+        assert!(model.is_used(x));
+        assert!(!model.is_used(unused));
+        assert!(model.is_binding_site(range_of(code, "x", 0)));
+    }
+}
@@ -4,10 +4,39 @@
 //
 // MIT License - Posit PBC
 
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::description::CONFIG_PREFIX;
+use crate::rule_options::RuleOptions;
+
 /// Resolved configuration settings used within jarl
 #[derive(Debug, Default)]
 pub struct Settings {
     pub linter: LinterSettings,
+    pub source: SettingsSource,
+}
+
+/// The file where the [Settings] are defined.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsSource {
+    #[default]
+    JarlToml,
+    /// The `Config/jarl/*` fields of `DESCRIPTION`.
+    Description,
+}
+
+impl SettingsSource {
+    /// Name a field of the settings in messages, e.g. "field `select` in
+    /// 'jarl.toml'".
+    pub fn field(&self, name: &str) -> String {
+        match self {
+            SettingsSource::JarlToml => format!("field `{name}` in 'jarl.toml'"),
+            SettingsSource::Description => {
+                format!("field `{CONFIG_PREFIX}{name}` in DESCRIPTION")
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -19,6 +48,17 @@ pub struct LinterSettings {
     pub default_exclude: Option<bool>,
     pub fixable: Option<Vec<String>>,
     pub unfixable: Option<Vec<String>>,
+    pub severity: Option<BTreeMap<String, String>>,
+    pub per_file_ignores: Option<BTreeMap<String, Vec<String>>>,
+    /// Directory of the patterns of `per_file_ignores` that come from an
+    /// extended file.
+    pub per_file_ignores_roots: BTreeMap<String, PathBuf>,
+    pub rules: Option<RuleOptions>,
+    pub document_extensions: Option<Vec<String>>,
+    pub roxygen_examples: Option<bool>,
+    pub require_nolint_reason: Option<bool>,
+    pub min_r_version: Option<String>,
+    pub baseline: Option<String>,
 }
 
 impl Default for LinterSettings {
@@ -34,6 +74,15 @@ impl Default for LinterSettings {
             default_exclude: None,
             fixable: None,
             unfixable: None,
+            severity: None,
+            per_file_ignores: None,
+            per_file_ignores_roots: BTreeMap::new(),
+            rules: None,
+            document_extensions: None,
+            roxygen_examples: None,
+            require_nolint_reason: None,
+            min_r_version: None,
+            baseline: None,
         }
     }
 }
@@ -7,8 +7,25 @@ use air_r_syntax::{RLanguage, RSyntaxNode};
 use biome_formatter::comments::{CommentStyle, Comments};
 use biome_rowan::{SyntaxTriviaPieceComments, TextRange};
 use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+use crate::diagnostic::{Applicability, Diagnostic, Edit, Fix, Suppression, ViolationData};
+use crate::directive::{
+    LintDirective, parse_comment_directive, parse_special_skip_file, split_nolint_reason,
+};
+use crate::lints::RuleId;
+use crate::rule_table::RuleSet;
+
+/// Name of the diagnostics that report `# nolint` comments that don't
+/// suppress anything, with `--report-unused-nolint`. This is not a rule: it
+/// can't be selected, ignored or suppressed.
+pub const UNUSED_NOLINT: &str = "unused_nolint";
+
+/// Name of the diagnostics that report `# nolint` comments that don't give a
+/// reason, with `require-nolint-reason = true`. Like [UNUSED_NOLINT], this is
+/// not a rule.
+pub const NOLINT_WITHOUT_REASON: &str = "nolint_without_reason";
 
-use crate::directive::{LintDirective, parse_comment_directive, parse_special_skip_file};
 /// Comment style for R that identifies nolint directives
 #[derive(Default)]
 pub struct RCommentStyle;
@@ -40,79 +57,169 @@ impl CommentStyle for RCommentStyle {
 /// Represents a region where linting should be skipped
 #[derive(Debug, Clone)]
 struct SkipRegion {
-    /// The range of text covered by this skip region
-    range: TextRange,
+    /// The lines covered by this skip region, from the line of the
+    /// `# nolint start` comment to the line of the `# nolint end` comment
+    lines: RangeInclusive<usize>,
     /// Rules to skip (None means skip all rules)
     rules: Option<HashSet<String>>,
+    /// The `# nolint start` comment
+    start: DirectiveComment,
+    /// The range of the `# nolint end` comment
+    end: TextRange,
+}
+
+/// A comment that contains a nolint directive
+#[derive(Debug, Clone)]
+struct DirectiveComment {
+    range: TextRange,
+    text: String,
+}
+
+/// A directive that applies to a line, like in `lintr`: `# nolint` at the end
+/// of a line of code, or `# nolint next` on the line before
+#[derive(Debug, Clone)]
+struct LineDirective {
+    /// The line where the violations are skipped (0-based)
+    line: usize,
+    /// Rules to skip (None means skip all rules)
+    rules: Option<Vec<String>>,
+    /// The range of the comment
+    comment: TextRange,
 }
 
 /// Tracks which nodes should skip linting based on comments
 #[derive(Debug)]
 pub struct SuppressionManager {
     comments: Comments<RLanguage>,
+    /// Positions of the new line characters, to find the line of a node
+    new_lines: Vec<usize>,
     /// Regions defined by nolint start/end blocks
     skip_regions: Vec<SkipRegion>,
+    /// Directives that apply to the violations that start on a line
+    line_directives: Vec<LineDirective>,
+    /// `# nolint`, `# nolint: rules` and `# nolint next` comments, which
+    /// apply to the node they are attached to or to a line
+    node_directives: Vec<DirectiveComment>,
+    /// `# nolint start` without `# nolint end` and the other way round, which
+    /// don't suppress anything
+    unmatched_directives: Vec<DirectiveComment>,
 }
 
 impl SuppressionManager {
     /// Create a new suppression manager from the root syntax node
     pub fn from_node(root: &RSyntaxNode) -> Self {
         let comments = Comments::from_node(root, &RCommentStyle, None);
-        let skip_regions = Self::build_skip_regions(root, &comments);
-        Self { comments, skip_regions }
+        let text = root.to_string();
+        let new_lines = text.match_indices('\n').map(|x| x.0).collect();
+        let mut manager = Self {
+            comments,
+            new_lines,
+            skip_regions: Vec::new(),
+            line_directives: Vec::new(),
+            node_directives: Vec::new(),
+            unmatched_directives: Vec::new(),
+        };
+        manager.build_directives(root, &text);
+        manager
+    }
+
+    /// Line (0-based) of an offset in the file
+    fn line_of(&self, offset: usize) -> usize {
+        self.new_lines
+            .partition_point(|&new_line| new_line < offset)
     }
 
-    /// Build skip regions from nolint start/end directives
-    fn build_skip_regions(root: &RSyntaxNode, comments: &Comments<RLanguage>) -> Vec<SkipRegion> {
-        let mut regions = Vec::new();
-        let mut stack: Vec<(TextRange, Option<Vec<String>>)> = Vec::new();
+    /// Build skip regions from nolint start/end directives, and collect the
+    /// other directives
+    fn build_directives(&mut self, root: &RSyntaxNode, text: &str) {
+        let mut stack: Vec<(DirectiveComment, Option<Vec<String>>)> = Vec::new();
 
-        Self::collect_start_end_directives(root, comments, &mut stack, &mut regions);
+        self.collect_directives(root, text, &mut stack);
 
-        regions
+        // Blocks that are never closed don't skip anything
+        self.unmatched_directives
+            .extend(stack.into_iter().map(|(start, _)| start));
     }
 
-    fn collect_start_end_directives(
+    fn collect_directives(
+        &mut self,
         node: &RSyntaxNode,
-        comments: &Comments<RLanguage>,
-        stack: &mut Vec<(TextRange, Option<Vec<String>>)>,
-        regions: &mut Vec<SkipRegion>,
+        text: &str,
+        stack: &mut Vec<(DirectiveComment, Option<Vec<String>>)>,
     ) {
         // Check all comment types for this node
-        for comment in comments
+        let directives = self
+            .comments
             .leading_comments(node)
             .iter()
-            .chain(comments.trailing_comments(node))
-            .chain(comments.dangling_comments(node))
-        {
-            let text = comment.piece().text();
-            let range = comment.piece().text_range();
-
-            match parse_comment_directive(text) {
-                Some(LintDirective::SkipStart) => {
+            .chain(self.comments.trailing_comments(node))
+            .chain(self.comments.dangling_comments(node))
+            .filter_map(|comment| {
+                let text = comment.piece().text();
+                let directive = parse_comment_directive(text)?;
+                let comment = DirectiveComment {
+                    range: comment.piece().text_range(),
+                    text: text.to_string(),
+                };
+                Some((comment, directive))
+            })
+            .collect::<Vec<_>>();
+
+        for (comment, directive) in directives {
+            let start = usize::from(comment.range.start());
+            let line = self.line_of(start);
+            match directive {
+                LintDirective::SkipStart => {
                     // Start skipping all rules
-                    stack.push((range, None));
+                    stack.push((comment, None));
                 }
-                Some(LintDirective::SkipStartRules(rules)) => {
+                LintDirective::SkipStartRules(rules) => {
                     // Start skipping specific rules
-                    stack.push((range, Some(rules)));
+                    stack.push((comment, Some(rules)));
                 }
-                Some(LintDirective::SkipEnd) => {
+                LintDirective::SkipEnd => {
                     // End the most recent skip block
-                    if let Some((start_range, rules)) = stack.pop() {
-                        regions.push(SkipRegion {
-                            range: TextRange::new(start_range.start(), range.end()),
-                            rules: rules.map(|r| r.into_iter().collect()),
+                    match stack.pop() {
+                        Some((start, rules)) => {
+                            let start_line = self.line_of(usize::from(start.range.start()));
+                            self.skip_regions.push(SkipRegion {
+                                lines: start_line..=line,
+                                rules: rules.map(|r| r.into_iter().collect()),
+                                start,
+                                end: comment.range,
+                            });
+                        }
+                        None => self.unmatched_directives.push(comment),
+                    }
+                }
+                LintDirective::Skip | LintDirective::SkipRules(_) => {
+                    // Like in `lintr`, a comment at the end of a line of code
+                    // also applies to the whole line
+                    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+                    if !text[line_start..start].trim().is_empty() {
+                        self.line_directives.push(LineDirective {
+                            line,
+                            rules: directive_rules(directive),
+                            comment: comment.range,
                         });
                     }
+                    self.node_directives.push(comment);
                 }
-                _ => {}
+                LintDirective::SkipNext | LintDirective::SkipNextRules(_) => {
+                    self.line_directives.push(LineDirective {
+                        line: line + 1,
+                        rules: directive_rules(directive),
+                        comment: comment.range,
+                    });
+                    self.node_directives.push(comment);
+                }
+                LintDirective::SkipFile => {}
             }
         }
 
         // Recursively process children
         for child in node.children() {
-            Self::collect_start_end_directives(&child, comments, stack, regions);
+            self.collect_directives(&child, text, stack);
         }
     }
 
@@ -171,105 +278,382 @@ impl SuppressionManager {
         false
     }
 
-    /// Check if a node should skip all lints or specific rules
+    /// Find the comment that makes a specific rule skipped for this node
+    ///
+    /// The rule is skipped if:
+    /// - the node starts on a line of a `# nolint start`/`# nolint end` block;
+    /// - the first directive attached to the node skips it;
+    /// - the node starts on a line that ends with a directive that skips it,
+    ///   or that follows a `# nolint next` directive that skips it.
     ///
-    /// Returns:
-    /// - `Some(None)` if all lints should be skipped
-    /// - `Some(Some(rules))` if specific rules should be skipped
-    /// - `None` if linting should proceed normally
-    pub fn check_suppression(&self, node: &RSyntaxNode) -> Option<Option<HashSet<String>>> {
-        // Check if node is in a skip region
-        let node_range = node.text_trimmed_range();
-        let mut region_suppression: Option<Option<HashSet<String>>> = None;
+    /// Returns the range of the comment, which is the `# nolint start` comment
+    /// for start/end blocks, or `None` if the rule is not skipped.
+    pub fn find_suppression(&self, node: &RSyntaxNode, rule_name: &str) -> Option<TextRange> {
+        let skips_rule = |rules: Option<&Vec<String>>| {
+            rules.is_none_or(|rules| rules.iter().any(|rule| rule == rule_name))
+        };
+
+        // Check skip regions first
+        let node_line = self.line_of(usize::from(node.text_trimmed_range().start()));
         for region in &self.skip_regions {
-            if region.range.contains_range(node_range) {
-                region_suppression = Some(region.rules.clone());
-                break;
+            if region.lines.contains(&node_line)
+                && region
+                    .rules
+                    .as_ref()
+                    .is_none_or(|rules| rules.contains(rule_name))
+            {
+                return Some(region.start.range);
             }
         }
 
-        // Check for node-level directives
-        // Helper function to check comments for nolint directives
-        let check_comments = |comments: &[biome_formatter::comments::SourceComment<
-            RLanguage,
-        >]|
-         -> Option<Option<HashSet<String>>> {
-            for comment in comments {
-                let text = comment.piece().text();
-
-                match parse_comment_directive(text) {
-                    Some(directive) => {
-                        return match directive {
-                            LintDirective::Skip => Some(None), // Skip all
-                            LintDirective::SkipRules(rules) => {
-                                Some(Some(rules.into_iter().collect()))
-                            }
-                            LintDirective::SkipFile => {
-                                // SkipFile directives are handled at file level via should_skip_file()
-                                // If we encounter one here, treat it as skip all for this node
-                                Some(None)
-                            }
-                            LintDirective::SkipStart
-                            | LintDirective::SkipStartRules(_)
-                            | LintDirective::SkipEnd => {
-                                // Start/End directives are handled by skip regions
-                                // Continue checking other comments for node-level directives
-                                continue;
-                            }
-                        };
-                    }
-                    None => {
-                        // Not a directive, continue checking other comments
+        // Then check node-level suppression. Only the first directive attached
+        // to the node is used.
+        let node_directive = self
+            .comments
+            .leading_comments(node)
+            .iter()
+            .chain(self.comments.trailing_comments(node))
+            .chain(self.comments.dangling_comments(node))
+            .find_map(|comment| {
+                match parse_comment_directive(comment.piece().text())? {
+                    LintDirective::Skip => Some((comment.piece().text_range(), None)),
+                    LintDirective::SkipRules(rules) => {
+                        Some((comment.piece().text_range(), Some(rules)))
                     }
+                    LintDirective::SkipFile => Some((comment.piece().text_range(), None)),
+                    // Start/End directives are handled by skip regions, and
+                    // next directives by line directives
+                    LintDirective::SkipStart
+                    | LintDirective::SkipStartRules(_)
+                    | LintDirective::SkipEnd
+                    | LintDirective::SkipNext
+                    | LintDirective::SkipNextRules(_) => None,
                 }
-            }
-            None
-        };
+            });
+        if let Some((range, rules)) = node_directive
+            && skips_rule(rules.as_ref())
+        {
+            return Some(range);
+        }
 
-        // Check leading comments
-        let leading = self.comments.leading_comments(node);
-        if let Some(result) = check_comments(leading) {
-            return Some(result);
+        // Finally check the directives of the line where the node starts
+        self.line_directives
+            .iter()
+            .find(|directive| directive.line == node_line && skips_rule(directive.rules.as_ref()))
+            .map(|directive| directive.comment)
+    }
+
+    /// The comment at `range`, as returned by [Self::find_suppression()], and
+    /// the reason it gives
+    pub fn suppression(&self, range: TextRange) -> Option<Suppression> {
+        let comment = self
+            .node_directives
+            .iter()
+            .chain(self.skip_regions.iter().map(|region| &region.start))
+            .find(|comment| comment.range == range)?;
+        let (_, reason) = split_nolint_reason(&comment.text);
+        Some(Suppression {
+            comment: comment.text.clone(),
+            reason: reason.map(str::to_string),
+        })
+    }
+
+    /// Report the directives that don't give a reason after `--`
+    ///
+    /// `# nolint end` comments are not reported: the reason goes on the
+    /// `# nolint start` comment.
+    pub fn directives_without_reason(&self) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = self
+            .node_directives
+            .iter()
+            .chain(self.skip_regions.iter().map(|region| &region.start))
+            .chain(&self.unmatched_directives)
+            .filter(|comment| {
+                !matches!(
+                    parse_comment_directive(&comment.text),
+                    Some(LintDirective::SkipEnd)
+                )
+            })
+            .filter(|comment| split_nolint_reason(&comment.text).1.is_none())
+            .map(|comment| {
+                Diagnostic::new(
+                    ViolationData::new(
+                        NOLINT_WITHOUT_REASON.to_string(),
+                        format!(
+                            "This `{}` comment doesn't explain why the violations are suppressed.",
+                            directive_head(&comment.text)
+                        ),
+                        Some(
+                            "Add a reason after `--`, e.g. `# nolint: rule -- reason`".to_string(),
+                        ),
+                    ),
+                    comment.range,
+                    Fix::empty(),
+                )
+            })
+            .collect();
+
+        diagnostics.sort_by_key(|diagnostic| diagnostic.range.start());
+        diagnostics
+    }
+
+    /// Report the directives that don't suppress anything
+    ///
+    /// `used` contains the comments that suppressed a violation, as returned
+    /// by [Self::find_suppression()], and the rule of the violation. Only the
+    /// rules in `rules` are considered: a directive that names a rule that is
+    /// not enabled isn't reported.
+    ///
+    /// This reports:
+    /// - rules that are unknown, listed several times, or that don't have any
+    ///   violation to suppress;
+    /// - `# nolint` and `# nolint start` that don't suppress any violation;
+    /// - `# nolint start` without `# nolint end` and the other way round.
+    ///
+    /// Their fix removes the useless rules, or the whole comment. It is unsafe
+    /// if the comment lists unknown rules, since they may be lintr linters
+    /// that Jarl doesn't implement.
+    pub fn unused_directives(
+        &self,
+        used: &HashSet<(TextRange, RuleId)>,
+        rules: &RuleSet,
+        contents: &str,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for comment in &self.node_directives {
+            diagnostics.extend(unused_directive(comment, None, used, rules, contents));
         }
 
-        // Check trailing comments
-        let trailing = self.comments.trailing_comments(node);
-        if let Some(result) = check_comments(trailing) {
-            return Some(result);
+        for region in &self.skip_regions {
+            diagnostics.extend(unused_directive(
+                &region.start,
+                Some(region.end),
+                used,
+                rules,
+                contents,
+            ));
         }
 
-        // Check dangling comments
-        let dangling = self.comments.dangling_comments(node);
-        if let Some(result) = check_comments(dangling) {
-            return Some(result);
+        for comment in &self.unmatched_directives {
+            let body = match parse_comment_directive(&comment.text) {
+                Some(LintDirective::SkipEnd) => {
+                    "This `# nolint end` comment has no matching `# nolint start`."
+                }
+                _ => {
+                    "This `# nolint start` comment has no matching `# nolint end`, so it doesn't suppress any violation."
+                }
+            };
+            diagnostics.push(unused_nolint_diagnostic(
+                body.to_string(),
+                "Remove the comment",
+                comment.range,
+                vec![removal_edit(contents, comment.range)],
+                Applicability::Safe,
+            ));
         }
 
-        // If no node-level directive, return region suppression
-        region_suppression
+        diagnostics.sort_by_key(|diagnostic| diagnostic.range.start());
+        diagnostics
     }
+}
 
-    /// Check if a specific rule should be skipped for this node
-    pub fn should_skip_rule(&self, node: &RSyntaxNode, rule_name: &str) -> bool {
-        // Check skip regions first
-        let node_range = node.text_trimmed_range();
-        for region in &self.skip_regions {
-            if region.range.contains_range(node_range) {
-                match &region.rules {
-                    None => return true, // Skip all
-                    Some(rules) => {
-                        if rules.contains(rule_name) {
-                            return true;
-                        }
-                    }
-                }
+/// Report `comment` if it doesn't suppress anything, or if some of its rules
+/// don't. `end` is the range of the `# nolint end` comment for start/end
+/// blocks, which is removed with the `# nolint start` comment.
+fn unused_directive(
+    comment: &DirectiveComment,
+    end: Option<TextRange>,
+    used: &HashSet<(TextRange, RuleId)>,
+    rules: &RuleSet,
+    contents: &str,
+) -> Option<Diagnostic> {
+    let name = format!("`{}`", directive_head(&comment.text));
+    // The reason is kept when the useless rules are removed
+    let (text, reason) = split_nolint_reason(&comment.text);
+    let reason = reason
+        .map(|reason| format!(" -- {reason}"))
+        .unwrap_or_default();
+    let remove_comment = || {
+        let mut edits = vec![removal_edit(contents, comment.range)];
+        edits.extend(end.map(|end| removal_edit(contents, end)));
+        edits
+    };
+
+    // Generic directives are useful if they suppress any rule
+    let Some((head, listed)) = text.split_once(':') else {
+        if used.iter().any(|(range, _)| *range == comment.range) {
+            return None;
+        }
+        return Some(unused_nolint_diagnostic(
+            format!("This {name} comment doesn't suppress any violation."),
+            "Remove the comment",
+            comment.range,
+            remove_comment(),
+            Applicability::Safe,
+        ));
+    };
+
+    let mut kept = Vec::new();
+    let mut unknown = Vec::new();
+    let mut unused = Vec::new();
+    let mut duplicated = Vec::new();
+    let mut seen = HashSet::new();
+
+    // lintr compatibility, see `parse_comment_directive()`
+    let listed = listed.trim();
+    let (listed, period) = match listed.strip_suffix('.') {
+        Some(listed) => (listed, "."),
+        None => (listed, ""),
+    };
+
+    for listed in listed.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        // lintr compatibility, see `parse_comment_directive()`
+        let rule_name = listed.trim_end_matches("_linter");
+        if !seen.insert(rule_name) {
+            duplicated.push(listed);
+            continue;
+        }
+        match RuleId::from_name(rule_name) {
+            None => unknown.push(listed),
+            Some(rule) if rules.contains(rule) && !used.contains(&(comment.range, rule)) => {
+                unused.push(listed)
             }
+            Some(_) => kept.push(listed),
         }
+    }
+
+    let mut problems = Vec::new();
+    if !unknown.is_empty() {
+        problems.push(format!("unknown {}", list_rules(&unknown)));
+    }
+    if !unused.is_empty() {
+        problems.push(format!(
+            "no violation of {} to suppress",
+            list_rules(&unused)
+        ));
+    }
+    if !duplicated.is_empty() {
+        problems.push(format!("{} listed several times", list_rules(&duplicated)));
+    }
+    if problems.is_empty() {
+        return None;
+    }
+    let problems = problems.join("; ");
+
+    // Unknown rules may be lintr linters that Jarl doesn't implement, so
+    // removing them could make lintr report violations again.
+    let applicability = if unknown.is_empty() {
+        Applicability::Safe
+    } else {
+        Applicability::Unsafe
+    };
+
+    if kept.is_empty() {
+        return Some(unused_nolint_diagnostic(
+            format!("This {name} comment doesn't suppress any violation: {problems}."),
+            "Remove the comment",
+            comment.range,
+            remove_comment(),
+            applicability,
+        ));
+    }
+
+    let edit = Edit {
+        content: format!(
+            "{}: {}{}{}",
+            head.trim_end(),
+            kept.join(", "),
+            period,
+            reason
+        ),
+        start: usize::from(comment.range.start()),
+        end: usize::from(comment.range.end()),
+    };
+    Some(unused_nolint_diagnostic(
+        format!("Some rules of this {name} comment don't suppress any violation: {problems}."),
+        "Remove the useless rules",
+        comment.range,
+        vec![edit],
+        applicability,
+    ))
+}
+
+/// The directive of a comment without its rules and reason, e.g.
+/// `# nolint start` for `# nolint start: any_is_na -- reason`
+fn directive_head(text: &str) -> &str {
+    let (text, _) = split_nolint_reason(text);
+    match text.split_once(':') {
+        Some((head, _)) => head.trim(),
+        None => text.trim(),
+    }
+}
+
+/// Rules skipped by a directive (None means skip all rules)
+fn directive_rules(directive: LintDirective) -> Option<Vec<String>> {
+    match directive {
+        LintDirective::SkipRules(rules)
+        | LintDirective::SkipStartRules(rules)
+        | LintDirective::SkipNextRules(rules) => Some(rules),
+        _ => None,
+    }
+}
+
+/// Format rule names as "rule `a`" or "rules `a`, `b`".
+fn list_rules(rules: &[&str]) -> String {
+    let names = rules
+        .iter()
+        .map(|rule| format!("`{rule}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if rules.len() == 1 {
+        format!("rule {names}")
+    } else {
+        format!("rules {names}")
+    }
+}
 
-        // Then check node-level suppression
-        match self.check_suppression(node) {
-            Some(None) => true, // Skip all
-            Some(Some(rules)) => rules.contains(rule_name),
-            None => false,
+fn unused_nolint_diagnostic(
+    body: String,
+    suggestion: &str,
+    range: TextRange,
+    edits: Vec<Edit>,
+    applicability: Applicability,
+) -> Diagnostic {
+    Diagnostic::new(
+        ViolationData::new(
+            UNUSED_NOLINT.to_string(),
+            body,
+            Some(suggestion.to_string()),
+        ),
+        range,
+        Fix { edits, applicability, to_skip: false },
+    )
+}
+
+/// Edit that removes the comment at `range`. A comment that is alone on its
+/// line is removed with the line, and a trailing comment is removed with the
+/// spaces before it.
+fn removal_edit(contents: &str, range: TextRange) -> Edit {
+    let start = usize::from(range.start());
+    let end = usize::from(range.end());
+    let line_start = contents[..start].rfind('\n').map_or(0, |i| i + 1);
+    let before = &contents[line_start..start];
+
+    if before.trim().is_empty() {
+        let line_end = contents[end..]
+            .find('\n')
+            .map_or(contents.len(), |i| end + i + 1);
+        Edit {
+            content: String::new(),
+            start: line_start,
+            end: line_end,
+        }
+    } else {
+        Edit {
+            content: String::new(),
+            start: line_start + before.trim_end().len(),
+            end,
         }
     }
 }
@@ -293,9 +677,8 @@ any(is.na(x))
         let expressions: Vec<_> = parsed.tree().expressions().into_iter().collect();
         let first_expr = expressions[0].syntax();
 
-        assert_eq!(manager.check_suppression(first_expr), Some(None));
-        assert!(manager.should_skip_rule(first_expr, "any_is_na"));
-        assert!(manager.should_skip_rule(first_expr, "coalesce"));
+        assert!(manager.find_suppression(first_expr, "any_is_na").is_some());
+        assert!(manager.find_suppression(first_expr, "coalesce").is_some());
     }
 
     #[test]
@@ -311,12 +694,9 @@ any(is.na(x))
         let expressions: Vec<_> = parsed.tree().expressions().into_iter().collect();
         let first_expr = expressions[0].syntax();
 
-        let suppressed = manager.check_suppression(first_expr);
-        assert!(matches!(suppressed, Some(Some(_))));
-
-        assert!(manager.should_skip_rule(first_expr, "any_is_na"));
-        assert!(manager.should_skip_rule(first_expr, "coalesce"));
-        assert!(!manager.should_skip_rule(first_expr, "scalar_in"));
+        assert!(manager.find_suppression(first_expr, "any_is_na").is_some());
+        assert!(manager.find_suppression(first_expr, "coalesce").is_some());
+        assert_eq!(manager.find_suppression(first_expr, "scalar_in"), None);
     }
 
     #[test]
@@ -332,8 +712,7 @@ any(is.na(x))
         let expressions: Vec<_> = parsed.tree().expressions().into_iter().collect();
         let first_expr = expressions[0].syntax();
 
-        assert_eq!(manager.check_suppression(first_expr), None);
-        assert!(!manager.should_skip_rule(first_expr, "any_is_na"));
+        assert_eq!(manager.find_suppression(first_expr, "any_is_na"), None);
     }
 
     #[test]
@@ -346,9 +725,8 @@ any(is.na(x))
         let expressions: Vec<_> = parsed.tree().expressions().into_iter().collect();
         let first_expr = expressions[0].syntax();
 
-        assert_eq!(manager.check_suppression(first_expr), Some(None));
-        assert!(manager.should_skip_rule(first_expr, "any_is_na"));
-        assert!(manager.should_skip_rule(first_expr, "coalesce"));
+        assert!(manager.find_suppression(first_expr, "any_is_na").is_some());
+        assert!(manager.find_suppression(first_expr, "coalesce").is_some());
     }
 
     #[test]
@@ -361,12 +739,206 @@ any(is.na(x))
         let expressions: Vec<_> = parsed.tree().expressions().into_iter().collect();
         let first_expr = expressions[0].syntax();
 
-        let suppressed = manager.check_suppression(first_expr);
-        assert!(matches!(suppressed, Some(Some(_))));
+        assert!(manager.find_suppression(first_expr, "any_is_na").is_some());
+        assert!(manager.find_suppression(first_expr, "coalesce").is_some());
+        assert_eq!(manager.find_suppression(first_expr, "scalar_in"), None);
+    }
+
+    #[test]
+    fn test_find_suppression() {
+        let code = r#"
+# nolint: any_is_na
+any(is.na(x))
+# nolint start
+any(is.na(y))
+# nolint end
+"#;
+
+        let parsed = parse(code, RParserOptions::default());
+        let manager = SuppressionManager::from_node(&parsed.syntax());
+
+        let expressions: Vec<_> = parsed.tree().expressions().into_iter().collect();
+        let first_expr = expressions[0].syntax();
+        let second_expr = expressions[1].syntax();
+
+        let nolint = code.find("# nolint: any_is_na").unwrap();
+        let start = code.find("# nolint start").unwrap();
+        assert_eq!(
+            manager
+                .find_suppression(first_expr, "any_is_na")
+                .map(|range| usize::from(range.start())),
+            Some(nolint)
+        );
+        assert_eq!(manager.find_suppression(first_expr, "coalesce"), None);
+        assert_eq!(
+            manager
+                .find_suppression(second_expr, "coalesce")
+                .map(|range| usize::from(range.start())),
+            Some(start)
+        );
+    }
+
+    fn calls(root: &RSyntaxNode) -> Vec<RSyntaxNode> {
+        root.descendants()
+            .filter(|node| node.kind() == air_r_syntax::RSyntaxKind::R_CALL)
+            .collect()
+    }
+
+    #[test]
+    fn test_trailing_skip_whole_line() {
+        // The comment is attached to the assignment but also applies to the
+        // call that starts on the same line
+        let code = "x <- any(is.na(y)) # nolint: any_is_na\n";
 
-        assert!(manager.should_skip_rule(first_expr, "any_is_na"));
-        assert!(manager.should_skip_rule(first_expr, "coalesce"));
-        assert!(!manager.should_skip_rule(first_expr, "scalar_in"));
+        let parsed = parse(code, RParserOptions::default());
+        let manager = SuppressionManager::from_node(&parsed.syntax());
+        let call = calls(&parsed.syntax()).remove(0);
+
+        assert!(manager.find_suppression(&call, "any_is_na").is_some());
+        assert_eq!(manager.find_suppression(&call, "coalesce"), None);
+
+        // A comment on its own line only applies to the next node
+        let code = "x <- 1\n# nolint\ny <- any(is.na(y))\n";
+
+        let parsed = parse(code, RParserOptions::default());
+        let manager = SuppressionManager::from_node(&parsed.syntax());
+        let call = calls(&parsed.syntax()).remove(0);
+
+        assert_eq!(manager.find_suppression(&call, "any_is_na"), None);
+    }
+
+    #[test]
+    fn test_skip_next_line() {
+        let code = r#"
+# nolint next: any_is_na_linter.
+x <- any(is.na(y))
+z <- any(is.na(y))
+"#;
+
+        let parsed = parse(code, RParserOptions::default());
+        let manager = SuppressionManager::from_node(&parsed.syntax());
+        let calls = calls(&parsed.syntax());
+
+        // `any()` and `is.na()` on the next line
+        assert!(manager.find_suppression(&calls[0], "any_is_na").is_some());
+        assert!(manager.find_suppression(&calls[1], "any_is_na").is_some());
+        assert_eq!(manager.find_suppression(&calls[0], "coalesce"), None);
+        assert_eq!(manager.find_suppression(&calls[2], "any_is_na"), None);
+    }
+
+    #[test]
+    fn test_skip_region_lines() {
+        // The region applies to the code that starts on its lines, like in
+        // lintr, even if the call ends after it
+        let code = r#"
+# nolint start
+x <- any(
+  # nolint end
+  is.na(y)
+)
+z <- any(is.na(y))
+"#;
+
+        let parsed = parse(code, RParserOptions::default());
+        let manager = SuppressionManager::from_node(&parsed.syntax());
+        let calls = calls(&parsed.syntax());
+
+        assert!(manager.find_suppression(&calls[0], "any_is_na").is_some());
+        assert_eq!(manager.find_suppression(&calls[1], "any_is_na"), None);
+        assert_eq!(manager.find_suppression(&calls[2], "any_is_na"), None);
+    }
+
+    #[test]
+    fn test_suppression_reason() {
+        let code = r#"
+# nolint: any_is_na -- NA are removed upstream
+any(is.na(x))
+# nolint start
+any(is.na(y))
+# nolint end
+"#;
+
+        let parsed = parse(code, RParserOptions::default());
+        let manager = SuppressionManager::from_node(&parsed.syntax());
+        let calls = calls(&parsed.syntax());
+
+        let suppression = manager
+            .find_suppression(&calls[0], "any_is_na")
+            .and_then(|range| manager.suppression(range))
+            .unwrap();
+        assert_eq!(
+            suppression.comment,
+            "# nolint: any_is_na -- NA are removed upstream"
+        );
+        assert_eq!(
+            suppression.reason.as_deref(),
+            Some("NA are removed upstream")
+        );
+
+        let suppression = manager
+            .find_suppression(&calls[2], "any_is_na")
+            .and_then(|range| manager.suppression(range))
+            .unwrap();
+        assert_eq!(suppression.comment, "# nolint start");
+        assert_eq!(suppression.reason, None);
+    }
+
+    #[test]
+    fn test_directives_without_reason() {
+        let code = r#"
+x <- 1 # nolint -- generated
+y <- 2 # nolint: any_is_na
+# nolint start: coalesce
+z <- 3
+# nolint end
+# nolint next --
+w <- 4
+"#;
+
+        let parsed = parse(code, RParserOptions::default());
+        let manager = SuppressionManager::from_node(&parsed.syntax());
+
+        let diagnostics = manager.directives_without_reason();
+        let reported: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| usize::from(diagnostic.range.start()))
+            .collect();
+        assert_eq!(
+            reported,
+            vec![
+                code.find("# nolint: any_is_na").unwrap(),
+                code.find("# nolint start").unwrap(),
+                code.find("# nolint next").unwrap(),
+            ]
+        );
+        assert_eq!(
+            diagnostics[1].message.body,
+            "This `# nolint start` comment doesn't explain why the violations are suppressed."
+        );
+    }
+
+    #[test]
+    fn test_removal_edit() {
+        let code = "x <- 1 # nolint
+  # nolint
+y <- 2
+";
+
+        let trailing = code.find("# nolint").unwrap();
+        let range = TextRange::new(
+            (trailing as u32).into(),
+            ((trailing + "# nolint".len()) as u32).into(),
+        );
+        let edit = removal_edit(code, range);
+        assert_eq!((edit.start, edit.end), (6, 15));
+
+        let own_line = code.rfind("# nolint").unwrap();
+        let range = TextRange::new(
+            (own_line as u32).into(),
+            ((own_line + "# nolint".len()) as u32).into(),
+        );
+        let edit = removal_edit(code, range);
+        assert_eq!((edit.start, edit.end), (16, 27));
     }
 
     #[test]
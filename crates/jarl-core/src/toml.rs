@@ -6,6 +6,7 @@
 //
 // MIT License - Posit PBC
 
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fs;
@@ -13,13 +14,20 @@ use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::config::parse_r_version;
+use crate::fs::{DEFAULT_DOCUMENT_EXTENSIONS, is_document_extension};
+use crate::rule_options::RuleOptions;
 use crate::settings::LinterSettings;
 use crate::settings::Settings;
+use crate::settings::SettingsSource;
 
 #[derive(Debug)]
 pub enum ParseTomlError {
     Read(PathBuf, io::Error),
     Deserialize(PathBuf, toml::de::Error),
+    /// The files that `extend` each other, the last one being the first one
+    /// that appears twice.
+    ExtendCycle(Vec<PathBuf>),
 }
 
 impl std::error::Error for ParseTomlError {}
@@ -35,15 +43,65 @@ impl Display for ParseTomlError {
             Self::Deserialize(path, err) => {
                 write!(f, "Failed to parse {path}:\n{err}", path = path.display())
             }
+            Self::ExtendCycle(paths) => {
+                let paths = paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>();
+                write!(
+                    f,
+                    "Circular `extend` between configuration files:\n{}",
+                    paths.join("\n-> ")
+                )
+            }
         }
     }
 }
 
 pub fn parse_jarl_toml(path: &Path) -> Result<TomlOptions, ParseTomlError> {
-    let toml = fs::read_to_string(path).unwrap();
+    let toml =
+        fs::read_to_string(path).map_err(|err| ParseTomlError::Read(path.to_path_buf(), err))?;
     toml::from_str(&toml).map_err(|err| ParseTomlError::Deserialize(path.to_path_buf(), err))
 }
 
+/// Parse a `jarl.toml` and the configuration files that it extends, see
+/// [GlobalTomlOptions::extend].
+pub fn load_jarl_toml(path: &Path) -> Result<TomlOptions, ParseTomlError> {
+    load_jarl_toml_impl(path, &mut vec![])
+}
+
+fn load_jarl_toml_impl(
+    path: &Path,
+    chain: &mut Vec<PathBuf>,
+) -> Result<TomlOptions, ParseTomlError> {
+    let canonical_path =
+        fs::canonicalize(path).map_err(|err| ParseTomlError::Read(path.to_path_buf(), err))?;
+    let is_cycle = chain.contains(&canonical_path);
+    chain.push(canonical_path);
+    if is_cycle {
+        return Err(ParseTomlError::ExtendCycle(std::mem::take(chain)));
+    }
+
+    let options = parse_jarl_toml(path)?;
+
+    let Some(extend) = &options.global.extend else {
+        return Ok(options);
+    };
+
+    // The path is relative to the file that contains `extend`.
+    let base_path = path.parent().unwrap_or(Path::new("")).join(extend);
+    let mut base = load_jarl_toml_impl(&base_path, chain)?;
+
+    // Paths in the extended file are relative to its own directory, while
+    // those of this file are relative to the directory of `jarl.toml`.
+    let base_directory = crate::fs::normalize_path(base_path.parent().unwrap_or(Path::new("")));
+    if let Some(lint) = &mut base.lint {
+        lint.resolve_paths(&base_directory);
+    }
+
+    Ok(options.extend(base))
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Default, serde::Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields, rename_all = "kebab-case")]
@@ -56,7 +114,25 @@ pub struct TomlOptions {
 #[derive(Clone, Debug, PartialEq, Eq, Default, serde::Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields, rename_all = "kebab-case")]
-pub struct GlobalTomlOptions {}
+pub struct GlobalTomlOptions {
+    /// # Configuration file to extend
+    ///
+    /// Path to another configuration file whose options are used as defaults,
+    /// e.g. `"../jarl.toml"`. The path is relative to the directory of this
+    /// configuration file. The extended file can itself extend another one.
+    ///
+    /// Options set in this file replace those of the extended file, except:
+    /// - `ignore`, `unfixable` and `exclude`, which are added to those of the
+    ///   extended file;
+    /// - `severity` and `per-file-ignores`, which are merged key by key;
+    /// - `rules`, which are merged option by option.
+    ///
+    /// Patterns in `exclude` are always relative to the directory of this
+    /// configuration file, even if they come from the extended file. The
+    /// `baseline` and the patterns of `per-file-ignores` are relative to the
+    /// directory of the file that defines them.
+    pub extend: Option<String>,
+}
 
 #[derive(Clone, Debug, PartialEq, Eq, Default, serde::Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -71,13 +147,13 @@ pub struct LinterTomlOptions {
     /// of `grepv` since the eponymous function was introduced in R 4.5.0.
     ///
     /// There are three ways to inform `jarl` about the minimum version used in
-    /// the project:
+    /// the project, by order of precedence:
     /// 1. pass the argument `--min-r-version` in the CLI, e.g.,
     ///    `jarl --min-r-version 4.3`;
-    /// 2. if the project is an R package, then `jarl` looks for mentions of a
+    /// 2. specify `min-r-version` in `jarl.toml`;
+    /// 3. if the project is an R package, then `jarl` looks for mentions of a
     ///    minimum R version in the `Depends` field sometimes present in the
-    ///    `DESCRIPTION` file.
-    /// 3. specify `min-r-version` in `jarl.toml`.
+    ///    `DESCRIPTION` file at the root of the package.
     pub select: Option<Vec<String>>,
 
     /// # Rules to ignore
@@ -159,6 +235,188 @@ pub struct LinterTomlOptions {
     /// This can be either `"<-"` or `"="`. Both are valid in R, so this
     /// option is useful to ensure consistency in a project.
     pub assignment: Option<String>,
+
+    /// # Severity of rule violations
+    ///
+    /// A table mapping rule names or groups of rules (e.g. `"PERF"`) to the
+    /// severity of their violations, one of `"error"`, `"warning"`, `"info"`
+    /// or `"hint"`. By default, rules in the `CORR` group are errors and all
+    /// other rules are warnings.
+    ///
+    /// ```toml
+    /// [lint.severity]
+    /// READ = "info"
+    /// any_is_na = "error"
+    /// ```
+    ///
+    /// The severity of a rule name takes precedence over the severity of its
+    /// groups.
+    pub severity: Option<BTreeMap<String, String>>,
+
+    /// # Rules to ignore in some files
+    ///
+    /// A table mapping patterns of files to the rules (or groups of rules) that
+    /// are ignored in those files, e.g. to allow `browser()` in tests:
+    ///
+    /// ```toml
+    /// [lint.per-file-ignores]
+    /// "tests/**" = ["browser", "implicit_assignment"]
+    /// "data-raw/*.R" = ["PERF"]
+    /// ```
+    ///
+    /// Patterns follow the same rules as `exclude`: they use the syntax of
+    /// [.gitignore](https://git-scm.com/docs/gitignore) and are resolved
+    /// relative to the directory that contains `jarl.toml`. Patterns that
+    /// come from an extended file are relative to the directory of this file.
+    pub per_file_ignores: Option<BTreeMap<String, Vec<String>>>,
+
+    /// Directory that the patterns of `per_file_ignores` that come from an
+    /// extended file are relative to. Other patterns are relative to the
+    /// directory of `jarl.toml`.
+    #[serde(skip)]
+    pub per_file_ignores_roots: BTreeMap<String, PathBuf>,
+
+    /// # Options of the rules
+    ///
+    /// One table per rule, named after the rule, e.g.:
+    ///
+    /// ```toml
+    /// [lint.rules.duplicated_arguments]
+    /// allowed-functions = ["c", "list2"]
+    /// ```
+    ///
+    /// See the documentation of each rule for the options it accepts.
+    pub rules: Option<RuleOptions>,
+
+    /// # Documents whose R chunks are checked
+    ///
+    /// Extensions of the documents in which jarl checks the R code chunks,
+    /// among `"Rmd"` (R Markdown), `"qmd"` (Quarto) and `"Rnw"` (Sweave). By
+    /// default, all of them are checked. Use `[]` to only check R scripts.
+    ///
+    /// Chunks of other languages (e.g. `{python}`) and chunks that are not
+    /// evaluated (e.g. `{r, eval = FALSE}`) are never checked.
+    pub document_extensions: Option<Vec<String>>,
+
+    /// # Whether to check the examples of roxygen comments
+    ///
+    /// If `true`, jarl also checks the code in the `@examples` and
+    /// `@examplesIf` sections of roxygen comments (`#'`), including the code
+    /// in `\dontrun{}` and `\donttest{}`. Diagnostics are reported on the
+    /// comment lines, and fixes keep the `#'` at the start of each line.
+    /// Defaults to `false`.
+    pub roxygen_examples: Option<bool>,
+
+    /// # Whether `# nolint` comments must give a reason
+    ///
+    /// If `true`, jarl reports the `# nolint` comments that don't explain why
+    /// the violations are suppressed. The reason follows `--`, e.g.
+    /// `# nolint: any_is_na -- NA are removed upstream`. `# nolint end`
+    /// comments don't need one. Defaults to `false`.
+    pub require_nolint_reason: Option<bool>,
+
+    /// # Minimum R version of the project
+    ///
+    /// The oldest R version that the project supports, e.g. `"4.3"` or
+    /// `"4.3.1"`. Rules that require a more recent version of R, such as
+    /// `grepv` (R 4.5.0), are disabled.
+    ///
+    /// This takes precedence over the `Depends` field of `DESCRIPTION`, but
+    /// `--min-r-version` in the CLI takes precedence over this. If the minimum
+    /// R version is unknown, all rules that require a specific version of R
+    /// are disabled.
+    pub min_r_version: Option<String>,
+
+    /// # Baseline of known violations
+    ///
+    /// Path to a file, relative to the directory of `jarl.toml`, that lists
+    /// violations that should not be reported, e.g. `"jarl-baseline.json"`.
+    /// If it comes from an extended file, it is relative to the directory of
+    /// this file.
+    /// This is useful to only report new violations in a project that has
+    /// many of them. Create or update this file with
+    /// `jarl check . --generate-baseline jarl-baseline.json`.
+    ///
+    /// `--baseline` in the CLI takes precedence over this.
+    pub baseline: Option<String>,
+}
+
+impl LinterTomlOptions {
+    /// Make the paths of these options, which are relative to `directory`,
+    /// usable from another configuration file that extends this one.
+    fn resolve_paths(&mut self, directory: &Path) {
+        if let Some(baseline) = &mut self.baseline {
+            *baseline = directory.join(&*baseline).to_string_lossy().into_owned();
+        }
+        // Patterns can't be made absolute, so we keep track of their directory
+        // instead. Those of the files that this one extends are already known.
+        if let Some(patterns) = &self.per_file_ignores {
+            for pattern in patterns.keys() {
+                self.per_file_ignores_roots
+                    .entry(pattern.clone())
+                    .or_insert_with(|| directory.to_path_buf());
+            }
+        }
+    }
+
+    /// Combine these options with the options of the file they extend, see
+    /// [GlobalTomlOptions::extend].
+    fn extend(self, base: LinterTomlOptions) -> LinterTomlOptions {
+        LinterTomlOptions {
+            select: self.select.or(base.select),
+            ignore: concat_options(base.ignore, self.ignore),
+            fixable: self.fixable.or(base.fixable),
+            unfixable: concat_options(base.unfixable, self.unfixable),
+            exclude: concat_options(base.exclude, self.exclude),
+            default_exclude: self.default_exclude.or(base.default_exclude),
+            assignment: self.assignment.or(base.assignment),
+            severity: merge_tables(base.severity, self.severity),
+            per_file_ignores_roots: base
+                .per_file_ignores_roots
+                .into_iter()
+                .filter(|(pattern, _)| {
+                    !self
+                        .per_file_ignores
+                        .as_ref()
+                        .is_some_and(|patterns| patterns.contains_key(pattern))
+                })
+                .chain(self.per_file_ignores_roots)
+                .collect(),
+            per_file_ignores: merge_tables(base.per_file_ignores, self.per_file_ignores),
+            rules: match (self.rules, base.rules) {
+                (Some(rules), Some(base)) => Some(rules.extend(base)),
+                (rules, base) => rules.or(base),
+            },
+            document_extensions: self.document_extensions.or(base.document_extensions),
+            roxygen_examples: self.roxygen_examples.or(base.roxygen_examples),
+            require_nolint_reason: self.require_nolint_reason.or(base.require_nolint_reason),
+            min_r_version: self.min_r_version.or(base.min_r_version),
+            baseline: self.baseline.or(base.baseline),
+        }
+    }
+}
+
+fn merge_tables<T>(
+    base: Option<BTreeMap<String, T>>,
+    other: Option<BTreeMap<String, T>>,
+) -> Option<BTreeMap<String, T>> {
+    match (base, other) {
+        (Some(mut base), Some(other)) => {
+            base.extend(other);
+            Some(base)
+        }
+        (base, other) => other.or(base),
+    }
+}
+
+fn concat_options(base: Option<Vec<String>>, other: Option<Vec<String>>) -> Option<Vec<String>> {
+    match (base, other) {
+        (Some(mut base), Some(other)) => {
+            base.extend(other);
+            Some(base)
+        }
+        (base, other) => other.or(base),
+    }
 }
 
 /// Return the path to the `jarl.toml` or `.jarl.toml` file in a given directory.
@@ -190,9 +448,41 @@ pub fn find_jarl_toml<P: AsRef<Path>>(path: P) -> Option<PathBuf> {
 }
 
 impl TomlOptions {
-    pub fn into_settings(self, _root: &Path) -> anyhow::Result<Settings> {
+    /// Combine these options with the options of the file they extend.
+    fn extend(self, base: TomlOptions) -> TomlOptions {
+        let lint = match (self.lint, base.lint) {
+            (Some(lint), Some(base)) => Some(lint.extend(base)),
+            (lint, base) => lint.or(base),
+        };
+        TomlOptions { global: self.global, lint }
+    }
+
+    /// Resolve these options into [Settings]. `source` is the file where they
+    /// are defined, to name it in error messages.
+    pub fn into_settings(self, _root: &Path, source: SettingsSource) -> anyhow::Result<Settings> {
         let linter = self.lint.unwrap_or_default();
 
+        if let Some(extensions) = &linter.document_extensions
+            && let Some(invalid) = extensions
+                .iter()
+                .find(|extension| !is_document_extension(extension, DEFAULT_DOCUMENT_EXTENSIONS))
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid value `{invalid}` in {}. Expected one of: {}.",
+                source.field("document-extensions"),
+                DEFAULT_DOCUMENT_EXTENSIONS.join(", ")
+            ));
+        }
+
+        if let Some(version) = &linter.min_r_version
+            && let Err(err) = parse_r_version(version.clone())
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid value `{version}` in {}: {err}.",
+                source.field("min-r-version")
+            ));
+        }
+
         let linter = LinterSettings {
             select: linter.select,
             ignore: linter.ignore,
@@ -201,8 +491,203 @@ impl TomlOptions {
             default_exclude: linter.default_exclude,
             fixable: linter.fixable,
             unfixable: linter.unfixable,
+            severity: linter.severity,
+            per_file_ignores: linter.per_file_ignores,
+            per_file_ignores_roots: linter.per_file_ignores_roots,
+            rules: linter.rules,
+            document_extensions: linter.document_extensions,
+            roxygen_examples: linter.roxygen_examples,
+            require_nolint_reason: linter.require_nolint_reason,
+            min_r_version: linter.min_r_version,
+            baseline: linter.baseline,
         };
 
-        Ok(Settings { linter })
+        Ok(Settings { linter, source })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(directory: &Path, path: &str, contents: &str) -> PathBuf {
+        let path = directory.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_extend_merge() {
+        let directory = TempDir::new().unwrap();
+        let directory = directory.path();
+
+        write(
+            directory,
+            "jarl.toml",
+            r#"
+[lint]
+select = ["PERF"]
+ignore = ["any_is_na"]
+exclude = ["tests/"]
+assignment = "="
+
+[lint.severity]
+PERF = "info"
+any_duplicated = "error"
+
+[lint.per-file-ignores]
+"tests/**" = ["browser"]
+"data-raw/*.R" = ["PERF"]
+"#,
+        );
+        let path = write(
+            directory,
+            "pkg/jarl.toml",
+            r#"
+extend = "../jarl.toml"
+
+[lint]
+select = ["PERF", "READ"]
+ignore = ["lengths"]
+
+[lint.severity]
+any_duplicated = "hint"
+
+[lint.per-file-ignores]
+"tests/**" = ["browser", "implicit_assignment"]
+"#,
+        );
+
+        let lint = load_jarl_toml(&path).unwrap().lint.unwrap();
+        assert_eq!(lint.select, Some(vec!["PERF".into(), "READ".into()]));
+        assert_eq!(
+            lint.ignore,
+            Some(vec!["any_is_na".into(), "lengths".into()])
+        );
+        assert_eq!(lint.exclude, Some(vec!["tests/".into()]));
+        assert_eq!(lint.assignment, Some("=".into()));
+        assert_eq!(
+            lint.severity,
+            Some(BTreeMap::from([
+                ("PERF".into(), "info".into()),
+                ("any_duplicated".into(), "hint".into()),
+            ]))
+        );
+        assert_eq!(
+            lint.per_file_ignores,
+            Some(BTreeMap::from([
+                ("data-raw/*.R".into(), vec!["PERF".into()]),
+                (
+                    "tests/**".into(),
+                    vec!["browser".into(), "implicit_assignment".into()]
+                ),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_extend_paths_relative_to_extended_file() {
+        let directory = TempDir::new().unwrap();
+        let directory = directory.path();
+
+        write(
+            directory,
+            "shared/base.toml",
+            r#"
+[lint]
+baseline = "jarl-baseline.json"
+
+[lint.per-file-ignores]
+"tests/**" = ["browser"]
+"data-raw/*.R" = ["PERF"]
+"#,
+        );
+        write(
+            directory,
+            "shared/jarl.toml",
+            "extend = \"base.toml\"\n[lint.per-file-ignores]\n\"R/*.R\" = [\"PERF\"]\n",
+        );
+        let path = write(
+            directory,
+            "pkg/jarl.toml",
+            r#"
+extend = "../shared/jarl.toml"
+
+[lint.per-file-ignores]
+"tests/**" = ["browser", "implicit_assignment"]
+"#,
+        );
+
+        let lint = load_jarl_toml(&path).unwrap().lint.unwrap();
+        let shared = directory.join("shared");
+        assert_eq!(
+            lint.baseline,
+            Some(
+                shared
+                    .join("jarl-baseline.json")
+                    .to_string_lossy()
+                    .into_owned()
+            )
+        );
+        // `tests/**` is defined in `pkg/jarl.toml`, so it's relative to `pkg`.
+        assert_eq!(
+            lint.per_file_ignores_roots,
+            BTreeMap::from([
+                ("R/*.R".into(), shared.clone()),
+                ("data-raw/*.R".into(), shared.clone()),
+            ])
+        );
+
+        // The baseline of the extending file is still relative to its own
+        // directory.
+        let path = write(
+            directory,
+            "other/jarl.toml",
+            "extend = \"../shared/jarl.toml\"\n[lint]\nbaseline = \"baseline.json\"\n",
+        );
+        let lint = load_jarl_toml(&path).unwrap().lint.unwrap();
+        assert_eq!(lint.baseline, Some("baseline.json".into()));
+    }
+
+    #[test]
+    fn test_extend_chain() {
+        let directory = TempDir::new().unwrap();
+        let directory = directory.path();
+
+        write(directory, "base.toml", "[lint]\nassignment = \"=\"\n");
+        write(directory, "a/jarl.toml", "extend = \"../base.toml\"\n");
+        let path = write(directory, "a/b/jarl.toml", "extend = \"../jarl.toml\"\n");
+
+        let lint = load_jarl_toml(&path).unwrap().lint.unwrap();
+        assert_eq!(lint.assignment, Some("=".into()));
+    }
+
+    #[test]
+    fn test_extend_cycle() {
+        let directory = TempDir::new().unwrap();
+        let directory = directory.path();
+
+        write(directory, "a/jarl.toml", "extend = \"../b/jarl.toml\"\n");
+        let path = write(directory, "b/jarl.toml", "extend = \"../a/jarl.toml\"\n");
+
+        let err = load_jarl_toml(&path).unwrap_err();
+        let ParseTomlError::ExtendCycle(paths) = err else {
+            panic!("Expected a cycle, got {err}");
+        };
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths.first(), paths.last());
+    }
+
+    #[test]
+    fn test_extend_missing_file() {
+        let directory = TempDir::new().unwrap();
+        let directory = directory.path();
+
+        let path = write(directory, "jarl.toml", "extend = \"missing.toml\"\n");
+
+        let err = load_jarl_toml(&path).unwrap_err();
+        assert!(matches!(err, ParseTomlError::Read(path, _) if path.ends_with("missing.toml")));
     }
 }
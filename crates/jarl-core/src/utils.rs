@@ -325,3 +325,27 @@ pub fn node_contains_comments(node: &air_r_syntax::RSyntaxNode) -> bool {
         && !node.has_trailing_comments()
         && !node.has_leading_comments()
 }
+
+/// 64-bit FNV-1a hasher. Unlike [std::hash::DefaultHasher], the result is
+/// guaranteed to be the same with all versions of Rust, which matters for the
+/// hashes that are written to disk, e.g. in baselines or in the cache.
+pub struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl std::hash::Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
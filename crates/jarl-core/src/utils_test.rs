@@ -1,11 +1,24 @@
 use crate::check::check;
+use crate::config::{Config, build_config};
 use crate::diagnostic::Diagnostic;
 use crate::settings::Settings;
 use crate::{config::ArgsConfig, discovery::discover_settings};
 use air_workspace::resolve::PathResolver;
 use std::fs;
+use std::path::Path;
 use tempfile::Builder;
 
+/// Build the default config used to lint `path`, optionally applying fixes
+pub fn config_for(path: &Path, fix: bool) -> Config {
+    let check_config = ArgsConfig {
+        files: vec![path.to_path_buf()],
+        fix,
+        ..Default::default()
+    };
+    let resolver = PathResolver::new(Settings::default());
+    build_config(&check_config, &resolver, vec![path.to_path_buf()]).unwrap()
+}
+
 /// Test utility function to check if a given R code contains a specific lint
 pub fn has_lint(text: &str, msg: &str, rule: &str, min_r_version: Option<&str>) -> bool {
     let temp_file = Builder::new()
@@ -18,15 +31,10 @@ pub fn has_lint(text: &str, msg: &str, rule: &str, min_r_version: Option<&str>)
 
     let check_config = ArgsConfig {
         files: vec![temp_file.path().to_path_buf()],
-        fix: false,
-        unsafe_fixes: false,
-        fix_only: false,
         select_rules: rule.to_string(),
-        ignore_rules: String::new(),
         min_r_version: min_r_version.map(|s| s.to_string()),
-        allow_dirty: false,
         allow_no_vcs: true,
-        assignment_op: None,
+        ..Default::default()
     };
 
     let mut resolver = PathResolver::new(Settings::default());
@@ -38,7 +46,7 @@ pub fn has_lint(text: &str, msg: &str, rule: &str, min_r_version: Option<&str>)
         }
     }
 
-    let config = crate::config::build_config(
+    let config = build_config(
         &check_config,
         &resolver,
         vec![temp_file.path().to_path_buf()],
@@ -78,15 +86,10 @@ pub fn has_no_lint(text: &str, rule: &str, min_r_version: Option<&str>) -> bool
 
     let check_config = ArgsConfig {
         files: vec![temp_file.path().to_path_buf()],
-        fix: false,
-        unsafe_fixes: false,
-        fix_only: false,
         select_rules: rule.to_string(),
-        ignore_rules: String::new(),
         min_r_version: min_r_version.map(|s| s.to_string()),
-        allow_dirty: false,
         allow_no_vcs: true,
-        assignment_op: None,
+        ..Default::default()
     };
 
     let mut resolver = PathResolver::new(Settings::default());
@@ -98,7 +101,7 @@ pub fn has_no_lint(text: &str, rule: &str, min_r_version: Option<&str>) -> bool
         }
     }
 
-    let config = crate::config::build_config(
+    let config = build_config(
         &check_config,
         &resolver,
         vec![temp_file.path().to_path_buf()],
@@ -137,13 +140,10 @@ pub fn apply_fixes(
         files: vec![temp_file.path().to_path_buf()],
         fix: true,
         unsafe_fixes,
-        fix_only: false,
         select_rules: rule.to_string(),
-        ignore_rules: String::new(),
         min_r_version: min_r_version.map(|s| s.to_string()),
-        allow_dirty: false,
         allow_no_vcs: true,
-        assignment_op: None,
+        ..Default::default()
     };
 
     let mut resolver = PathResolver::new(Settings::default());
@@ -155,7 +155,7 @@ pub fn apply_fixes(
         }
     }
 
-    let config = crate::config::build_config(
+    let config = build_config(
         &check_config,
         &resolver,
         vec![temp_file.path().to_path_buf()],
@@ -180,15 +180,10 @@ pub fn check_code(text: &str, rule: &str, min_r_version: Option<&str>) -> Vec<Di
 
     let check_config = ArgsConfig {
         files: vec![temp_file.path().to_path_buf()],
-        fix: false,
-        unsafe_fixes: false,
-        fix_only: false,
         select_rules: rule.to_string(),
-        ignore_rules: String::new(),
         min_r_version: min_r_version.map(|s| s.to_string()),
-        allow_dirty: false,
         allow_no_vcs: true,
-        assignment_op: None,
+        ..Default::default()
     };
 
     let mut resolver = PathResolver::new(Settings::default());
@@ -200,7 +195,7 @@ pub fn check_code(text: &str, rule: &str, min_r_version: Option<&str>) -> Vec<Di
         }
     }
 
-    let config = crate::config::build_config(
+    let config = build_config(
         &check_config,
         &resolver,
         vec![temp_file.path().to_path_buf()],
@@ -0,0 +1,413 @@
+use air_r_syntax::{
+    AnyRExpression, RBinaryExpressionFields, RForStatementFields, RFunctionDefinition,
+    RIfStatementFields, RWhileStatementFields,
+};
+
+/// The part of a statement that is evaluated before its body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Header {
+    /// The condition of `if()`.
+    If,
+    /// The condition of `while()`.
+    While,
+    /// The sequence of `for()`.
+    For,
+}
+
+/// Where the expression being visited is located, based on its ancestors.
+///
+/// A function definition starts a new context: the body of a function defined
+/// in a loop is not considered to be in the loop, since it is not necessarily
+/// run there.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Context {
+    function_depth: u32,
+    loop_depth: u32,
+    condition_depth: u32,
+    header: Option<Header>,
+}
+
+impl Context {
+    /// Is the expression in the parameters or in the body of a function?
+    pub fn in_function(&self) -> bool {
+        self.function_depth > 0
+    }
+
+    /// Is the expression in the body of a `for`, `while` or `repeat` loop?
+    /// The sequence of a `for` loop and the condition of a `while` loop are
+    /// not part of the body.
+    pub fn in_loop(&self) -> bool {
+        self.loop_depth > 0
+    }
+
+    /// Is the expression in the condition of an `if` or `while` statement?
+    pub fn in_condition(&self) -> bool {
+        self.condition_depth > 0
+    }
+
+    /// The closest statement whose condition or sequence contains the
+    /// expression, e.g. `Header::If` for `x` in `if (x) y`. Braces reset it:
+    /// in `if ({ x <- 1; x })`, `x <- 1` is an expression of its own rather
+    /// than part of the condition.
+    pub fn header(&self) -> Option<Header> {
+        self.header
+    }
+
+    /// Number of function definitions around the expression.
+    pub fn function_depth(&self) -> u32 {
+        self.function_depth
+    }
+
+    /// Number of loops around the expression, in the current function.
+    pub fn loop_depth(&self) -> u32 {
+        self.loop_depth
+    }
+}
+
+/// Receives every expression of the AST, in source order.
+///
+/// `enter()` is called before visiting the children of the expression and
+/// `exit()` after. Both receive the context of the expression, which doesn't
+/// account for the expression itself, e.g. a `for` loop is not "in a loop"
+/// unless it is nested in another loop.
+pub trait Visitor {
+    fn enter(&mut self, expression: &AnyRExpression, context: Context) -> anyhow::Result<()>;
+
+    fn exit(&mut self, _expression: &AnyRExpression, _context: Context) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Visit `expression` and all the expressions it contains.
+pub fn walk_expression<V: Visitor>(
+    visitor: &mut V,
+    expression: &AnyRExpression,
+) -> anyhow::Result<()> {
+    Walker { visitor, context: Context::default() }.walk(expression)
+}
+
+struct Walker<'a, V> {
+    visitor: &'a mut V,
+    context: Context,
+}
+
+impl<V: Visitor> Walker<'_, V> {
+    // Not all children are expressions that we visit. For instance, the names
+    // of arguments and parameters, the right-hand side of `$` and `@`, or
+    // both sides of `::` are names and not expressions that are evaluated.
+    fn walk(&mut self, expression: &AnyRExpression) -> anyhow::Result<()> {
+        self.visitor.enter(expression, self.context)?;
+
+        match expression {
+            AnyRExpression::RBinaryExpression(children) => {
+                let RBinaryExpressionFields { left, right, .. } = children.as_fields();
+                self.walk(&left?)?;
+                self.walk(&right?)?;
+            }
+            AnyRExpression::RBracedExpressions(children) => {
+                self.in_braces(|walker| {
+                    for expr in children.expressions() {
+                        walker.walk(&expr)?;
+                    }
+                    Ok(())
+                })?;
+            }
+            AnyRExpression::RCall(children) => {
+                self.walk(&children.function()?)?;
+                for argument in children.arguments()?.items() {
+                    if let Some(value) = argument?.value() {
+                        self.walk(&value)?;
+                    }
+                }
+            }
+            AnyRExpression::RExtractExpression(children) => {
+                self.walk(&children.left()?)?;
+            }
+            AnyRExpression::RForStatement(children) => {
+                let RForStatementFields { variable, sequence, body, .. } = children.as_fields();
+                self.walk(&AnyRExpression::RIdentifier(variable?))?;
+                self.in_header(Header::For, |walker| walker.walk(&sequence?))?;
+                self.in_loop(|walker| walker.walk(&body?))?;
+            }
+            AnyRExpression::RFunctionDefinition(children) => {
+                self.in_function(|walker| walker.walk_function(children))?;
+            }
+            AnyRExpression::RIfStatement(children) => {
+                let RIfStatementFields { condition, consequence, else_clause, .. } =
+                    children.as_fields();
+                self.in_header(Header::If, |walker| walker.walk(&condition?))?;
+                self.walk(&consequence?)?;
+                if let Some(else_clause) = else_clause {
+                    self.walk(&else_clause.alternative()?)?;
+                }
+            }
+            AnyRExpression::RParenthesizedExpression(children) => {
+                self.walk(&children.body()?)?;
+            }
+            AnyRExpression::RRepeatStatement(children) => {
+                self.in_loop(|walker| walker.walk(&children.body()?))?;
+            }
+            AnyRExpression::RSubset(children) => {
+                self.walk(&children.function()?)?;
+                for argument in children.arguments()?.items() {
+                    if let Some(value) = argument?.value() {
+                        self.walk(&value)?;
+                    }
+                }
+            }
+            AnyRExpression::RSubset2(children) => {
+                self.walk(&children.function()?)?;
+                for argument in children.arguments()?.items() {
+                    if let Some(value) = argument?.value() {
+                        self.walk(&value)?;
+                    }
+                }
+            }
+            AnyRExpression::RUnaryExpression(children) => {
+                self.walk(&children.argument()?)?;
+            }
+            AnyRExpression::RWhileStatement(children) => {
+                let RWhileStatementFields { condition, body, .. } = children.as_fields();
+                self.in_header(Header::While, |walker| walker.walk(&condition?))?;
+                self.in_loop(|walker| walker.walk(&body?))?;
+            }
+            // Leaves, e.g. identifiers, values, `pkg::fun`, `break`.
+            _ => {}
+        }
+
+        self.visitor.exit(expression, self.context)
+    }
+
+    fn walk_function(&mut self, function: &RFunctionDefinition) -> anyhow::Result<()> {
+        for parameter in function.parameters()?.items() {
+            if let Some(default) = parameter?.default() {
+                self.walk(&default.value()?)?;
+            }
+        }
+        self.walk(&function.body()?)
+    }
+
+    fn in_function(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let previous = self.context;
+        self.context = Context {
+            function_depth: previous.function_depth + 1,
+            ..Context::default()
+        };
+        let result = f(self);
+        self.context = previous;
+        result
+    }
+
+    fn in_loop(&mut self, f: impl FnOnce(&mut Self) -> anyhow::Result<()>) -> anyhow::Result<()> {
+        self.context.loop_depth += 1;
+        let result = f(self);
+        self.context.loop_depth -= 1;
+        result
+    }
+
+    // The sequence of a `for` loop is not a condition.
+    fn in_header(
+        &mut self,
+        header: Header,
+        f: impl FnOnce(&mut Self) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let previous = self.context;
+        self.context.header = Some(header);
+        if header != Header::For {
+            self.context.condition_depth += 1;
+        }
+        let result = f(self);
+        self.context = previous;
+        result
+    }
+
+    fn in_braces(&mut self, f: impl FnOnce(&mut Self) -> anyhow::Result<()>) -> anyhow::Result<()> {
+        let previous = self.context.header;
+        self.context.header = None;
+        let result = f(self);
+        self.context.header = previous;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use air_r_parser::{RParserOptions, parse};
+    use biome_rowan::AstNode;
+
+    /// Records the text of the visited expressions and their context.
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+    }
+
+    impl Visitor for Recorder {
+        fn enter(&mut self, expression: &AnyRExpression, context: Context) -> anyhow::Result<()> {
+            let mut flags = String::new();
+            if context.in_function() {
+                flags.push('f');
+            }
+            if context.in_loop() {
+                flags.push('l');
+            }
+            if context.in_condition() {
+                flags.push('c');
+            }
+            self.events.push(format!(
+                "enter {} [{}]",
+                expression.syntax().text_trimmed(),
+                flags
+            ));
+            Ok(())
+        }
+
+        fn exit(&mut self, expression: &AnyRExpression, _context: Context) -> anyhow::Result<()> {
+            self.events
+                .push(format!("exit {}", expression.syntax().text_trimmed()));
+            Ok(())
+        }
+    }
+
+    /// Records the text of the visited expressions and their header.
+    #[derive(Default)]
+    struct HeaderRecorder {
+        headers: Vec<(String, Option<Header>)>,
+    }
+
+    impl Visitor for HeaderRecorder {
+        fn enter(&mut self, expression: &AnyRExpression, context: Context) -> anyhow::Result<()> {
+            self.headers.push((
+                expression.syntax().text_trimmed().to_string(),
+                context.header(),
+            ));
+            Ok(())
+        }
+    }
+
+    fn record(code: &str) -> Vec<String> {
+        let parsed = parse(code, RParserOptions::default());
+        let mut recorder = Recorder::default();
+        for expr in parsed.tree().expressions() {
+            walk_expression(&mut recorder, &expr).unwrap();
+        }
+        recorder.events
+    }
+
+    fn entered(code: &str) -> Vec<String> {
+        record(code)
+            .into_iter()
+            .filter_map(|event| event.strip_prefix("enter ").map(|x| x.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_enter_and_exit_are_nested() {
+        assert_eq!(
+            record("f(x)"),
+            vec![
+                "enter f(x) []",
+                "enter f []",
+                "enter x []",
+                "exit x",
+                "exit f",
+                "exit f(x)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_visits_parameter_defaults() {
+        let entered = entered("function(x = foo(y), z) x");
+        assert!(entered.contains(&"foo(y) [f]".to_string()));
+        assert!(entered.contains(&"y [f]".to_string()));
+        // Parameter names are not visited.
+        assert!(!entered.contains(&"z [f]".to_string()));
+        assert!(entered.contains(&"x [f]".to_string()));
+    }
+
+    #[test]
+    fn test_visits_lambdas() {
+        let entered = entered("\\(x) foo(x)");
+        assert!(entered.contains(&"foo(x) [f]".to_string()));
+    }
+
+    #[test]
+    fn test_visits_subset2_and_extract() {
+        let entered = entered("x[[foo(y)]]$bar");
+        assert!(entered.contains(&"x[[foo(y)]] []".to_string()));
+        assert!(entered.contains(&"x []".to_string()));
+        assert!(entered.contains(&"foo(y) []".to_string()));
+        // The right-hand side of `$` is a name.
+        assert!(!entered.contains(&"bar []".to_string()));
+    }
+
+    #[test]
+    fn test_does_not_visit_namespace_names() {
+        let entered = entered("pkg::fun(x)");
+        assert_eq!(entered, vec!["pkg::fun(x) []", "pkg::fun []", "x []"]);
+    }
+
+    #[test]
+    fn test_context() {
+        let entered = entered(
+            "
+if (a) b
+while (c) d
+for (e in f) g
+repeat h
+",
+        );
+        assert!(entered.contains(&"a [c]".to_string()));
+        assert!(entered.contains(&"b []".to_string()));
+        assert!(entered.contains(&"c [c]".to_string()));
+        assert!(entered.contains(&"d [l]".to_string()));
+        assert!(entered.contains(&"f []".to_string()));
+        assert!(entered.contains(&"g [l]".to_string()));
+        assert!(entered.contains(&"h [l]".to_string()));
+    }
+
+    #[test]
+    fn test_header() {
+        let headers = |code: &str| {
+            let parsed = parse(code, RParserOptions::default());
+            let mut recorder = HeaderRecorder::default();
+            for expr in parsed.tree().expressions() {
+                walk_expression(&mut recorder, &expr).unwrap();
+            }
+            recorder.headers
+        };
+        let header_of = |code: &str, target: &str| {
+            headers(code)
+                .into_iter()
+                .find(|(text, _)| text == target)
+                .unwrap()
+                .1
+        };
+
+        assert_eq!(header_of("if (a) b else c", "a"), Some(Header::If));
+        assert_eq!(header_of("if (a) b else c", "b"), None);
+        assert_eq!(header_of("if (a) b else c", "c"), None);
+        assert_eq!(header_of("while (a) b", "a"), Some(Header::While));
+        assert_eq!(header_of("while (a) b", "b"), None);
+        assert_eq!(header_of("for (i in a) b", "a"), Some(Header::For));
+        assert_eq!(header_of("for (i in a) b", "b"), None);
+
+        // The closest header wins, braces and functions reset it.
+        assert_eq!(header_of("if (while (a) b) c", "a"), Some(Header::While));
+        assert_eq!(header_of("if (while (a) b) c", "b"), Some(Header::If));
+        assert_eq!(header_of("if (f(a)) b", "a"), Some(Header::If));
+        assert_eq!(header_of("if ({ a }) b", "a"), None);
+        assert_eq!(header_of("if (function() a) b", "a"), None);
+    }
+
+    #[test]
+    fn test_function_resets_loop_context() {
+        let entered = entered("for (i in x) { function() y; z }");
+        assert!(entered.contains(&"y [f]".to_string()));
+        assert!(entered.contains(&"z [l]".to_string()));
+    }
+}
@@ -6,7 +6,6 @@
 use anyhow::{Result, anyhow};
 use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
 use serde::{Deserialize, Serialize};
-use tempfile::TempDir;
 
 use std::path::Path;
 
@@ -16,18 +15,17 @@ use crate::session::DocumentSnapshot;
 use crate::utils::should_exclude_file_based_on_settings;
 
 use air_workspace::resolve::PathResolver;
-use jarl_core::discovery::{DiscoveredSettings, discover_r_file_paths, discover_settings};
+use jarl_core::check::lint_source;
+use jarl_core::discovery::{DiscoveredSettings, discover_settings};
 use jarl_core::{
     config::ArgsConfig, config::build_config, diagnostic::Diagnostic as JarlDiagnostic,
-    settings::Settings,
+    diagnostic::Edit, diagnostic::Severity as JarlSeverity, settings::Settings,
 };
 
 /// Fix information that can be attached to a diagnostic for code actions
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DiagnosticFix {
-    pub content: String,
-    pub start: usize,
-    pub end: usize,
+    pub edits: Vec<Edit>,
     pub is_safe: bool,
     pub rule_name: String,
 }
@@ -90,25 +88,6 @@ fn run_jarl_linting(
         return Ok(Vec::new());
     }
 
-    // TODO: we shoudln't have to write the content to a tempfile to then read
-    // it and get diagnostic. The check function should be able to take the R
-    // code as a string.
-    // Write in-memory content to a temporary file for linting
-    let temp_dir = TempDir::new()?;
-    let temp_dir = temp_dir.path();
-    let temp_file = temp_dir.join(format!("jarl_lsp_{}.R", std::process::id()));
-
-    std::fs::write(&temp_file, content)
-        .map_err(|e| anyhow!("Failed to write temporary file: {}", e))?;
-    let temp_path_str = temp_file.to_string_lossy().to_string();
-    let temp_path: Vec<String> = vec![temp_path_str];
-
-    // Use temp path for discovering R file paths (just the temp file itself)
-    let paths = discover_r_file_paths(&temp_path, &resolver, true)
-        .into_iter()
-        .filter_map(Result::ok)
-        .collect::<Vec<_>>();
-
     // Check if TOML has assignment setting and if so use it, otherwise use
     // the assignment from the workspace settings.
     let toml_has_assignment = resolver
@@ -123,7 +102,7 @@ fn run_jarl_linting(
     };
 
     let check_config = ArgsConfig {
-        files: temp_path.iter().map(|s| s.into()).collect(),
+        files: vec![file_path.to_path_buf()],
         fix: false,
         unsafe_fixes: false,
         fix_only: false,
@@ -133,36 +112,28 @@ fn run_jarl_linting(
         allow_dirty: false,
         allow_no_vcs: false,
         assignment_op: assignment_operator,
+        cache_dir: None,
+        report_unused_nolint: false,
+        show_suppressed: false,
+        verbose: false,
     };
 
-    let config = build_config(&check_config, &resolver, paths)?;
-
-    let diagnostics = jarl_core::check::check(config);
-    let mut all_diagnostics: Vec<JarlDiagnostic> = diagnostics
-        .into_iter()
-        .flat_map(|(_, result)| match result {
-            Ok(diags) => {
-                tracing::debug!("Found {} diagnostics for file", diags.len());
-                diags
-            }
-            Err(e) => {
-                tracing::error!("Error checking file: {}", e);
-                Vec::new()
-            }
-        })
-        .collect();
-
-    // Clean up temporary file
-    if let Err(e) = std::fs::remove_file(&temp_file) {
-        tracing::warn!("Failed to remove temporary file {:?}: {}", temp_file, e);
-    }
+    // The content of the buffer may differ from the content of the file on
+    // disk, so we lint the content directly.
+    let config = build_config(&check_config, &resolver, vec![file_path.to_path_buf()])?;
 
-    // Update diagnostics to point to the original file instead of temp file
-    for diagnostic in &mut all_diagnostics {
-        diagnostic.filename = file_path.to_path_buf();
-    }
+    let diagnostics = match lint_source(content, file_path, &config) {
+        Ok(diagnostics) => {
+            tracing::debug!("Found {} diagnostics for file", diagnostics.len());
+            diagnostics
+        }
+        Err(e) => {
+            tracing::error!("Error checking file: {}", e);
+            Vec::new()
+        }
+    };
 
-    Ok(all_diagnostics)
+    Ok(diagnostics)
 }
 
 /// Convert a Jarl diagnostic to LSP diagnostic format with fix information
@@ -181,16 +152,17 @@ fn convert_to_lsp_diagnostic(
 
     let range = Range::new(start_pos, end_pos);
 
-    // TODO-etienne: don't have that
-    // let severity = convert_severity(jarl_diag.severity);
-    let severity = DiagnosticSeverity::WARNING;
+    let severity = convert_severity(jarl_diag.severity);
 
     // Extract fix information if available
     // Always include fix_data even if there's no actual fix, so we can access the rule_name
     let diagnostic_fix = DiagnosticFix {
-        content: jarl_diag.fix.content.clone(),
-        start: jarl_diag.fix.start,
-        end: jarl_diag.fix.end,
+        // Display-only fixes are not offered as code actions.
+        edits: if jarl_diag.has_no_fix() {
+            vec![]
+        } else {
+            jarl_diag.fix.edits.clone()
+        },
         is_safe: jarl_diag.has_safe_fix(),
         rule_name: jarl_diag.message.name.clone(),
     };
@@ -290,15 +262,15 @@ pub fn byte_offset_to_lsp_position(
     Ok(Position::new(line as u32, lsp_character))
 }
 
-// /// Convert Jarl severity to LSP diagnostic severity
-// fn convert_severity(severity: JarlSeverity) -> DiagnosticSeverity {
-//     match severity {
-//         JarlSeverity::Error => DiagnosticSeverity::ERROR,
-//         JarlSeverity::Warning => DiagnosticSeverity::WARNING,
-//         JarlSeverity::Info => DiagnosticSeverity::INFORMATION,
-//         JarlSeverity::Hint => DiagnosticSeverity::HINT,
-//     }
-// }
+/// Convert Jarl severity to LSP diagnostic severity
+fn convert_severity(severity: JarlSeverity) -> DiagnosticSeverity {
+    match severity {
+        JarlSeverity::Error => DiagnosticSeverity::ERROR,
+        JarlSeverity::Warning => DiagnosticSeverity::WARNING,
+        JarlSeverity::Info => DiagnosticSeverity::INFORMATION,
+        JarlSeverity::Hint => DiagnosticSeverity::HINT,
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -306,6 +278,7 @@ mod tests {
     use crate::document::{DocumentKey, TextDocument};
     use crate::session::DocumentSnapshot;
     use lsp_types::{ClientCapabilities, Url};
+    use tempfile::TempDir;
 
     fn create_test_snapshot(content: &str) -> DocumentSnapshot {
         let uri = Url::parse("file:///test.R").unwrap();
@@ -328,6 +301,32 @@ mod tests {
         assert!(diagnostics.is_empty());
     }
 
+    #[test]
+    fn test_parse_error_is_reported_as_error() {
+        let snapshot = create_test_snapshot("x <- 1\nf(\n");
+        let diagnostics = lint_document(&snapshot).unwrap();
+        assert!(!diagnostics.is_empty());
+        assert!(
+            diagnostics
+                .iter()
+                .all(|d| d.severity == Some(DiagnosticSeverity::ERROR))
+        );
+    }
+
+    #[test]
+    fn test_severity_of_rules() {
+        let snapshot = create_test_snapshot("any(is.na(x))\nx == NA\n");
+        let diagnostics = lint_document(&snapshot).unwrap();
+        let severities = diagnostics
+            .iter()
+            .map(|d| d.severity.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            severities,
+            vec![DiagnosticSeverity::WARNING, DiagnosticSeverity::ERROR]
+        );
+    }
+
     #[test]
     fn test_position_conversion() {
         let content = "hello\nworld\ntest";
@@ -5,6 +5,7 @@
 
 use anyhow::{Context, Result, anyhow};
 use crossbeam::channel;
+use jarl_core::directive::update_existing_nolint;
 use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
 use lsp_types::{self as types, notification::Notification as _, request::Request as _};
 
@@ -571,7 +572,7 @@ impl Server {
         let fix_data = diagnostic.data.as_ref()?;
         let fix: crate::lint::DiagnosticFix = serde_json::from_value(fix_data.clone()).ok()?;
 
-        if fix.content.is_empty() && fix.start == fix.end {
+        if fix.edits.is_empty() {
             return None; // No fix available
         }
 
@@ -579,18 +580,24 @@ impl Server {
         let content = snapshot.content();
         let encoding = snapshot.position_encoding();
 
-        let start_pos =
-            crate::lint::byte_offset_to_lsp_position(fix.start, content, encoding).ok()?;
-        let end_pos = crate::lint::byte_offset_to_lsp_position(fix.end, content, encoding).ok()?;
-
-        let edit_range = types::Range::new(start_pos, end_pos);
-
-        // Create the text edit for this single file
-        let text_edit = types::TextEdit { range: edit_range, new_text: fix.content.clone() };
+        // Create the text edits for this single file. All edits of a fix must
+        // be applied together.
+        let text_edits = fix
+            .edits
+            .iter()
+            .map(|edit| {
+                let start_pos =
+                    crate::lint::byte_offset_to_lsp_position(edit.start, content, encoding).ok()?;
+                let end_pos =
+                    crate::lint::byte_offset_to_lsp_position(edit.end, content, encoding).ok()?;
+                let edit_range = types::Range::new(start_pos, end_pos);
+                Some(types::TextEdit { range: edit_range, new_text: edit.content.clone() })
+            })
+            .collect::<Option<Vec<_>>>()?;
 
         // Create workspace edit with just this file's changes
         let mut changes = std::collections::HashMap::new();
-        changes.insert(snapshot.uri().clone(), vec![text_edit]);
+        changes.insert(snapshot.uri().clone(), text_edits);
 
         let workspace_edit = types::WorkspaceEdit { changes: Some(changes), ..Default::default() };
 
@@ -647,8 +654,7 @@ impl Server {
                 return None;
             }
 
-            if let Some(updated_comment) = Self::update_existing_nolint(&prev_line_text, &rule_name)
-            {
+            if let Some(updated_comment) = update_existing_nolint(&prev_line_text, &rule_name) {
                 // Update existing nolint comment (replace without newline since we're replacing the line content)
                 let prev_line_start = types::Position::new(line_start - 1, 0);
                 let prev_line_end =
@@ -762,48 +768,6 @@ impl Server {
     fn get_line_text(content: &str, line_number: usize) -> Option<String> {
         content.lines().nth(line_number).map(|s| s.to_string())
     }
-
-    /// Update an existing nolint comment to include a new rule
-    fn update_existing_nolint(line: &str, rule_name: &str) -> Option<String> {
-        let trimmed = line.trim();
-
-        // Check if this is a nolint comment
-        if !trimmed.starts_with("# nolint") {
-            return None;
-        }
-
-        // If it's already a generic "# nolint", leave it as is
-        if trimmed == "# nolint" {
-            return None;
-        }
-
-        // Extract existing rules
-        if let Some(colon_pos) = trimmed.find(':') {
-            let rules_part = trimmed[colon_pos + 1..].trim();
-            let existing_rules: Vec<&str> = rules_part.split(',').map(|s| s.trim()).collect();
-
-            // Check if the rule is already there
-            if existing_rules.contains(&rule_name) {
-                return None;
-            }
-
-            // Add the new rule
-            let indent = line
-                .chars()
-                .take_while(|c| c.is_whitespace())
-                .collect::<String>();
-            let all_rules = existing_rules
-                .iter()
-                .chain(std::iter::once(&rule_name))
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-                .join(", ");
-
-            Some(format!("{}# nolint: {}", indent, all_rules))
-        } else {
-            None
-        }
-    }
 }
 
 /// Check if two ranges overlap
@@ -818,6 +782,7 @@ mod tests {
     use crate::document::{DocumentKey, TextDocument};
     use crate::lint::DiagnosticFix;
     use crate::session::DocumentSnapshot;
+    use jarl_core::diagnostic::Edit;
     use lsp_server::Connection;
     use lsp_types::{
         CodeActionContext, CodeActionParams, Position, Range, TextDocumentIdentifier, Url,
@@ -871,9 +836,11 @@ mod tests {
         let snapshot = create_test_snapshot("x = 1\n");
 
         let fix = DiagnosticFix {
-            content: "x <- 1".to_string(),
-            start: 0, // replace entire assignment
-            end: 5,   // end of "x = 1"
+            edits: vec![Edit {
+                content: "x <- 1".to_string(),
+                start: 0, // replace entire assignment
+                end: 5,   // end of "x = 1"
+            }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -930,9 +897,7 @@ mod tests {
         let snapshot = create_test_snapshot("y = 2\n");
 
         let fix = DiagnosticFix {
-            content: "".to_string(),
-            start: 0,
-            end: 0,
+            edits: vec![],
             is_safe: true,
             rule_name: "class_comparison".to_string(),
         };
@@ -952,9 +917,11 @@ mod tests {
         let snapshot = create_test_snapshot("x = 1\n");
 
         let fix = DiagnosticFix {
-            content: "x <- 1".to_string(),
-            start: 0,
-            end: 5, // "x = 1"
+            edits: vec![Edit {
+                content: "x <- 1".to_string(),
+                start: 0,
+                end: 5, // "x = 1"
+            }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -986,9 +953,11 @@ mod tests {
         let snapshot = create_test_snapshot("result <- any(is.na(data$column))\n");
 
         let fix = DiagnosticFix {
-            content: "anyNA(data$column)".to_string(),
-            start: 10, // start of "any(is.na(...))"
-            end: 33,   // end of "any(is.na(data$column))"
+            edits: vec![Edit {
+                content: "anyNA(data$column)".to_string(),
+                start: 10, // start of "any(is.na(...))"
+                end: 33,   // end of "any(is.na(data$column))"
+            }],
             is_safe: true,
             rule_name: "any_is_na".to_string(),
         };
@@ -1054,9 +1023,7 @@ mod tests {
 
         for (start, end, replacement, range_start, range_end) in test_cases {
             let fix = DiagnosticFix {
-                content: replacement.to_string(),
-                start,
-                end,
+                edits: vec![Edit { content: replacement.to_string(), start, end }],
                 is_safe: true,
                 rule_name: "assignment_operator".to_string(),
             };
@@ -1144,9 +1111,11 @@ mod tests {
         let snapshot1 = create_test_snapshot(content1);
 
         let fix1 = DiagnosticFix {
-            content: "héllo <- 1".to_string(),
-            start: 0,
-            end: content1.len(),
+            edits: vec![Edit {
+                content: "héllo <- 1".to_string(),
+                start: 0,
+                end: content1.len(),
+            }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1168,9 +1137,11 @@ mod tests {
         let snapshot2 = create_test_snapshot(content2);
 
         let fix2 = DiagnosticFix {
-            content: "🚀_var <- 2".to_string(),
-            start: 0,
-            end: content2.len(),
+            edits: vec![Edit {
+                content: "🚀_var <- 2".to_string(),
+                start: 0,
+                end: content2.len(),
+            }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1189,9 +1160,11 @@ mod tests {
         let snapshot3 = create_test_snapshot(content3);
 
         let fix3 = DiagnosticFix {
-            content: "世界 <- 3".to_string(),
-            start: 0,
-            end: content3.len(),
+            edits: vec![Edit {
+                content: "世界 <- 3".to_string(),
+                start: 0,
+                end: content3.len(),
+            }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1231,9 +1204,11 @@ mod tests {
         let snapshot = create_test_snapshot(content);
 
         let fix = DiagnosticFix {
-            content: "anyNA(données$colonne)".to_string(),
-            start: 12, // start of "any(is.na(...))"
-            end: 39,   // end of "any(is.na(données$colonne))"
+            edits: vec![Edit {
+                content: "anyNA(données$colonne)".to_string(),
+                start: 12, // start of "any(is.na(...))"
+                end: 39,   // end of "any(is.na(données$colonne))"
+            }],
             is_safe: true,
             rule_name: "any_is_na".to_string(),
         };
@@ -1270,9 +1245,11 @@ mod tests {
 
         // Create a fix that targets the "=" character
         let fix = DiagnosticFix {
-            content: "🚀 <- 1".to_string(),
-            start: 5, // byte position of "=" in UTF-8
-            end: 6,
+            edits: vec![Edit {
+                content: "🚀 <- 1".to_string(),
+                start: 5, // byte position of "=" in UTF-8
+                end: 6,
+            }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1340,9 +1317,7 @@ mod tests {
         let snapshot = create_test_snapshot("x = 1\n");
 
         let fix = DiagnosticFix {
-            content: "x <- 1".to_string(),
-            start: 0,
-            end: 5,
+            edits: vec![Edit { content: "x <- 1".to_string(), start: 0, end: 5 }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1371,9 +1346,7 @@ mod tests {
         let snapshot = create_test_snapshot("x = 1\n");
 
         let fix = DiagnosticFix {
-            content: "x <- 1".to_string(),
-            start: 0,
-            end: 5,
+            edits: vec![Edit { content: "x <- 1".to_string(), start: 0, end: 5 }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1398,9 +1371,7 @@ mod tests {
         let snapshot = create_test_snapshot("# nolint: assignment_operator\nx = 1\n");
 
         let fix = DiagnosticFix {
-            content: "x <- 1".to_string(),
-            start: 30,
-            end: 35,
+            edits: vec![Edit { content: "x <- 1".to_string(), start: 30, end: 35 }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1422,9 +1393,7 @@ mod tests {
         let snapshot = create_test_snapshot("# nolint\nx = 1\n");
 
         let fix = DiagnosticFix {
-            content: "x <- 1".to_string(),
-            start: 9,
-            end: 14,
+            edits: vec![Edit { content: "x <- 1".to_string(), start: 9, end: 14 }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1449,9 +1418,11 @@ mod tests {
 
         // Create two different diagnostics for different lines with comments between
         let fix1 = DiagnosticFix {
-            content: "anyNA(any(duplicated(x)))".to_string(),
-            start: 0,
-            end: 67,
+            edits: vec![Edit {
+                content: "anyNA(any(duplicated(x)))".to_string(),
+                start: 0,
+                end: 67,
+            }],
             is_safe: true,
             rule_name: "any_is_na".to_string(),
         };
@@ -1463,9 +1434,11 @@ mod tests {
         );
 
         let fix2 = DiagnosticFix {
-            content: "anyDuplicated(x)".to_string(),
-            start: 37,
-            end: 54,
+            edits: vec![Edit {
+                content: "anyDuplicated(x)".to_string(),
+                start: 37,
+                end: 54,
+            }],
             is_safe: true,
             rule_name: "any_duplicated".to_string(),
         };
@@ -1500,9 +1473,7 @@ mod tests {
         let snapshot = create_test_snapshot(content);
 
         let fix = DiagnosticFix {
-            content: "x <- 1".to_string(),
-            start: 0,
-            end: 5,
+            edits: vec![Edit { content: "x <- 1".to_string(), start: 0, end: 5 }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1536,9 +1507,7 @@ mod tests {
         let snapshot = create_test_snapshot(content);
 
         let fix = DiagnosticFix {
-            content: "x <- 1".to_string(),
-            start: 14,
-            end: 19,
+            edits: vec![Edit { content: "x <- 1".to_string(), start: 14, end: 19 }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1572,9 +1541,11 @@ mod tests {
         let snapshot = create_test_snapshot(content);
 
         let fix = DiagnosticFix {
-            content: "anyDuplicated(which(grepl(\"a\", x)))".to_string(),
-            start: 35,
-            end: 73,
+            edits: vec![Edit {
+                content: "anyDuplicated(which(grepl(\"a\", x)))".to_string(),
+                start: 35,
+                end: 73,
+            }],
             is_safe: true,
             rule_name: "any_duplicated".to_string(),
         };
@@ -1612,9 +1583,7 @@ mod tests {
         let snapshot = create_test_snapshot(content);
 
         let fix = DiagnosticFix {
-            content: "x <- 1".to_string(),
-            start: 19,
-            end: 24,
+            edits: vec![Edit { content: "x <- 1".to_string(), start: 19, end: 24 }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
@@ -1648,9 +1617,7 @@ mod tests {
         let snapshot = create_test_snapshot(content);
 
         let fix = DiagnosticFix {
-            content: "x <- 1".to_string(),
-            start: 18,
-            end: 23,
+            edits: vec![Edit { content: "x <- 1".to_string(), start: 18, end: 23 }],
             is_safe: true,
             rule_name: "assignment_operator".to_string(),
         };
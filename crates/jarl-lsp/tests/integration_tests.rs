@@ -62,15 +62,14 @@ fn test_server_startup_basic() {
 
 #[test]
 fn test_diagnostic_fix_serialization() {
+    use jarl_core::diagnostic::Edit;
     use jarl_lsp::lint::DiagnosticFix;
     use serde_json;
 
     // Test that DiagnosticFix can be properly serialized/deserialized
     // This is used when embedding fix data in LSP diagnostics
     let fix = DiagnosticFix {
-        content: "x <- 1".to_string(),
-        start: 0,
-        end: 5,
+        edits: vec![Edit { content: "x <- 1".to_string(), start: 0, end: 5 }],
         is_safe: true,
         rule_name: "assignment_operator".to_string(),
     };
@@ -78,9 +77,7 @@ fn test_diagnostic_fix_serialization() {
     let json_value = serde_json::to_value(&fix).unwrap();
     let deserialized: DiagnosticFix = serde_json::from_value(json_value).unwrap();
 
-    assert_eq!(deserialized.content, fix.content);
-    assert_eq!(deserialized.start, fix.start);
-    assert_eq!(deserialized.end, fix.end);
+    assert_eq!(deserialized.edits, fix.edits);
     assert_eq!(deserialized.is_safe, fix.is_safe);
 }
 
@@ -2,7 +2,8 @@ use crate::logging::LogLevel;
 use crate::output_format::OutputFormat;
 use clap::builder::Styles;
 use clap::builder::styling::{AnsiColor, Effects};
-use clap::{Parser, Subcommand, arg};
+use clap::{Parser, Subcommand, ValueEnum, arg};
+use jarl_core::diagnostic::Severity;
 
 // Configures Clap v3-style help menu colors
 const STYLES: Styles = Styles::styled()
@@ -32,6 +33,15 @@ pub(crate) enum Command {
     /// Check a set of files or directories
     Check(CheckCommand),
 
+    /// Convert the configuration of another linter to a `jarl.toml`
+    Migrate(MigrateCommand),
+
+    /// Explain a rule
+    Rule(RuleCommand),
+
+    /// List all rules
+    Rules(RulesCommand),
+
     /// Start a language server
     Server(ServerCommand),
 }
@@ -41,7 +51,7 @@ pub(crate) enum Command {
 pub struct CheckCommand {
     #[arg(
         required = true,
-        help = "List of files or directories to check or fix lints, for example `jarl check .`."
+        help = "List of files or directories to check or fix lints, for example `jarl check .`. Use `-` to read code from stdin."
     )]
     pub files: Vec<String>,
     #[arg(
@@ -64,6 +74,12 @@ pub struct CheckCommand {
         help = "Apply fixes to resolve lint violations, but don't report on leftover violations. Implies `--fix`."
     )]
     pub fix_only: bool,
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Don't write the fixes to the files, print them as a unified diff instead. Exits with a non-zero status if some files would be changed. Implies `--fix`."
+    )]
+    pub diff: bool,
     #[arg(
         long,
         default_value = "false",
@@ -97,6 +113,12 @@ pub struct CheckCommand {
         help = "Show the time taken by the function."
     )]
     pub with_timing: bool,
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Also list the rules that are disabled because of the minimum R version of the project, even if they were not selected by name."
+    )]
+    pub verbose: bool,
     #[arg(
         short,
         long,
@@ -110,12 +132,132 @@ pub struct CheckCommand {
         help="Output serialization format for violations."
     )]
     pub output_format: OutputFormat,
+    #[arg(
+        long,
+        value_enum,
+        help = "Minimum severity of the diagnostics that make the check fail. For example, `error` only fails if there are errors. By default, any diagnostic makes the check fail."
+    )]
+    pub fail_on: Option<Severity>,
     #[arg(
         long,
         value_enum,
         help = "Assignment operator to use, can be either `<-` or `=`."
     )]
     pub assignment_op: Option<String>,
+    #[arg(
+        long,
+        help = "The name of the file when passing code via stdin with `-`. This is used to discover the configuration and to report diagnostics."
+    )]
+    pub stdin_filename: Option<String>,
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with = "cache_dir",
+        help = "Don't read or write the cache of diagnostics, i.e. lint all files from scratch."
+    )]
+    pub no_cache: bool,
+    #[arg(
+        long,
+        help = "Directory where the cache of diagnostics is stored. Defaults to `.jarl_cache` in the project root, i.e. the closest directory with a `jarl.toml`, a `DESCRIPTION` or a `.git`, or else the current directory."
+    )]
+    pub cache_dir: Option<String>,
+    #[arg(
+        long,
+        help = "Don't report the violations listed in this baseline file, and report those of its violations that were fixed. This takes precedence over `baseline` in `jarl.toml`."
+    )]
+    pub baseline: Option<String>,
+    #[arg(
+        long,
+        conflicts_with_all = ["baseline", "fix", "unsafe_fixes", "fix_only", "diff"],
+        help = "Write all current violations to this baseline file instead of reporting them. Use it with `--baseline` (or `baseline` in `jarl.toml`) to only report new violations."
+    )]
+    pub generate_baseline: Option<String>,
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with_all = ["baseline", "generate_baseline", "fix", "unsafe_fixes", "fix_only", "diff", "report_unused_nolint"],
+        help = "Add or extend a trailing `# nolint: <rules>` comment to suppress each current violation. Use it to enable new rules without fixing the existing code first."
+    )]
+    pub add_nolint: bool,
+    #[arg(
+        long,
+        requires = "add_nolint",
+        help = "Explain why the violations are suppressed in the comments added by `--add-nolint`, e.g. `# nolint: any_is_na -- <reason>`. Use it with `require-nolint-reason = true` in `jarl.toml`."
+    )]
+    pub add_nolint_reason: Option<String>,
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Report `# nolint` comments that don't suppress any violation, that name unknown rules, or that are not closed. Their fix removes the useless comments or rules."
+    )]
+    pub report_unused_nolint: bool,
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with_all = ["generate_baseline", "fix", "unsafe_fixes", "fix_only", "diff", "add_nolint"],
+        help = "Also report the violations that are suppressed by `# nolint` comments, with the comment and its reason. They don't make the check fail. Requires `--output-format json`."
+    )]
+    pub show_suppressed: bool,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub(crate) struct RuleCommand {
+    #[arg(help = "Name of the rule to explain, for example `any_is_na`.")]
+    pub name: String,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = RuleOutputFormat::default(),
+        help = "Output format of the rule documentation."
+    )]
+    pub output_format: RuleOutputFormat,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub(crate) struct RulesCommand {
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = RuleOutputFormat::default(),
+        help = "Output format of the list of rules."
+    )]
+    pub output_format: RuleOutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum RuleOutputFormat {
+    #[default]
+    /// Print the rules as text
+    Text,
+    /// Print the rules as JSON
+    Json,
+}
+
+#[derive(Clone, Debug, Parser)]
+pub(crate) struct MigrateCommand {
+    #[command(subcommand)]
+    pub source: MigrateSource,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub(crate) enum MigrateSource {
+    /// Convert a `.lintr` file
+    Lintr(MigrateLintrCommand),
+}
+
+#[derive(Clone, Debug, Parser)]
+pub(crate) struct MigrateLintrCommand {
+    #[arg(
+        default_value = ".lintr",
+        help = "Path to the `.lintr` file to convert. The `jarl.toml` is written in the same directory."
+    )]
+    pub path: String,
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Overwrite the `jarl.toml` if it already exists."
+    )]
+    pub force: bool,
 }
 
 #[derive(Clone, Debug, Parser)]
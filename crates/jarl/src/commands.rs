@@ -1,2 +1,4 @@
 pub(crate) mod check;
+pub(crate) mod migrate;
+pub(crate) mod rule;
 pub(crate) mod server;
@@ -1,12 +1,20 @@
 use air_workspace::resolve::PathResolver;
+use jarl_core::baseline::Baseline;
+use jarl_core::cache::{DEFAULT_CACHE_DIR, default_cache_dir};
 use jarl_core::discovery::{DiscoveredSettings, discover_r_file_paths, discover_settings};
 use jarl_core::{
-    config::ArgsConfig, config::build_config, diagnostic::Diagnostic, settings::Settings,
+    check::FixedSource, config::ArgsConfig, config::Config, config::build_config,
+    config::build_configs, diagnostic::Diagnostic, diagnostic::Severity, settings::Settings,
 };
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use clap::Parser;
 use colored::Colorize;
+use similar::TextDiff;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use crate::args::CheckCommand;
@@ -24,6 +32,16 @@ pub fn check() -> Result<ExitStatus> {
         None
     };
 
+    if args.show_suppressed && !matches!(args.output_format, OutputFormat::Json) {
+        return Err(anyhow!(
+            "`--show-suppressed` can only be used with `--output-format json`."
+        ));
+    }
+
+    if args.files.iter().any(|file| file == "-") {
+        return check_stdin(&args, start);
+    }
+
     let mut resolver = PathResolver::new(Settings::default());
     for DiscoveredSettings { directory, settings } in discover_settings(&args.files)? {
         resolver.add(&directory, settings);
@@ -46,22 +64,54 @@ pub fn check() -> Result<ExitStatus> {
     // use std::path::Path;
     // let paths = vec![Path::new("demos/foo.R").to_path_buf()];
 
-    let check_config = ArgsConfig {
-        files: args.files.iter().map(|s| s.into()).collect(),
-        fix: args.fix,
-        unsafe_fixes: args.unsafe_fixes,
-        fix_only: args.fix_only,
-        select_rules: args.select_rules.clone(),
-        ignore_rules: args.ignore_rules.clone(),
-        min_r_version: args.min_r_version.clone(),
-        allow_dirty: args.allow_dirty,
-        allow_no_vcs: args.allow_no_vcs,
-        assignment_op: args.assignment_op,
-    };
+    let check_config = args_config(&args, args.files.iter().map(|s| s.into()).collect());
 
-    let config = build_config(&check_config, &resolver, paths)?;
+    // Each file is checked with the settings of its nearest `jarl.toml`.
+    let configs = build_configs(&check_config, &resolver, paths)?;
+    print_warnings(&configs);
 
-    let file_results = jarl_core::check::check(config);
+    if args.diff {
+        return check_diff(configs, start);
+    }
+
+    if let Some(path) = &args.generate_baseline {
+        return generate_baseline(configs, Path::new(path), start);
+    }
+
+    if args.add_nolint {
+        return add_nolint(&args, configs, start);
+    }
+
+    // Baselines are loaded once even if several configs use them, so that we
+    // know which of their violations were fixed.
+    let mut baselines: BTreeMap<PathBuf, Baseline> = BTreeMap::new();
+    let mut file_results = Vec::new();
+
+    for config in configs {
+        let baseline_path = args
+            .baseline
+            .as_ref()
+            .map(PathBuf::from)
+            .or_else(|| config.baseline.clone());
+        let results = jarl_core::check::check(config);
+
+        let Some(baseline_path) = baseline_path else {
+            file_results.extend(results);
+            continue;
+        };
+        let baseline = match baselines.entry(baseline_path) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let baseline = Baseline::load(entry.key())?;
+                entry.insert(baseline)
+            }
+        };
+        for (path, result) in results {
+            let result =
+                result.and_then(|diagnostics| baseline.filter(Path::new(&path), diagnostics));
+            file_results.push((path, result));
+        }
+    }
 
     let mut all_errors = Vec::new();
     let mut all_diagnostics = Vec::new();
@@ -79,43 +129,448 @@ pub fn check() -> Result<ExitStatus> {
         }
     }
 
-    // Flatten all diagnostics into a single vector and sort globally
-    let mut all_diagnostics_flat: Vec<&Diagnostic> = all_diagnostics
-        .iter()
-        .flat_map(|(_path, diagnostics)| diagnostics.iter())
-        .collect();
+    emit(&args, &all_diagnostics, &all_errors, HashMap::new())?;
+    report_fixed_violations(baselines.values());
 
-    all_diagnostics_flat.sort();
+    if let Some(start) = start {
+        let duration = start.elapsed();
+        println!("\nChecked files in: {duration:?}");
+    }
 
-    let mut stdout = std::io::stdout();
+    if !all_errors.is_empty() || has_parse_errors(&all_diagnostics) {
+        return Ok(ExitStatus::Error);
+    }
 
-    match args.output_format {
-        OutputFormat::Concise => {
-            ConciseEmitter.emit(&mut stdout, &all_diagnostics_flat, &all_errors)?;
+    if !has_failing_diagnostics(args_fail_on(&args), &all_diagnostics) {
+        return Ok(ExitStatus::Success);
+    }
+
+    Ok(ExitStatus::Failure)
+}
+
+/// Lint (or fix) the code passed via stdin, e.g. `cat foo.R | jarl check -`.
+///
+/// Settings are discovered from `--stdin-filename` if provided, and from the
+/// current directory otherwise. When fixes are applied, the fixed code is
+/// printed to stdout instead of the diagnostics.
+fn check_stdin(args: &CheckCommand, start: Option<Instant>) -> Result<ExitStatus> {
+    if args.files.len() > 1 {
+        return Err(anyhow!(
+            "Cannot pass other paths when reading from stdin with `-`."
+        ));
+    }
+
+    let mut contents = String::new();
+    std::io::stdin().read_to_string(&mut contents)?;
+
+    let path = std::env::current_dir()?.join(args.stdin_filename.as_deref().unwrap_or("-"));
+
+    let mut resolver = PathResolver::new(Settings::default());
+    for DiscoveredSettings { directory, settings } in discover_settings(&[&path])? {
+        resolver.add(&directory, settings);
+    }
+
+    // The code doesn't come from a file, so there's nothing to cache.
+    let mut check_config = args_config(args, vec![path.clone()]);
+    check_config.cache_dir = None;
+    let config = build_config(&check_config, &resolver, vec![path.clone()])?;
+    print_warnings(std::slice::from_ref(&config));
+
+    if args.diff {
+        let (diagnostics, fixed) = jarl_core::check::fix_source(&contents, &path, &config)?;
+        if report_parse_errors(&diagnostics) {
+            return Ok(ExitStatus::Error);
         }
-        OutputFormat::Json => {
-            JsonEmitter.emit(&mut stdout, &all_diagnostics_flat, &all_errors)?;
+        if fixed == contents {
+            return Ok(ExitStatus::Success);
         }
-        OutputFormat::Github => {
-            GithubEmitter.emit(&mut stdout, &all_diagnostics_flat, &all_errors)?;
+        let name = args.stdin_filename.as_deref().unwrap_or("-");
+        print_diff(name, &contents, &fixed);
+        return Ok(ExitStatus::Failure);
+    }
+
+    if args.add_nolint {
+        let (diagnostics, updated) = jarl_core::nolint::add_nolint_source(
+            &contents,
+            &path,
+            &config,
+            args.add_nolint_reason.as_deref(),
+        )?;
+        print!("{updated}");
+
+        if report_parse_errors(&diagnostics) {
+            return Ok(ExitStatus::Error);
         }
-        OutputFormat::Full => {
-            FullEmitter.emit(&mut stdout, &all_diagnostics_flat, &all_errors)?;
+        return Ok(ExitStatus::Success);
+    }
+
+    if config.apply_fixes || config.apply_unsafe_fixes {
+        let (diagnostics, fixed) = jarl_core::check::fix_source(&contents, &path, &config)?;
+        print!("{fixed}");
+
+        if report_parse_errors(&diagnostics) {
+            return Ok(ExitStatus::Error);
+        }
+        let fail_on = args_fail_on(args);
+        if args.fix_only || !diagnostics.iter().any(|d| is_failing(fail_on, d)) {
+            return Ok(ExitStatus::Success);
         }
+        return Ok(ExitStatus::Failure);
     }
 
+    let baseline = args
+        .baseline
+        .as_ref()
+        .map(PathBuf::from)
+        .or_else(|| config.baseline.clone())
+        .map(|baseline_path| Baseline::load(&baseline_path))
+        .transpose()?;
+    let result = match (
+        jarl_core::check::lint_source(&contents, &path, &config),
+        baseline,
+    ) {
+        (Ok(diagnostics), Some(mut baseline)) => {
+            Ok(baseline.filter_source(&path, &contents, diagnostics))
+        }
+        (result, _) => result,
+    };
+
+    let (all_diagnostics, all_errors) = match result {
+        Ok(diagnostics) if diagnostics.is_empty() => (vec![], vec![]),
+        Ok(diagnostics) => (vec![(path.display().to_string(), diagnostics)], vec![]),
+        Err(e) => (vec![], vec![(path.display().to_string(), e)]),
+    };
+
+    emit(
+        args,
+        &all_diagnostics,
+        &all_errors,
+        HashMap::from([(path, contents)]),
+    )?;
+
     if let Some(start) = start {
         let duration = start.elapsed();
         println!("\nChecked files in: {duration:?}");
     }
 
-    if !all_errors.is_empty() {
+    if !all_errors.is_empty() || has_parse_errors(&all_diagnostics) {
         return Ok(ExitStatus::Error);
     }
 
-    if all_diagnostics.is_empty() {
+    if !has_failing_diagnostics(args_fail_on(args), &all_diagnostics) {
         return Ok(ExitStatus::Success);
     }
 
     Ok(ExitStatus::Failure)
 }
+
+/// Print the fixes that would be applied as a unified diff, without modifying
+/// the files.
+///
+/// This returns a failure if some files would be changed, so that it can be
+/// used to check that there are no pending fixes.
+fn check_diff(configs: Vec<Config>, start: Option<Instant>) -> Result<ExitStatus> {
+    let mut file_results = configs
+        .into_iter()
+        .flat_map(jarl_core::check::preview_fixes)
+        .collect::<Vec<_>>();
+    file_results.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut n_changed = 0usize;
+    let mut has_errors = false;
+
+    for (path, result) in file_results {
+        match result {
+            Ok(FixedSource { original, fixed, diagnostics }) => {
+                if report_parse_errors(&diagnostics) {
+                    has_errors = true;
+                } else if original != fixed {
+                    print_diff(&path, &original, &fixed);
+                    n_changed += 1;
+                }
+            }
+            Err(err) => {
+                has_errors = true;
+                eprintln!("{}: {}", "Error".red().bold(), err);
+            }
+        }
+    }
+
+    match n_changed {
+        0 => eprintln!("No fixes to apply."),
+        1 => eprintln!("1 file would be fixed."),
+        n => eprintln!("{n} files would be fixed."),
+    }
+
+    if let Some(start) = start {
+        let duration = start.elapsed();
+        println!("\nChecked files in: {duration:?}");
+    }
+
+    if has_errors {
+        return Ok(ExitStatus::Error);
+    }
+
+    if n_changed > 0 {
+        return Ok(ExitStatus::Failure);
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Store the violations of all files in a new baseline at `path`, so that
+/// they are not reported when checking with `--baseline`.
+fn generate_baseline(
+    configs: Vec<Config>,
+    path: &Path,
+    start: Option<Instant>,
+) -> Result<ExitStatus> {
+    let mut file_results = configs
+        .into_iter()
+        .flat_map(jarl_core::check::check)
+        .collect::<Vec<_>>();
+    file_results.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut baseline = Baseline::new(path);
+    let mut has_errors = false;
+
+    for (file, result) in file_results {
+        match result {
+            Ok(diagnostics) => {
+                has_errors |= report_parse_errors(&diagnostics);
+                baseline.add(Path::new(&file), &diagnostics)?;
+            }
+            Err(err) => {
+                has_errors = true;
+                eprintln!("{}: {}", "Error".red().bold(), err);
+            }
+        }
+    }
+
+    baseline.write()?;
+    match baseline.len() {
+        1 => eprintln!("Wrote 1 violation to '{}'.", path.display()),
+        n => eprintln!("Wrote {n} violations to '{}'.", path.display()),
+    }
+
+    if let Some(start) = start {
+        let duration = start.elapsed();
+        println!("\nChecked files in: {duration:?}");
+    }
+
+    if has_errors {
+        return Ok(ExitStatus::Error);
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Add `# nolint` comments to suppress the violations of all files, and
+/// report the violations that can't be suppressed this way.
+fn add_nolint(
+    args: &CheckCommand,
+    configs: Vec<Config>,
+    start: Option<Instant>,
+) -> Result<ExitStatus> {
+    let mut all_errors = Vec::new();
+    let mut all_diagnostics = Vec::new();
+    let mut n_changed = 0usize;
+
+    let reason = args.add_nolint_reason.as_deref();
+    for (path, result) in configs
+        .into_iter()
+        .flat_map(|config| jarl_core::nolint::add_nolint(config, reason))
+    {
+        match result {
+            Ok(FixedSource { original, fixed, diagnostics }) => {
+                if original != fixed {
+                    n_changed += 1;
+                }
+                if !diagnostics.is_empty() {
+                    all_diagnostics.push((path, diagnostics));
+                }
+            }
+            Err(e) => {
+                all_errors.push((path, e));
+            }
+        }
+    }
+
+    emit(args, &all_diagnostics, &all_errors, HashMap::new())?;
+    match n_changed {
+        0 => eprintln!("No `# nolint` comments to add."),
+        1 => eprintln!("Added `# nolint` comments to 1 file."),
+        n => eprintln!("Added `# nolint` comments to {n} files."),
+    }
+
+    if let Some(start) = start {
+        let duration = start.elapsed();
+        println!("\nChecked files in: {duration:?}");
+    }
+
+    if !all_errors.is_empty() || has_parse_errors(&all_diagnostics) {
+        return Ok(ExitStatus::Error);
+    }
+
+    if !has_failing_diagnostics(args_fail_on(args), &all_diagnostics) {
+        return Ok(ExitStatus::Success);
+    }
+
+    Ok(ExitStatus::Failure)
+}
+
+/// Print the violations of the baselines that were not found anymore, so that
+/// the baselines can be regenerated and shrink over time.
+fn report_fixed_violations<'a>(baselines: impl Iterator<Item = &'a Baseline>) {
+    for baseline in baselines {
+        let fixed = baseline.fixed();
+        if fixed.is_empty() {
+            continue;
+        }
+        let path = baseline.path().display();
+        let summary = match fixed.len() {
+            1 => "1 violation of the baseline".to_string(),
+            n => format!("{n} violations of the baseline"),
+        };
+        eprintln!(
+            "{}: {summary} '{path}' {} fixed. Run `jarl check --generate-baseline {path}` to remove {} from the baseline:",
+            "Warning".yellow().bold(),
+            if fixed.len() == 1 { "was" } else { "were" },
+            if fixed.len() == 1 { "it" } else { "them" },
+        );
+        for (file, rule) in fixed {
+            eprintln!("  {file}: {rule}");
+        }
+    }
+}
+
+/// Print the warnings about the configuration to stderr, once each even if
+/// several configurations have the same warning.
+fn print_warnings(configs: &[Config]) {
+    let mut printed = HashSet::new();
+    for warning in configs.iter().flat_map(|config| &config.warnings) {
+        if printed.insert(warning) {
+            eprintln!("{}: {}", "Warning".yellow().bold(), warning);
+        }
+    }
+}
+
+/// Print the syntax errors found in `diagnostics` to stderr, and return
+/// whether there were any. This is used when the diagnostics themselves are
+/// not displayed, e.g. with `--diff`.
+fn report_parse_errors(diagnostics: &[Diagnostic]) -> bool {
+    let mut found = false;
+    for diagnostic in diagnostics.iter().filter(|d| d.is_parse_error()) {
+        found = true;
+        let (row, col) = diagnostic
+            .location
+            .map(|loc| (loc.row(), loc.column() + 1))
+            .unwrap_or((1, 1));
+        eprintln!(
+            "{}: {}:{row}:{col}: {}",
+            "Error".red().bold(),
+            diagnostic.filename.display(),
+            diagnostic.message.body
+        );
+    }
+    found
+}
+
+/// The minimum severity of the diagnostics that make the check fail. By
+/// default, all diagnostics do.
+fn args_fail_on(args: &CheckCommand) -> Severity {
+    args.fail_on.unwrap_or(Severity::Hint)
+}
+
+fn has_failing_diagnostics(
+    fail_on: Severity,
+    all_diagnostics: &[(String, Vec<Diagnostic>)],
+) -> bool {
+    all_diagnostics
+        .iter()
+        .flat_map(|(_, diagnostics)| diagnostics)
+        .any(|diagnostic| is_failing(fail_on, diagnostic))
+}
+
+/// Does this diagnostic make the check fail? Suppressed violations, which are
+/// only reported with `--show-suppressed`, never do.
+fn is_failing(fail_on: Severity, diagnostic: &Diagnostic) -> bool {
+    !diagnostic.is_suppressed() && diagnostic.severity >= fail_on
+}
+
+fn has_parse_errors(all_diagnostics: &[(String, Vec<Diagnostic>)]) -> bool {
+    all_diagnostics
+        .iter()
+        .flat_map(|(_, diagnostics)| diagnostics)
+        .any(|diagnostic| diagnostic.is_parse_error())
+}
+
+/// Print the changes between `original` and `fixed` as a unified diff.
+fn print_diff(path: &str, original: &str, fixed: &str) {
+    let diff = TextDiff::from_lines(original, fixed);
+    print!("{}", diff.unified_diff().header(path, path));
+}
+
+fn args_config(args: &CheckCommand, files: Vec<PathBuf>) -> ArgsConfig {
+    ArgsConfig {
+        files,
+        fix: args.fix || args.diff,
+        unsafe_fixes: args.unsafe_fixes,
+        fix_only: args.fix_only,
+        select_rules: args.select_rules.clone(),
+        ignore_rules: args.ignore_rules.clone(),
+        min_r_version: args.min_r_version.clone(),
+        allow_dirty: args.allow_dirty,
+        allow_no_vcs: args.allow_no_vcs,
+        assignment_op: args.assignment_op.clone(),
+        cache_dir: if args.no_cache {
+            None
+        } else {
+            Some(match &args.cache_dir {
+                Some(cache_dir) => PathBuf::from(cache_dir),
+                None => std::env::current_dir()
+                    .map(|dir| default_cache_dir(&dir))
+                    .unwrap_or_else(|_| PathBuf::from(DEFAULT_CACHE_DIR)),
+            })
+        },
+        report_unused_nolint: args.report_unused_nolint,
+        show_suppressed: args.show_suppressed,
+        verbose: args.verbose,
+    }
+}
+
+/// Display the diagnostics and errors in the format requested by the user.
+/// `sources` contains the content of files that don't exist on disk.
+fn emit(
+    args: &CheckCommand,
+    all_diagnostics: &[(String, Vec<Diagnostic>)],
+    all_errors: &[(String, anyhow::Error)],
+    sources: HashMap<PathBuf, String>,
+) -> Result<()> {
+    // Flatten all diagnostics into a single vector and sort globally
+    let mut all_diagnostics_flat: Vec<&Diagnostic> = all_diagnostics
+        .iter()
+        .flat_map(|(_path, diagnostics)| diagnostics.iter())
+        .collect();
+
+    all_diagnostics_flat.sort();
+
+    let mut stdout = std::io::stdout();
+
+    match args.output_format {
+        OutputFormat::Concise => {
+            ConciseEmitter.emit(&mut stdout, &all_diagnostics_flat, all_errors)?;
+        }
+        OutputFormat::Json => {
+            JsonEmitter.emit(&mut stdout, &all_diagnostics_flat, all_errors)?;
+        }
+        OutputFormat::Github => {
+            GithubEmitter.emit(&mut stdout, &all_diagnostics_flat, all_errors)?;
+        }
+        OutputFormat::Full => {
+            FullEmitter { sources }.emit(&mut stdout, &all_diagnostics_flat, all_errors)?;
+        }
+    }
+
+    Ok(())
+}
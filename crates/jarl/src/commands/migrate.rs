@@ -0,0 +1,48 @@
+use jarl_core::lintr::migrate_lintr;
+
+use anyhow::{Context, anyhow};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+
+use crate::args::{MigrateCommand, MigrateLintrCommand, MigrateSource};
+use crate::status::ExitStatus;
+
+/// Convert the configuration of another linter, e.g. `jarl migrate lintr`.
+pub(crate) fn migrate(command: MigrateCommand) -> anyhow::Result<ExitStatus> {
+    match command.source {
+        MigrateSource::Lintr(command) => lintr(command),
+    }
+}
+
+/// Convert a `.lintr` file to a `jarl.toml` in the same directory.
+fn lintr(command: MigrateLintrCommand) -> anyhow::Result<ExitStatus> {
+    let path = Path::new(&command.path);
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read '{}'", path.display()))?;
+    let migration = migrate_lintr(&contents)?;
+
+    let output = path.with_file_name("jarl.toml");
+    if output.exists() && !command.force {
+        return Err(anyhow!(
+            "'{}' already exists. Use `--force` to overwrite it.",
+            output.display()
+        ));
+    }
+    fs::write(&output, &migration.toml)
+        .with_context(|| format!("Failed to write '{}'", output.display()))?;
+
+    for warning in &migration.warnings {
+        eprintln!("{}: {}", "Warning".yellow().bold(), warning);
+    }
+    if !migration.unsupported_linters.is_empty() {
+        eprintln!(
+            "{}: These linters don't have an equivalent rule in Jarl: {}.",
+            "Warning".yellow().bold(),
+            migration.unsupported_linters.join(", ")
+        );
+    }
+    eprintln!("Wrote '{}'.", output.display());
+
+    Ok(ExitStatus::Success)
+}
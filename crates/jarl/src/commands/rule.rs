@@ -0,0 +1,132 @@
+use jarl_core::config::format_r_version;
+use jarl_core::diagnostic::Severity;
+use jarl_core::lints::{RULES, RuleId, RuleInfo};
+
+use anyhow::anyhow;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::args::{RuleCommand, RuleOutputFormat, RulesCommand};
+use crate::status::ExitStatus;
+
+/// Metadata of a rule, as printed by `jarl rules --output-format json`.
+#[derive(Serialize)]
+struct RuleMetadata {
+    name: &'static str,
+    categories: Vec<&'static str>,
+    /// One of `"safe"`, `"unsafe"` or `"none"`.
+    fix: &'static str,
+    /// Severity of the diagnostics unless it is changed in `jarl.toml`.
+    default_severity: &'static str,
+    minimum_r_version: Option<String>,
+}
+
+impl RuleMetadata {
+    fn new(rule: &RuleInfo) -> Self {
+        let categories = rule.categories.split(',').collect::<Vec<_>>();
+        Self {
+            name: rule.name,
+            fix: rule.fix_status.as_str(),
+            default_severity: Severity::from_categories(&categories).as_str(),
+            categories,
+            minimum_r_version: rule.minimum_r_version.map(format_r_version),
+        }
+    }
+}
+
+/// Metadata and documentation of a rule, as printed by
+/// `jarl rule <name> --output-format json`.
+#[derive(Serialize)]
+struct RuleDocumentation {
+    #[serde(flatten)]
+    metadata: RuleMetadata,
+    documentation: Option<&'static str>,
+}
+
+/// Print the documentation of a rule, e.g. `jarl rule any_is_na`.
+pub(crate) fn rule(command: RuleCommand) -> anyhow::Result<ExitStatus> {
+    let Some(id) = RuleId::from_name(&command.name) else {
+        return Err(anyhow!(
+            "Unknown rule `{}`. Use `jarl rules` to list all rules.",
+            command.name
+        ));
+    };
+    let rule = id.info();
+    let metadata = RuleMetadata::new(rule);
+    let documentation = id.documentation();
+
+    match command.output_format {
+        RuleOutputFormat::Text => {
+            println!("{}\n", format!("# {}", metadata.name).bold());
+            println!("Categories: {}", metadata.categories.join(", "));
+            println!("Default severity: {}", metadata.default_severity);
+            println!("Fix: {}", metadata.fix);
+            println!(
+                "Minimum R version: {}",
+                metadata.minimum_r_version.as_deref().unwrap_or("none")
+            );
+            if let Some(version) = &metadata.minimum_r_version {
+                println!(
+                    "This rule is disabled if the minimum R version of the project is unknown or lower than {version}. Set it with `--min-r-version`, with `min-r-version` in 'jarl.toml', or with `Depends` in DESCRIPTION."
+                );
+            }
+            println!();
+            match documentation {
+                Some(documentation) => println!("{documentation}"),
+                None => println!("This rule is not documented yet."),
+            }
+        }
+        RuleOutputFormat::Json => {
+            let output = RuleDocumentation { metadata, documentation };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Print the list of all rules and their metadata, e.g. `jarl rules`.
+pub(crate) fn rules(command: RulesCommand) -> anyhow::Result<ExitStatus> {
+    let rules = RULES.iter().map(RuleMetadata::new).collect::<Vec<_>>();
+
+    match command.output_format {
+        RuleOutputFormat::Text => {
+            let rows = rules
+                .iter()
+                .map(|rule| {
+                    [
+                        rule.name.to_string(),
+                        rule.categories.join(","),
+                        rule.fix.to_string(),
+                        rule.default_severity.to_string(),
+                        rule.minimum_r_version.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            let header =
+                ["Name", "Categories", "Fix", "Severity", "Minimum R version"].map(String::from);
+
+            let mut widths = header.each_ref().map(|column| column.len());
+            for row in &rows {
+                for (width, column) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(column.len());
+                }
+            }
+
+            for row in std::iter::once(&header).chain(&rows) {
+                let line = row
+                    .iter()
+                    .zip(widths)
+                    .map(|(column, width)| format!("{column:width$}"))
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                println!("{}", line.trim_end());
+            }
+        }
+        RuleOutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&rules)?);
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
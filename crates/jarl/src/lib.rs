@@ -19,6 +19,9 @@ pub fn run(args: Args) -> anyhow::Result<ExitStatus> {
 
     match args.command {
         Command::Check(_command) => commands::check::check(),
+        Command::Migrate(command) => commands::migrate::migrate(command),
+        Command::Rule(command) => commands::rule::rule(command),
+        Command::Rules(command) => commands::rule::rules(command),
         Command::Server(command) => commands::server::server(command),
     }
 }
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::Write;
 
-use jarl_core::diagnostic::Diagnostic;
+use jarl_core::diagnostic::{Diagnostic, Severity};
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
 pub enum OutputFormat {
@@ -49,12 +49,7 @@ impl Emitter for ConciseEmitter {
         // First, print all parsing errors
         if !errors.is_empty() {
             for (_path, err) in errors {
-                let root_cause = err.chain().last().unwrap();
-                if root_cause.is::<jarl_core::error::ParseError>() {
-                    eprintln!("{}: {}", "Error".red().bold(), root_cause);
-                } else {
-                    eprintln!("{}: {}", "Error".red().bold(), err);
-                }
+                eprintln!("{}: {}", "Error".red().bold(), err);
             }
         }
 
@@ -169,9 +164,15 @@ impl Emitter for GithubEmitter {
             // - one after the "::" marker: this is so that the workflow shows
             //   the location of diagnostics when we inspect the workflow itself,
             //   without the Github annotations.
+            // GitHub only has three levels of annotations.
+            let level = match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info | Severity::Hint => "notice",
+            };
             write!(
                 writer,
-                "::warning title=Jarl ({}),file={file},line={row},col={col}::{file}:{row}:{col} ",
+                "::{level} title=Jarl ({}),file={file},line={row},col={col}::{file}:{row}:{col} ",
                 diagnostic.message.name,
                 file = diagnostic.filename.to_string_lossy()
             )?;
@@ -188,7 +189,12 @@ impl Emitter for GithubEmitter {
     }
 }
 
-pub struct FullEmitter;
+#[derive(Default)]
+pub struct FullEmitter {
+    /// Contents of files that don't live on disk (e.g. code passed via stdin).
+    /// Those take precedence over the content of the file on disk.
+    pub sources: std::collections::HashMap<std::path::PathBuf, String>,
+}
 
 impl Emitter for FullEmitter {
     fn emit<W: Write>(
@@ -210,12 +216,7 @@ impl Emitter for FullEmitter {
         // First, print all parsing errors
         if !errors.is_empty() {
             for (_path, err) in errors {
-                let root_cause = err.chain().last().unwrap();
-                if root_cause.is::<jarl_core::error::ParseError>() {
-                    eprintln!("{}: {}", "Error".red().bold(), root_cause);
-                } else {
-                    eprintln!("{}: {}", "Error".red().bold(), err);
-                }
+                eprintln!("{}: {}", "Error".red().bold(), err);
             }
             if !diagnostics.is_empty() {
                 eprintln!(); // Add separator between errors and diagnostics
@@ -243,7 +244,11 @@ impl Emitter for FullEmitter {
             };
 
             // Read the source file
-            let source = match fs::read_to_string(&diagnostic.filename) {
+            let source = match self.sources.get(&diagnostic.filename) {
+                Some(content) => Ok(content.clone()),
+                None => fs::read_to_string(&diagnostic.filename),
+            };
+            let source = match source {
                 Ok(content) => content,
                 Err(e) => {
                     eprintln!(
@@ -262,20 +267,25 @@ impl Emitter for FullEmitter {
             // Create the snippet with annotate-snippets
             let file_path = relativize_path(diagnostic.filename.clone());
 
+            let level = match diagnostic.severity {
+                Severity::Error => Level::Error,
+                Severity::Warning => Level::Warning,
+                Severity::Info => Level::Info,
+                Severity::Hint => Level::Note,
+            };
+
             // Build the message with snippet
             let snippet = Snippet::source(&source)
                 .origin(&file_path)
                 .fold(true)
                 .annotation(
-                    Level::Warning
+                    level
                         .span(start_offset..end_offset)
                         .label(&diagnostic.message.body),
                 );
 
             // Create the main message
-            let mut message = Level::Warning
-                .title(&diagnostic.message.name)
-                .snippet(snippet);
+            let mut message = level.title(&diagnostic.message.name).snippet(snippet);
 
             // Add suggestion as a footer message if present
             if let Some(suggestion_text) = &diagnostic.message.suggestion {
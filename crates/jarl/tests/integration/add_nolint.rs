@@ -0,0 +1,207 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_add_nolint() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("test.R"),
+        "any(is.na(x))\nany(duplicated(x)) # nolint: coalesce\nany(\n  is.na(y)\n)\nx <- 1\n",
+    )?;
+    std::fs::write(directory.join("clean.R"), "x <- 1\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--add-nolint")
+            .arg("--allow-no-vcs")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    assert_eq!(
+        std::fs::read_to_string(directory.join("test.R"))?,
+        "any(is.na(x)) # nolint: any_is_na\nany(duplicated(x)) # nolint: coalesce, any_duplicated\nany(\n  is.na(y)\n) # nolint: any_is_na\nx <- 1\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(directory.join("clean.R"))?,
+        "x <- 1\n"
+    );
+
+    // All violations are suppressed
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    // Nothing left to add
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--add-nolint")
+            .arg("--allow-no-vcs")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_add_nolint_reports_nested_violations() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    // The trailing comment would be attached to the assignment, not to the
+    // `any()` call, which starts on another line, so it wouldn't suppress the
+    // violation.
+    std::fs::write(directory.join("test.R"), "y <- any(\n  is.na(x)\n)\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--add-nolint")
+            .arg("--allow-no-vcs")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    assert_eq!(
+        std::fs::read_to_string(directory.join("test.R"))?,
+        "y <- any(\n  is.na(x)\n)\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_add_nolint_with_reason() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nrequire-nolint-reason = true\n",
+    )?;
+    std::fs::write(directory.join("test.R"), "any(is.na(x))\n")?;
+
+    // Without a reason, the new comments are reported
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--add-nolint")
+            .arg("--allow-no-vcs")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    assert_eq!(
+        std::fs::read_to_string(directory.join("test.R"))?,
+        "any(is.na(x)) # nolint: any_is_na\n"
+    );
+
+    std::fs::write(directory.join("test.R"), "any(is.na(x))\n")?;
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--add-nolint")
+            .arg("--add-nolint-reason")
+            .arg("to fix later")
+            .arg("--allow-no-vcs")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    assert_eq!(
+        std::fs::read_to_string(directory.join("test.R"))?,
+        "any(is.na(x)) # nolint: any_is_na -- to fix later\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_add_nolint_requires_vcs() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), "any(is.na(x))\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--add-nolint")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    assert_eq!(
+        std::fs::read_to_string(directory.join("test.R"))?,
+        "any(is.na(x))\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_add_nolint_stdin() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg("-")
+            .arg("--add-nolint")
+            .run_with_stdin("any(is.na(x))\n")
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_add_nolint_conflicts_with_fix() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), "any(is.na(x))\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--add-nolint")
+            .arg("--fix")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    Ok(())
+}
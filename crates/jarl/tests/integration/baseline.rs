@@ -0,0 +1,145 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_baseline() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("test.R"),
+        "any(is.na(x))\nany(duplicated(x))\n",
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--generate-baseline")
+            .arg("jarl-baseline.json")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    let baseline: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(
+        directory.join("jarl-baseline.json"),
+    )?)?;
+    assert_eq!(baseline["version"], 1);
+    assert_eq!(baseline["files"]["test.R"].as_array().unwrap().len(), 2);
+
+    // Known violations are not reported, even if they moved.
+    std::fs::write(
+        directory.join("test.R"),
+        "x <- 1\n\nany(is.na(x))\nany(duplicated(x))\nany(is.na(y))\n",
+    )?;
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--baseline")
+            .arg("jarl-baseline.json")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    // Fixed violations are reported.
+    std::fs::write(directory.join("test.R"), "any(is.na(x))\n")?;
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--baseline")
+            .arg("jarl-baseline.json")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_baseline_in_toml() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::create_dir_all(directory.join("R"))?;
+    std::fs::write(directory.join("R/test.R"), "any(is.na(x))\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--generate-baseline")
+            .arg("jarl-baseline.json")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nbaseline = \"jarl-baseline.json\"\n",
+    )?;
+
+    // The baseline is resolved relative to `jarl.toml`, and the paths it
+    // contains are relative to the baseline.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory.join("R"))
+            .arg("check")
+            .arg("test.R")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_baseline() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), "any(is.na(x))\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--baseline")
+            .arg("missing.json")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--fix")
+            .arg("--generate-baseline")
+            .arg("jarl-baseline.json")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
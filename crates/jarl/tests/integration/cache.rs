@@ -0,0 +1,172 @@
+use std::process::Command;
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_cache_is_created_and_reused() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let test_path = "test.R";
+    std::fs::write(directory.join(test_path), "any(is.na(x))\n")?;
+
+    let first = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--output-format")
+        .arg("concise")
+        .run();
+
+    assert!(directory.join(".jarl_cache").is_dir());
+    assert!(first.stdout.contains("any_is_na"));
+
+    let second = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--output-format")
+        .arg("concise")
+        .run();
+
+    assert_eq!(first.status.code(), second.status.code());
+    assert_eq!(first.stdout, second.stdout);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_is_invalidated_by_changes() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let test_path = "test.R";
+    std::fs::write(directory.join(test_path), "any(is.na(x))\n")?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--output-format")
+        .arg("concise")
+        .run();
+    assert!(output.stdout.contains("any_is_na"));
+
+    std::fs::write(directory.join(test_path), "anyNA(x)\n")?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .run();
+    assert!(output.status.success());
+    assert!(output.stdout.contains("All checks passed!"));
+
+    // Changing the rules also invalidates the cache.
+    std::fs::write(directory.join(test_path), "any(is.na(x))\n")?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--ignore-rules")
+        .arg("any_is_na")
+        .run();
+    assert!(output.status.success());
+    assert!(output.stdout.contains("All checks passed!"));
+
+    Ok(())
+}
+
+#[test]
+fn test_no_cache() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let test_path = "test.R";
+    std::fs::write(directory.join(test_path), "any(is.na(x))\n")?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--no-cache")
+        .run();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!directory.join(".jarl_cache").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_in_project_root() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("jarl.toml"), "[lint]\n")?;
+    std::fs::create_dir(directory.join("R"))?;
+    std::fs::write(directory.join("R").join("test.R"), "any(is.na(x))\n")?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory.join("R"))
+        .arg("check")
+        .arg(".")
+        .run();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(directory.join(".jarl_cache").is_dir());
+    assert!(!directory.join("R").join(".jarl_cache").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_dir() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let test_path = "test.R";
+    std::fs::write(directory.join(test_path), "any(is.na(x))\n")?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--cache-dir")
+        .arg("custom_cache")
+        .run();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(directory.join("custom_cache").is_dir());
+    assert!(!directory.join(".jarl_cache").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_not_used_when_fixing() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let test_path = "test.R";
+    std::fs::write(directory.join(test_path), "any(is.na(x))\n")?;
+
+    let _ = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--fix")
+        .arg("--allow-no-vcs")
+        .run();
+
+    assert!(!directory.join(".jarl_cache").exists());
+    assert_eq!(
+        std::fs::read_to_string(directory.join(test_path))?,
+        "anyNA(x)\n"
+    );
+
+    Ok(())
+}
@@ -171,6 +171,35 @@ any(duplicated(x))
     Ok(())
 }
 
+#[test]
+fn test_nolint_lintr_lines() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let test_path = "test.R";
+    std::fs::write(
+        directory.join(test_path),
+        "
+# nolint next
+x <- any(is.na(y))
+x <- any(is.na(y)) # nolint: any_is_na_linter.
+# nolint next: any_duplicated_linter.
+any(is.na(x))
+",
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_nolint_skip_file() -> anyhow::Result<()> {
     let directory = TempDir::new()?;
@@ -0,0 +1,108 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_config_in_description() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::create_dir_all(directory.join("R"))?;
+    std::fs::write(
+        directory.join("DESCRIPTION"),
+        r#"Package: mypackage
+Version: 1.0.0
+Config/jarl/select: any_is_na,
+    any_duplicated
+Config/jarl/exclude: R/excluded.R
+"#,
+    )?;
+    let code = "any(is.na(x))\nany(duplicated(x))\nlength(levels(x))\n";
+    std::fs::write(directory.join("R/a.R"), code)?;
+    std::fs::write(directory.join("R/excluded.R"), code)?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--output-format")
+        .arg("concise")
+        .run();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.contains("a.R [1:1] any_is_na"));
+    assert!(output.stdout.contains("a.R [2:1] any_duplicated"));
+    assert!(!output.stdout.contains("length_levels"));
+    assert!(!output.stdout.contains("excluded.R"));
+
+    // `jarl.toml` takes precedence over DESCRIPTION.
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nselect = [\"length_levels\"]\n",
+    )?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--output-format")
+        .arg("concise")
+        .run();
+
+    assert!(output.stdout.contains("length_levels"));
+    assert!(!output.stdout.contains("any_is_na"));
+    assert!(output.stdout.contains("excluded.R"));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_config_in_description() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), "any(is.na(x))\n")?;
+    std::fs::write(
+        directory.join("DESCRIPTION"),
+        "Package: mypackage\nConfig/jarl/select: not_a_rule\n",
+    )?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .run();
+
+    assert_eq!(output.status.code(), Some(255));
+    assert!(
+        output
+            .stderr
+            .contains("Unknown rules in field `Config/jarl/select` in DESCRIPTION: not_a_rule")
+    );
+
+    std::fs::write(
+        directory.join("DESCRIPTION"),
+        "Package: mypackage\nConfig/jarl/selected: any_is_na\n",
+    )?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .run();
+
+    // Unknown fields fail like invalid ones.
+    assert_eq!(output.status.code(), Some(255));
+    assert!(output.stderr.contains("Invalid `Config/jarl/*` fields in"));
+    assert!(
+        output
+            .stderr
+            .contains("Unknown field `Config/jarl/selected` in DESCRIPTION")
+    );
+    assert!(!output.stdout.contains("any_is_na"));
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use std::process::Command;
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_diff_does_not_modify_files() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let test_path = "test.R";
+    let test_contents = "x <- 1\nany(is.na(x))\n";
+    std::fs::write(directory.join(test_path), test_contents)?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--diff")
+        .run();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        output.stdout,
+        "--- test.R\n+++ test.R\n@@ -1,2 +1,2 @@\n x <- 1\n-any(is.na(x))\n+anyNA(x)\n"
+    );
+    assert!(output.stderr.contains("1 file would be fixed."));
+    assert_eq!(
+        std::fs::read_to_string(directory.join(test_path))?,
+        test_contents
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_diff_no_changes() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    // `for_loop_index` has no fix.
+    let test_path = "test.R";
+    std::fs::write(directory.join(test_path), "for (x in x) {}\n")?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--diff")
+        .run();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(output.stderr.contains("No fixes to apply."));
+
+    Ok(())
+}
+
+#[test]
+fn test_diff_unsafe_fixes() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let test_path = "test.R";
+    let test_contents = "!all.equal(x, y)\n";
+    std::fs::write(directory.join(test_path), test_contents)?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--diff")
+        .run();
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--diff")
+        .arg("--unsafe-fixes")
+        .run();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.contains("-!all.equal(x, y)"));
+    assert!(output.stdout.contains("+!isTRUE(all.equal(x, y))"));
+    assert_eq!(
+        std::fs::read_to_string(directory.join(test_path))?,
+        test_contents
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_diff_does_not_require_vcs() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let test_path = "test.R";
+    std::fs::write(directory.join(test_path), "any(is.na(x))\n")?;
+
+    // No `--allow-no-vcs` needed since files are not modified.
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--diff")
+        .run();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!output.stderr.contains("Error"));
+
+    Ok(())
+}
+
+#[test]
+fn test_diff_stdin() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg("-")
+        .arg("--diff")
+        .arg("--stdin-filename")
+        .arg("foo.R")
+        .run_with_stdin("any(is.na(x))\n");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        output.stdout,
+        "--- foo.R\n+++ foo.R\n@@ -1 +1 @@\n-any(is.na(x))\n+anyNA(x)\n"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+const RMD: &str = r#"---
+title: "Report"
+---
+
+Some text with `r any(is.na(x))`.
+
+```{r}
+x <- 1
+any(is.na(x))
+```
+
+```{r, eval = FALSE}
+any(is.na(x))
+```
+
+```{python}
+any(is.na(x))
+```
+
+```{r}
+  y <- any(is.na(x))
+```
+"#;
+
+#[test]
+fn test_markdown_chunks_are_checked() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("report.Rmd"), RMD)?;
+    std::fs::write(directory.join("report.qmd"), RMD)?;
+
+    // Lines and columns are the ones of the document.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fix_markdown_chunks() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("report.Rmd"), RMD)?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--fix")
+            .arg("--allow-no-vcs")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    // Only the evaluated R chunks are modified.
+    let fixed = std::fs::read_to_string(directory.join("report.Rmd"))?;
+    assert_eq!(
+        fixed,
+        RMD.replacen(
+            "```{r}\nx <- 1\nany(is.na(x))",
+            "```{r}\nx <- 1\nanyNA(x)",
+            1
+        )
+        .replace("  y <- any(is.na(x))", "  y <- anyNA(x)")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sweave_chunks_are_checked() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("report.Rnw"),
+        r#"\documentclass{article}
+\begin{document}
+<<setup>>=
+any(is.na(x))
+@
+<<eval=FALSE>>=
+any(is.na(x))
+@
+\end{document}
+"#,
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_document_extensions() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("report.Rmd"), RMD)?;
+    std::fs::write(directory.join("report.qmd"), RMD)?;
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint]
+document-extensions = ["qmd"]
+"#,
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint]
+document-extensions = ["md"]
+"#,
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
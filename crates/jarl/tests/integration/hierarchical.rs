@@ -0,0 +1,220 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+/// Two packages with their own `jarl.toml`, and a root `jarl.toml` for the
+/// rest of the repository.
+fn create_monorepo(directory: &std::path::Path) -> anyhow::Result<()> {
+    let code = "any(is.na(x))\nany(duplicated(x))\n";
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint]
+select = ["any_is_na"]
+"#,
+    )?;
+    std::fs::write(directory.join("script.R"), code)?;
+
+    std::fs::create_dir_all(directory.join("pkgA/R"))?;
+    std::fs::write(
+        directory.join("pkgA/jarl.toml"),
+        r#"
+[lint]
+select = ["any_duplicated"]
+"#,
+    )?;
+    std::fs::write(directory.join("pkgA/R/a.R"), code)?;
+
+    std::fs::create_dir_all(directory.join("pkgB/R"))?;
+    std::fs::write(
+        directory.join("pkgB/jarl.toml"),
+        r#"
+[lint]
+exclude = ["R/excluded.R"]
+"#,
+    )?;
+    std::fs::write(directory.join("pkgB/R/b.R"), code)?;
+    std::fs::write(directory.join("pkgB/R/excluded.R"), code)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_nearest_toml_is_used() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+    create_monorepo(directory)?;
+
+    // Exclude patterns are relative to the directory of their `jarl.toml`,
+    // and there is no `select` in the `jarl.toml` of pkgB so all rules are
+    // used there.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_nearest_toml_is_used_for_subdirectory() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+    create_monorepo(directory)?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory.join("pkgA"))
+            .arg("check")
+            .arg("R")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_extend_parent_toml() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint]
+select = ["any_is_na", "any_duplicated", "length_levels"]
+ignore = ["any_is_na"]
+"#,
+    )?;
+
+    std::fs::create_dir_all(directory.join("pkg/R"))?;
+    std::fs::write(
+        directory.join("pkg/jarl.toml"),
+        r#"
+extend = "../jarl.toml"
+
+[lint]
+ignore = ["any_duplicated"]
+"#,
+    )?;
+    std::fs::write(
+        directory.join("pkg/R/a.R"),
+        "any(is.na(x))\nany(duplicated(x))\nlength(levels(x))\n",
+    )?;
+
+    // `select` is inherited and both `ignore` are combined.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_extend_cycle() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("jarl.toml"), "extend = \"pkg/jarl.toml\"\n")?;
+    std::fs::create_dir_all(directory.join("pkg"))?;
+    std::fs::write(
+        directory.join("pkg/jarl.toml"),
+        "extend = \"../jarl.toml\"\n",
+    )?;
+    std::fs::write(directory.join("pkg/a.R"), "any(is.na(x))\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_nested_settings_in_excluded_directories() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nexclude = [\"vendor/\"]\n",
+    )?;
+    std::fs::write(directory.join("test.R"), "any(is.na(x))\n")?;
+
+    // Those would make the run fail if they were read.
+    std::fs::create_dir_all(directory.join("renv/library/pkg"))?;
+    std::fs::write(directory.join("renv/library/pkg/jarl.toml"), "[lint\n")?;
+    std::fs::create_dir_all(directory.join("vendor/pkg"))?;
+    std::fs::write(directory.join("vendor/pkg/jarl.toml"), "[lint\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_description_in_subdirectory() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::create_dir_all(directory.join("pkg/R"))?;
+    std::fs::write(
+        directory.join("pkg/DESCRIPTION"),
+        "Package: pkg\nConfig/jarl/selected: any_duplicated\n",
+    )?;
+    std::fs::write(directory.join("pkg/R/a.R"), "any(is.na(x))\n")?;
+
+    // Invalid `Config/jarl/*` fields fail the run, like an invalid `jarl.toml`.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
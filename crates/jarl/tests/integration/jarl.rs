@@ -340,6 +340,40 @@ fn test_fix_options() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_unsafe_fixes_are_reported_but_not_applied() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let test_path = "test.R";
+    let test_contents = "any(is.na(x))\n!all.equal(x, y)\n";
+    std::fs::write(directory.join(test_path), test_contents)?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--fix")
+        .arg("--allow-no-vcs")
+        .run();
+
+    // The safe fix is applied, the unsafe one is still reported.
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.contains("all_equal"));
+    assert!(!output.stdout.contains("any_is_na"));
+    assert!(
+        output
+            .stdout
+            .contains("1 fix is available with the `--fix --unsafe-fixes` option.")
+    );
+    assert_eq!(
+        std::fs::read_to_string(directory.join(test_path))?,
+        "anyNA(x)\n!all.equal(x, y)\n"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_safe_and_unsafe_lints() -> anyhow::Result<()> {
     let directory = TempDir::new()?;
@@ -6,14 +6,30 @@
 /// Resolves problems with:
 /// - Compilation times, by only having 1 integration test binary
 /// - Dead code analysis of integration test helpers https://github.com/rust-lang/rust/issues/46379
+mod add_nolint;
 mod allow_dirty;
 mod allow_no_vcs;
 mod assignment_op;
+mod baseline;
+mod cache;
 mod comments;
+mod description;
+mod diff;
+mod documents;
 mod help;
 mod helpers;
+mod hierarchical;
 mod jarl;
+mod migrate;
 mod min_r_version;
+mod nolint_reason;
 mod output_format;
+mod per_file_ignores;
+mod roxygen;
+mod rule_command;
+mod rule_options;
 mod rules;
+mod severity;
+mod stdin;
 mod toml;
+mod unused_nolint;
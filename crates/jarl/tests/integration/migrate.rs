@@ -0,0 +1,109 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_migrate_lintr() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join(".lintr"),
+        r#"linters: linters_with_defaults(
+    any_is_na_linter(),
+    object_name_linter = NULL
+  )
+exclusions: list("R/generated.R", "tests/testthat" = list(any_is_na_linter = Inf))
+encoding: "UTF-8"
+"#,
+    )?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("migrate")
+        .arg("lintr")
+        .run();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.contains("Wrote 'jarl.toml'."));
+    assert!(
+        output
+            .stderr
+            .contains("These linters don't have an equivalent rule in Jarl: brace_linter,")
+    );
+    assert_eq!(
+        std::fs::read_to_string(directory.join("jarl.toml"))?,
+        r#"[lint]
+select = ["any_is_na", "assignment", "equals_na", "true_false_symbol"]
+exclude = ["R/generated.R"]
+
+[lint.per-file-ignores]
+"tests/testthat" = ["any_is_na"]
+"#
+    );
+
+    // The new `jarl.toml` is valid
+    std::fs::write(directory.join("test.R"), "any(duplicated(x))\n")?;
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .run();
+    assert_eq!(output.status.code(), Some(0));
+    Ok(())
+}
+
+#[test]
+fn test_migrate_lintr_does_not_overwrite() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join(".lintr"),
+        "linters: list(any_is_na_linter())\n",
+    )?;
+    std::fs::write(directory.join("jarl.toml"), "[lint]\n")?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("migrate")
+        .arg("lintr")
+        .run();
+    assert_eq!(output.status.code(), Some(255));
+    assert!(output.stderr.contains("already exists"));
+    assert_eq!(
+        std::fs::read_to_string(directory.join("jarl.toml"))?,
+        "[lint]\n"
+    );
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("migrate")
+        .arg("lintr")
+        .arg("--force")
+        .run();
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        std::fs::read_to_string(directory.join("jarl.toml"))?,
+        "[lint]\nselect = [\"any_is_na\"]\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_migrate_lintr_missing_file() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("migrate")
+        .arg("lintr")
+        .run();
+    assert_eq!(output.status.code(), Some(255));
+    assert!(output.stderr.contains("Failed to read '.lintr'"));
+    assert!(!directory.join("jarl.toml").exists());
+    Ok(())
+}
@@ -24,6 +24,7 @@ fn test_min_r_version_from_cli_only() -> anyhow::Result<()> {
             .arg(".")
             .run()
             .normalize_os_executable_name()
+            .normalize_temp_paths()
     );
 
     // This should not report a lint (the project could be using 4.4.0 so
@@ -37,6 +38,7 @@ fn test_min_r_version_from_cli_only() -> anyhow::Result<()> {
             .arg("4.4.0")
             .run()
             .normalize_os_executable_name()
+            .normalize_temp_paths()
     );
     // This should report a lint.
     insta::assert_snapshot!(
@@ -48,6 +50,7 @@ fn test_min_r_version_from_cli_only() -> anyhow::Result<()> {
             .arg("4.6.0")
             .run()
             .normalize_os_executable_name()
+            .normalize_temp_paths()
     );
 
     Ok(())
@@ -79,6 +82,7 @@ Depends: R (>= 4.4.0), utils, stats"#,
             .arg(".")
             .run()
             .normalize_os_executable_name()
+            .normalize_temp_paths()
     );
 
     // This should report a lint.
@@ -95,6 +99,149 @@ Depends: R (>= 4.6.0), utils, stats"#,
             .arg(".")
             .run()
             .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_min_r_version_from_toml() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), "grep('a', x, value = TRUE)")?;
+    std::fs::write(
+        directory.join("DESCRIPTION"),
+        "Package: mypackage\nDepends: R (>= 4.4.0)\n",
+    )?;
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nmin-r-version = \"4.5\"\n",
+    )?;
+
+    // `jarl.toml` takes precedence over DESCRIPTION.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    // The CLI takes precedence over `jarl.toml`.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--min-r-version")
+            .arg("4.4")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nmin-r-version = \"4.x\"\n",
+    )?;
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_min_r_version_from_description_in_parent() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::create_dir_all(directory.join("R"))?;
+    std::fs::write(directory.join("R/test.R"), "grep('a', x, value = TRUE)")?;
+    std::fs::write(
+        directory.join("DESCRIPTION"),
+        "Package: mypackage\nDepends: R (>= 4.5.0)\n",
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory.join("R"))
+            .arg("check")
+            .arg("test.R")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_warning_for_rules_disabled_by_r_version() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), "grep('a', x, value = TRUE)")?;
+
+    // Unknown R version.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--select-rules")
+            .arg("grepv,any_is_na")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    // R version that is too old.
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nselect = [\"grepv\"]\nmin-r-version = \"4.4\"\n",
+    )?;
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    // Rules that are selected through their group are summarized in a single
+    // warning, only with `--verbose`.
+    std::fs::write(directory.join("jarl.toml"), "[lint]\nselect = [\"READ\"]\n")?;
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--verbose")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
     );
 
     Ok(())
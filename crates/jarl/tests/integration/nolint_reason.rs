@@ -0,0 +1,116 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_nolint_reason_not_required_by_default() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("test.R"),
+        "any(is.na(x)) # nolint: any_is_na\n",
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_require_nolint_reason() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nrequire-nolint-reason = true\n",
+    )?;
+    std::fs::write(
+        directory.join("test.R"),
+        "any(is.na(x)) # nolint: any_is_na\nany(is.na(y)) # nolint: any_is_na -- NA are removed upstream\n# nolint start -- vendored code\nany(is.na(z))\n# nolint end\n",
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_show_suppressed() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("test.R"),
+        "any(is.na(x)) # nolint: any_is_na -- NA are removed upstream\n# nolint next\nany(is.na(y))\n",
+    )?;
+
+    // Suppressed violations don't make the check fail
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--show-suppressed")
+            .arg("--output-format")
+            .arg("json")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_show_suppressed_requires_json() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), "any(is.na(x)) # nolint\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--show-suppressed")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--show-suppressed")
+            .arg("--fix")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_per_file_ignores() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint]
+select = ["any_is_na", "any_duplicated", "length_levels"]
+
+[lint.per-file-ignores]
+"tests/**" = ["any_is_na"]
+"data-raw/*.R" = ["PERF"]
+"#,
+    )?;
+
+    let code = "any(is.na(x))\nany(duplicated(x))\nlength(levels(x))\n";
+    std::fs::create_dir_all(directory.join("R"))?;
+    std::fs::create_dir_all(directory.join("tests/testthat"))?;
+    std::fs::create_dir_all(directory.join("data-raw"))?;
+    std::fs::write(directory.join("R/a.R"), code)?;
+    std::fs::write(directory.join("tests/testthat/test-a.R"), code)?;
+    std::fs::write(directory.join("data-raw/b.R"), code)?;
+
+    // Groups of rules are expanded: `any_is_na` and `any_duplicated` are in
+    // PERF but `length_levels` isn't.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_per_file_ignores_relative_to_toml() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::create_dir_all(directory.join("pkg/tests"))?;
+    std::fs::write(
+        directory.join("pkg/jarl.toml"),
+        r#"
+[lint.per-file-ignores]
+"tests/" = ["any_is_na"]
+"#,
+    )?;
+    std::fs::write(directory.join("pkg/tests/a.R"), "any(is.na(x))\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_per_file_ignores_from_extended_file() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    // The patterns of the extended file are relative to its own directory.
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint.per-file-ignores]
+"pkg/tests/" = ["any_is_na"]
+"#,
+    )?;
+    std::fs::create_dir_all(directory.join("pkg/R"))?;
+    std::fs::create_dir_all(directory.join("pkg/tests"))?;
+    std::fs::write(
+        directory.join("pkg/jarl.toml"),
+        "extend = \"../jarl.toml\"\n",
+    )?;
+    std::fs::write(directory.join("pkg/R/a.R"), "any(is.na(x))\n")?;
+    std::fs::write(directory.join("pkg/tests/a.R"), "any(is.na(x))\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg("pkg")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_per_file_ignores_unknown_rule() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint.per-file-ignores]
+"tests/**" = ["not_a_rule"]
+"#,
+    )?;
+    std::fs::write(directory.join("test.R"), "any(is.na(x))\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
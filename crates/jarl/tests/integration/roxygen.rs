@@ -0,0 +1,151 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+const CODE: &str = r#"#' Title
+#'
+#' @examples
+#' any(is.na(x))
+#' \dontrun{
+#' y <- x \%in\% 1
+#' if (any(is.na(y))) 1
+#' }
+#' @export
+f <- function(x) x
+"#;
+
+#[test]
+fn test_roxygen_examples_are_opt_in() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), CODE)?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_roxygen_examples() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), CODE)?;
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nroxygen-examples = true\n",
+    )?;
+
+    // Diagnostics are located on the comment lines.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--fix")
+            .arg("--allow-no-vcs")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(directory.join("test.R"))?,
+        CODE.replace("any(is.na(x))", "anyNA(x)")
+            .replace("any(is.na(y))", "anyNA(y)")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_roxygen_examples_parse_error() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("test.R"),
+        "#' @examples\n#' f(\nf <- function(x) x\n",
+    )?;
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nroxygen-examples = true\n",
+    )?;
+
+    // Broken examples are reported like syntax errors in the code.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_roxygen_examples_fix_escaped_percent() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("test.R"),
+        "#' @examples\n#' any(is.na(x \\%in\\% y))\n#' if (is.null(x)) y else x\nf <- 1\n",
+    )?;
+    std::fs::write(
+        directory.join("jarl.toml"),
+        "[lint]\nroxygen-examples = true\n",
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--fix")
+            .arg("--allow-no-vcs")
+            .arg("--min-r-version")
+            .arg("4.4")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    // `%` are escaped in the fixed examples, whether they were already in the
+    // code or added by the fix.
+    assert_eq!(
+        std::fs::read_to_string(directory.join("test.R"))?,
+        "#' @examples\n#' anyNA(x \\%in\\% y)\n#' x \\%||\\% y\nf <- 1\n"
+    );
+
+    Ok(())
+}
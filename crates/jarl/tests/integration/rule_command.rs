@@ -0,0 +1,92 @@
+use std::process::Command;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_rule() {
+    let output = Command::new(binary_path()).arg("rule").arg("grepv").run();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.starts_with("# grepv\n"));
+    assert!(output.stdout.contains("Categories: READ\n"));
+    assert!(output.stdout.contains("Fix: safe\n"));
+    assert!(output.stdout.contains("Minimum R version: 4.5.0\n"));
+    assert!(output.stdout.contains(
+        "This rule is disabled if the minimum R version of the project is unknown or lower than 4.5.0."
+    ));
+    assert!(output.stdout.contains("## What it does"));
+    assert!(output.stdout.contains("## Why is this bad?"));
+    assert!(!output.stdout.contains("///"));
+}
+
+#[test]
+fn test_rule_json() -> anyhow::Result<()> {
+    let output = Command::new(binary_path())
+        .arg("rule")
+        .arg("any_is_na")
+        .arg("--output-format")
+        .arg("json")
+        .run();
+
+    assert_eq!(output.status.code(), Some(0));
+    let rule: serde_json::Value = serde_json::from_str(&output.stdout)?;
+    assert_eq!(rule["name"], "any_is_na");
+    assert_eq!(rule["categories"], serde_json::json!(["PERF"]));
+    assert_eq!(rule["fix"], "safe");
+    assert_eq!(rule["default_severity"], "warning");
+    assert_eq!(rule["minimum_r_version"], serde_json::Value::Null);
+    assert!(
+        rule["documentation"]
+            .as_str()
+            .unwrap()
+            .starts_with("## What it does")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_rule() {
+    let output = Command::new(binary_path())
+        .arg("rule")
+        .arg("not_a_rule")
+        .run();
+
+    assert_eq!(output.status.code(), Some(255));
+    assert!(
+        output
+            .stderr
+            .contains("Unknown rule `not_a_rule`. Use `jarl rules` to list all rules.")
+    );
+}
+
+#[test]
+fn test_rules() -> anyhow::Result<()> {
+    let output = Command::new(binary_path()).arg("rules").run();
+
+    assert_eq!(output.status.code(), Some(0));
+    let lines = output.stdout.lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with("Name"));
+    let coalesce = lines
+        .iter()
+        .find(|line| line.starts_with("coalesce "))
+        .unwrap();
+    assert!(coalesce.contains("READ"));
+    assert!(coalesce.ends_with("4.4.0"));
+
+    let output = Command::new(binary_path())
+        .arg("rules")
+        .arg("--output-format")
+        .arg("json")
+        .run();
+
+    assert_eq!(output.status.code(), Some(0));
+    let rules: Vec<serde_json::Value> = serde_json::from_str(&output.stdout)?;
+    assert_eq!(rules.len(), lines.len() - 1);
+    let lengths = rules.iter().find(|rule| rule["name"] == "lengths").unwrap();
+    assert_eq!(lengths["categories"], serde_json::json!(["PERF", "READ"]));
+    assert!(rules.iter().all(|rule| rule.get("documentation").is_none()));
+
+    Ok(())
+}
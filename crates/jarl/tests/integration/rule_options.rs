@@ -0,0 +1,130 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_duplicated_arguments_allowed_functions() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("test.R"),
+        "c(a = 1, a = 2)\nlist2(a = 1, a = 2)\nlist(a = 1, a = 2)\n",
+    )?;
+
+    let check = || {
+        Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--select-rules")
+            .arg("duplicated_arguments")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+    };
+
+    let output = check();
+    assert!(!output.stdout.contains("test.R [1:1]"));
+    assert!(output.stdout.contains("test.R [2:1]"));
+    assert!(output.stdout.contains("test.R [3:1]"));
+
+    // The list of functions replaces the default one, and supports prefixes.
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint.rules.duplicated_arguments]
+allowed-functions = ["list*"]
+"#,
+    )?;
+
+    let output = check();
+    assert!(output.stdout.contains("test.R [1:1]"));
+    assert!(!output.stdout.contains("test.R [2:1]"));
+    assert!(!output.stdout.contains("test.R [3:1]"));
+
+    Ok(())
+}
+
+#[test]
+fn test_implicit_assignment_options() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("test.R"),
+        "if (x <- 1) 1\nwhile (x <- 1) 1\nexpect_error(x <- 1)\nlocal(x <- 1)\n",
+    )?;
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint]
+select = ["implicit_assignment"]
+
+[lint.rules.implicit_assignment]
+allowed-functions = ["local"]
+allowed-contexts = ["while"]
+"#,
+    )?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .arg("--output-format")
+        .arg("concise")
+        .run();
+
+    assert!(output.stdout.contains("test.R [1:5]"));
+    assert!(!output.stdout.contains("test.R [2:"));
+    assert!(output.stdout.contains("test.R [3:14]"));
+    assert!(!output.stdout.contains("test.R [4:"));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_rule_options() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), "any(is.na(x))\n")?;
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint.rules.implicit_assignment]
+allowed-contexts = ["repeat"]
+"#,
+    )?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .run();
+
+    assert_eq!(output.status.code(), Some(255));
+    assert!(output.stderr.contains("unknown variant `repeat`"));
+
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint.rules.any_is_na]
+foo = true
+"#,
+    )?;
+
+    let output = Command::new(binary_path())
+        .current_dir(directory)
+        .arg("check")
+        .arg(".")
+        .run();
+
+    assert_eq!(output.status.code(), Some(255));
+    assert!(output.stderr.contains("unknown field `any_is_na`"));
+
+    Ok(())
+}
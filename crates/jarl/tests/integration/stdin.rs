@@ -0,0 +1,134 @@
+use std::process::Command;
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_check_stdin() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg("-")
+            .arg("--output-format")
+            .arg("concise")
+            .run_with_stdin("any(is.na(x))\n")
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    // No files are created or modified.
+    assert_eq!(std::fs::read_dir(directory)?.count(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_check_stdin_no_lints() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg("-")
+            .run_with_stdin("anyNA(x)\n")
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_check_stdin_full_output() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    // The code snippet is taken from stdin, not from the disk.
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg("-")
+            .run_with_stdin("any(is.na(x))\n")
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fix_stdin() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg("-")
+            .arg("--fix")
+            .run_with_stdin("x <- 1\nany(is.na(x))\n")
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stdin_filename_uses_toml() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::create_dir(directory.join("R"))?;
+    std::fs::write(
+        directory.join("jarl.toml"),
+        r#"
+[lint]
+ignore = ["any_is_na"]
+"#,
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg("-")
+            .arg("--stdin-filename")
+            .arg("R/foo.R")
+            .arg("--output-format")
+            .arg("concise")
+            .run_with_stdin("any(is.na(x))\n")
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_stdin_with_other_paths() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg("-")
+            .arg(".")
+            .run_with_stdin("any(is.na(x))\n")
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+
+    Ok(())
+}
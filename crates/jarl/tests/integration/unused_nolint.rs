@@ -0,0 +1,123 @@
+use std::process::Command;
+
+use tempfile::TempDir;
+
+use crate::helpers::CommandExt;
+use crate::helpers::binary_path;
+
+#[test]
+fn test_unused_nolint_not_reported_by_default() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), "x <- 1 # nolint\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unused_nolint() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("test.R"),
+        "x <- 1 # nolint\nany(is.na(x)) # nolint: any_is_na, foo\nany(is.na(x)) # nolint: any_is_na\n# nolint end\n# nolint start\n",
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--report-unused-nolint")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unused_nolint_fix() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(
+        directory.join("test.R"),
+        "x <- 1 # nolint\n# nolint start\ny <- 2\n# nolint end\nany(is.na(x)) # nolint: any_is_na, foo\n",
+    )?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--report-unused-nolint")
+            .arg("--fix")
+            .arg("--allow-no-vcs")
+            .arg("--output-format")
+            .arg("concise")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    assert_eq!(
+        std::fs::read_to_string(directory.join("test.R"))?,
+        "x <- 1\ny <- 2\nany(is.na(x)) # nolint: any_is_na, foo\n"
+    );
+
+    // Unknown rules may be lintr linters, they are only removed with unsafe
+    // fixes
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--report-unused-nolint")
+            .arg("--fix")
+            .arg("--unsafe-fixes")
+            .arg("--allow-no-vcs")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    assert_eq!(
+        std::fs::read_to_string(directory.join("test.R"))?,
+        "x <- 1\ny <- 2\nany(is.na(x)) # nolint: any_is_na\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unused_nolint_ignores_unselected_rules() -> anyhow::Result<()> {
+    let directory = TempDir::new()?;
+    let directory = directory.path();
+
+    std::fs::write(directory.join("test.R"), "x <- 1 # nolint: any_is_na\n")?;
+
+    insta::assert_snapshot!(
+        &mut Command::new(binary_path())
+            .current_dir(directory)
+            .arg("check")
+            .arg(".")
+            .arg("--report-unused-nolint")
+            .arg("--select-rules")
+            .arg("any_duplicated")
+            .run()
+            .normalize_os_executable_name()
+            .normalize_temp_paths()
+    );
+    Ok(())
+}